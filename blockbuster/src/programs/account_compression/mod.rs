@@ -0,0 +1,130 @@
+use crate::{
+    error::BlockbusterError,
+    program_handler::{ParseResult, ProgramParser},
+    programs::ProgramParseResult,
+};
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use spl_account_compression::state::{
+    merkle_tree_get_size, CompressionAccountType, ConcurrentMerkleTreeHeader,
+    CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
+
+/// Size of a single canopy node, the canopy stores the upper levels of the tree as raw hashes.
+const CANOPY_NODE_SIZE: usize = 32;
+
+/// Header of a concurrent merkle tree account owned by either the SPL or the MPL account
+/// compression program. Both programs share the same account layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcurrentMerkleTreeAccount {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub creation_slot: u64,
+    pub canopy_depth: u32,
+    /// Number of leaves appended to the tree, taken from the index of its rightmost proof.
+    pub num_leaves: u32,
+}
+
+impl ParseResult for ConcurrentMerkleTreeAccount {
+    fn result_type(&self) -> ProgramParseResult {
+        ProgramParseResult::ConcurrentMerkleTree(self)
+    }
+}
+
+impl ConcurrentMerkleTreeAccount {
+    pub fn try_unpack_data(data: &[u8]) -> Result<Self, BlockbusterError> {
+        if data.len() < CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 {
+            return Err(BlockbusterError::InvalidDataLength);
+        }
+        let (header_bytes, rest) = data.split_at(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes)?;
+        if header.account_type != CompressionAccountType::ConcurrentMerkleTree {
+            return Err(BlockbusterError::UninitializedAccount);
+        }
+
+        let tree_size = merkle_tree_get_size(&header)
+            .map_err(|e| BlockbusterError::CustomDeserializationError(e.to_string()))?;
+        let canopy_bytes = rest
+            .len()
+            .checked_sub(tree_size)
+            .ok_or(BlockbusterError::InvalidDataLength)?;
+
+        // The tree ends with its rightmost proof, whose index is followed by 4 bytes of padding.
+        let num_leaves = rest
+            .get(tree_size - 8..tree_size - 4)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or(BlockbusterError::InvalidDataLength)?;
+
+        Ok(Self {
+            max_depth: header.get_max_depth(),
+            max_buffer_size: header.get_max_buffer_size(),
+            creation_slot: header.get_creation_slot(),
+            canopy_depth: canopy_depth(canopy_bytes / CANOPY_NODE_SIZE),
+            num_leaves,
+        })
+    }
+}
+
+// A canopy of depth `d` holds `2^(d + 1) - 2` nodes, the root itself is not stored.
+fn canopy_depth(canopy_nodes: usize) -> u32 {
+    (canopy_nodes + 2).ilog2() - 1
+}
+
+pub struct SplAccountCompressionParser;
+
+impl ProgramParser for SplAccountCompressionParser {
+    fn key(&self) -> Pubkey {
+        spl_account_compression::id()
+    }
+
+    fn key_match(&self, key: &Pubkey) -> bool {
+        key == &spl_account_compression::id()
+    }
+
+    fn handles_account_updates(&self) -> bool {
+        true
+    }
+
+    fn handles_instructions(&self) -> bool {
+        false
+    }
+
+    fn handle_account(
+        &self,
+        account_data: &[u8],
+    ) -> Result<Box<(dyn ParseResult + 'static)>, BlockbusterError> {
+        Ok(Box::new(ConcurrentMerkleTreeAccount::try_unpack_data(
+            account_data,
+        )?))
+    }
+}
+
+pub struct MplAccountCompressionParser;
+
+impl ProgramParser for MplAccountCompressionParser {
+    fn key(&self) -> Pubkey {
+        mpl_account_compression::id()
+    }
+
+    fn key_match(&self, key: &Pubkey) -> bool {
+        key == &mpl_account_compression::id()
+    }
+
+    fn handles_account_updates(&self) -> bool {
+        true
+    }
+
+    fn handles_instructions(&self) -> bool {
+        false
+    }
+
+    fn handle_account(
+        &self,
+        account_data: &[u8],
+    ) -> Result<Box<(dyn ParseResult + 'static)>, BlockbusterError> {
+        Ok(Box::new(ConcurrentMerkleTreeAccount::try_unpack_data(
+            account_data,
+        )?))
+    }
+}
@@ -9,18 +9,23 @@ use log::warn;
 use mpl_bubblegum::{
     get_instruction_type,
     instructions::{
-        MintV2InstructionArgs, UnverifyCreatorInstructionArgs, UnverifyCreatorV2InstructionArgs,
-        UpdateMetadataInstructionArgs, UpdateMetadataV2InstructionArgs,
-        VerifyCreatorInstructionArgs, VerifyCreatorV2InstructionArgs,
+        CreateTreeConfigInstructionArgs, CreateTreeConfigV2InstructionArgs, MintV2InstructionArgs,
+        UnverifyCreatorInstructionArgs, UnverifyCreatorV2InstructionArgs,
+        UpdateAssetDataV2InstructionArgs, UpdateMetadataInstructionArgs,
+        UpdateMetadataV2InstructionArgs, VerifyCreatorInstructionArgs,
+        VerifyCreatorV2InstructionArgs,
     },
     types::{BubblegumEventType, MetadataArgs, UpdateArgs},
 };
 pub use mpl_bubblegum::{
-    types::{LeafSchema, UseMethod},
+    types::{AssetDataSchema, LeafSchema, UseMethod},
     InstructionName, LeafSchemaEvent, ID,
 };
 use solana_sdk::pubkey::Pubkey;
 
+// `get_instruction_type` does not map `SetTreeDelegate`, so it is matched on its discriminator.
+const SET_TREE_DELEGATE_DISCRIMINATOR: [u8; 8] = [253, 118, 66, 37, 190, 49, 154, 102];
+
 #[derive(Eq, PartialEq)]
 pub enum Payload {
     Unknown,
@@ -28,6 +33,8 @@ pub enum Payload {
         args: MetadataArgs,
         authority: Pubkey,
         tree_id: Pubkey,
+        asset_data: Option<Vec<u8>>,
+        asset_data_schema: Option<AssetDataSchema>,
     },
     Decompress {
         args: MetadataArgs,
@@ -49,6 +56,22 @@ pub enum Payload {
         update_args: UpdateArgs,
         tree_id: Pubkey,
     },
+    UpdateAssetData {
+        asset_data: Option<Vec<u8>>,
+        asset_data_schema: Option<AssetDataSchema>,
+        tree_id: Pubkey,
+    },
+    CreateTree {
+        tree_id: Pubkey,
+        tree_creator: Pubkey,
+        max_depth: u32,
+        max_buffer_size: u32,
+        public: bool,
+    },
+    SetTreeDelegate {
+        tree_id: Pubkey,
+        new_tree_delegate: Pubkey,
+    },
 }
 //TODO add more of the parsing here to minimize program transformer code
 pub struct BubblegumInstruction {
@@ -182,6 +205,12 @@ impl ProgramParser for BubblegumParser {
             }
         }
 
+        if matches!(b_inst.instruction, InstructionName::Unknown)
+            && outer_ix_data.starts_with(&SET_TREE_DELEGATE_DISCRIMINATOR)
+        {
+            b_inst.payload = Some(build_set_tree_delegate_payload(keys)?);
+        }
+
         if outer_ix_data.len() >= 8 {
             let ix_data = &outer_ix_data[8..];
             if !ix_data.is_empty() {
@@ -238,7 +267,15 @@ impl ProgramParser for BubblegumParser {
                     InstructionName::UpdateMetadataV2 => {
                         b_inst.payload = Some(build_update_metadata_v2_payload(keys, ix_data)?);
                     }
-                    InstructionName::UpdateAssetDataV2 => {} // Not supported
+                    InstructionName::UpdateAssetDataV2 => {
+                        b_inst.payload = Some(build_update_asset_data_v2_payload(keys, ix_data)?);
+                    }
+                    InstructionName::CreateTree => {
+                        b_inst.payload = Some(build_create_tree_payload(keys, ix_data)?);
+                    }
+                    InstructionName::CreateTreeV2 => {
+                        b_inst.payload = Some(build_create_tree_v2_payload(keys, ix_data)?);
+                    }
                     _ => {}
                 };
             }
@@ -344,6 +381,8 @@ fn build_mint_v1_payload(
         args,
         authority,
         tree_id,
+        asset_data: None,
+        asset_data_schema: None,
     })
 }
 
@@ -383,6 +422,8 @@ fn build_mint_v2_payload(keys: &[Pubkey], ix_data: &[u8]) -> Result<Payload, Blo
         args: args.metadata.into(),
         authority,
         tree_id,
+        asset_data: args.asset_data,
+        asset_data_schema: args.asset_data_schema,
     })
 }
 
@@ -435,6 +476,25 @@ fn build_creator_verification_v2_payload(
     })
 }
 
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md
+fn build_update_asset_data_v2_payload(
+    keys: &[Pubkey],
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = UpdateAssetDataV2InstructionArgs::try_from_slice(ix_data)?;
+
+    let tree_id = *keys
+        .get(5)
+        .ok_or(BlockbusterError::InstructionParsingError)?;
+
+    Ok(Payload::UpdateAssetData {
+        asset_data: args.new_asset_data,
+        asset_data_schema: args.new_asset_data_schema,
+        tree_id,
+    })
+}
+
 // See Bubblegum for offsets and positions:
 // https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md
 fn build_update_metadata_v2_payload(
@@ -453,3 +513,79 @@ fn build_update_metadata_v2_payload(
         tree_id,
     })
 }
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-create_tree
+fn build_create_tree_payload(keys: &[Pubkey], ix_data: &[u8]) -> Result<Payload, BlockbusterError> {
+    let args = CreateTreeConfigInstructionArgs::try_from_slice(ix_data)?;
+
+    let tree_id = *keys
+        .get(1)
+        .ok_or(BlockbusterError::InstructionParsingError)?;
+
+    let tree_creator = *keys
+        .get(3)
+        .ok_or(BlockbusterError::InstructionParsingError)?;
+
+    Ok(Payload::CreateTree {
+        tree_id,
+        tree_creator,
+        max_depth: args.max_depth,
+        max_buffer_size: args.max_buffer_size,
+        public: args.public.unwrap_or(false),
+    })
+}
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-create_tree
+fn build_create_tree_v2_payload(
+    keys: &[Pubkey],
+    ix_data: &[u8],
+) -> Result<Payload, BlockbusterError> {
+    let args = CreateTreeConfigV2InstructionArgs::try_from_slice(ix_data)?;
+
+    let tree_id = *keys
+        .get(1)
+        .ok_or(BlockbusterError::InstructionParsingError)?;
+
+    let payer = *keys
+        .get(2)
+        .ok_or(BlockbusterError::InstructionParsingError)?;
+
+    let tree_creator = *keys
+        .get(3)
+        .ok_or(BlockbusterError::InstructionParsingError)?;
+
+    // Tree creator is optional in V2, None being signified by the program ID.
+    // Tree creator defaults to the payer.
+    let tree_creator = if tree_creator == mpl_bubblegum::ID {
+        payer
+    } else {
+        tree_creator
+    };
+
+    Ok(Payload::CreateTree {
+        tree_id,
+        tree_creator,
+        max_depth: args.max_depth,
+        max_buffer_size: args.max_buffer_size,
+        public: args.public.unwrap_or(false),
+    })
+}
+
+// See Bubblegum for offsets and positions:
+// https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/README.md#-set_tree_delegate
+fn build_set_tree_delegate_payload(keys: &[Pubkey]) -> Result<Payload, BlockbusterError> {
+    let new_tree_delegate = *keys
+        .get(2)
+        .ok_or(BlockbusterError::InstructionParsingError)?;
+
+    let tree_id = *keys
+        .get(3)
+        .ok_or(BlockbusterError::InstructionParsingError)?;
+
+    Ok(Payload::SetTreeDelegate {
+        tree_id,
+        new_tree_delegate,
+    })
+}
@@ -1,10 +1,12 @@
+use account_compression::ConcurrentMerkleTreeAccount;
 use bubblegum::BubblegumInstruction;
 use mpl_core_program::MplCoreAccountState;
 use token_account::TokenProgramEntity;
 use token_extensions::TokenExtensionsProgramEntity;
 use token_inscriptions::TokenInscriptionAccount;
-use token_metadata::TokenMetadataAccountState;
+use token_metadata::{TokenMetadataAccountState, TokenMetadataInstruction};
 
+pub mod account_compression;
 pub mod bubblegum;
 pub mod mpl_core_program;
 pub mod token_account;
@@ -30,8 +32,10 @@ pub enum ProgramParseResult<'a> {
     Bubblegum(&'a BubblegumInstruction),
     MplCore(&'a MplCoreAccountState),
     TokenMetadata(&'a TokenMetadataAccountState),
+    TokenMetadataInstruction(&'a TokenMetadataInstruction),
     TokenProgramEntity(&'a TokenProgramEntity),
     TokenExtensionsProgramEntity(&'a TokenExtensionsProgramEntity),
     TokenInscriptionAccount(&'a TokenInscriptionAccount),
+    ConcurrentMerkleTree(&'a ConcurrentMerkleTreeAccount),
     Unknown,
 }
@@ -9,6 +9,47 @@ use solana_sdk::{pubkey::Pubkey, pubkeys};
 
 pubkeys!(mpl_core_id, "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
 
+/// Name of an MPL Core instruction, from its discriminator.
+pub fn instruction_name(data: &[u8]) -> Option<&'static str> {
+    let name = match *data.first()? {
+        0 => "CreateV1",
+        1 => "CreateCollectionV1",
+        2 => "AddPluginV1",
+        3 => "AddCollectionPluginV1",
+        4 => "RemovePluginV1",
+        5 => "RemoveCollectionPluginV1",
+        6 => "UpdatePluginV1",
+        7 => "UpdateCollectionPluginV1",
+        8 => "ApprovePluginAuthorityV1",
+        9 => "ApproveCollectionPluginAuthorityV1",
+        10 => "RevokePluginAuthorityV1",
+        11 => "RevokeCollectionPluginAuthorityV1",
+        12 => "BurnV1",
+        13 => "BurnCollectionV1",
+        14 => "TransferV1",
+        15 => "UpdateV1",
+        16 => "UpdateCollectionV1",
+        17 => "CompressV1",
+        18 => "DecompressV1",
+        19 => "Collect",
+        20 => "CreateV2",
+        21 => "CreateCollectionV2",
+        22 => "AddExternalPluginAdapterV1",
+        23 => "AddCollectionExternalPluginAdapterV1",
+        24 => "RemoveExternalPluginAdapterV1",
+        25 => "RemoveCollectionExternalPluginAdapterV1",
+        26 => "UpdateExternalPluginAdapterV1",
+        27 => "UpdateCollectionExternalPluginAdapterV1",
+        28 => "WriteExternalPluginAdapterDataV1",
+        29 => "WriteCollectionExternalPluginAdapterDataV1",
+        30 => "UpdateV2",
+        31 => "ExecuteV1",
+        32 => "UpdateCollectionInfoV1",
+        _ => return None,
+    };
+    Some(name)
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum MplCoreAccountData {
     Asset(IndexableAsset),
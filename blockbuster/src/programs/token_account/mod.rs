@@ -5,13 +5,48 @@ use crate::{
     programs::ProgramParseResult,
 };
 use solana_sdk::{program_pack::Pack, pubkey::Pubkey, pubkeys};
-use spl_token::state::{Account as TokenAccount, Mint};
+use spl_token::{
+    instruction::TokenInstruction,
+    state::{Account as TokenAccount, Mint},
+};
 
 pubkeys!(
     token_program_id,
     "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
 );
 
+/// Name of a SPL Token instruction, `None` when its data cannot be unpacked.
+pub fn instruction_name(data: &[u8]) -> Option<&'static str> {
+    let name = match TokenInstruction::unpack(data).ok()? {
+        TokenInstruction::InitializeMint { .. } => "InitializeMint",
+        TokenInstruction::InitializeAccount { .. } => "InitializeAccount",
+        TokenInstruction::InitializeMultisig { .. } => "InitializeMultisig",
+        TokenInstruction::Transfer { .. } => "Transfer",
+        TokenInstruction::Approve { .. } => "Approve",
+        TokenInstruction::Revoke { .. } => "Revoke",
+        TokenInstruction::SetAuthority { .. } => "SetAuthority",
+        TokenInstruction::MintTo { .. } => "MintTo",
+        TokenInstruction::Burn { .. } => "Burn",
+        TokenInstruction::CloseAccount { .. } => "CloseAccount",
+        TokenInstruction::FreezeAccount { .. } => "FreezeAccount",
+        TokenInstruction::ThawAccount { .. } => "ThawAccount",
+        TokenInstruction::TransferChecked { .. } => "TransferChecked",
+        TokenInstruction::ApproveChecked { .. } => "ApproveChecked",
+        TokenInstruction::MintToChecked { .. } => "MintToChecked",
+        TokenInstruction::BurnChecked { .. } => "BurnChecked",
+        TokenInstruction::InitializeAccount2 { .. } => "InitializeAccount2",
+        TokenInstruction::SyncNative { .. } => "SyncNative",
+        TokenInstruction::InitializeAccount3 { .. } => "InitializeAccount3",
+        TokenInstruction::InitializeMultisig2 { .. } => "InitializeMultisig2",
+        TokenInstruction::InitializeMint2 { .. } => "InitializeMint2",
+        TokenInstruction::GetAccountDataSize { .. } => "GetAccountDataSize",
+        TokenInstruction::InitializeImmutableOwner { .. } => "InitializeImmutableOwner",
+        TokenInstruction::AmountToUiAmount { .. } => "AmountToUiAmount",
+        TokenInstruction::UiAmountToAmount { .. } => "UiAmountToAmount",
+    };
+    Some(name)
+}
+
 pub struct TokenProgramParser;
 
 pub enum TokenProgramEntity {
@@ -27,6 +27,7 @@ use spl_token_2022::{
         transfer_hook::TransferHook,
         BaseStateWithExtensions, StateWithExtensions,
     },
+    instruction::TokenInstruction,
     state::{Account, Mint},
 };
 use spl_token_group_interface::state::{TokenGroup, TokenGroupMember};
@@ -117,6 +118,59 @@ pubkeys!(
     "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
 );
 
+/// Name of a Token-2022 instruction, `None` when its data cannot be unpacked. Extension
+/// instructions are named after their extension.
+#[allow(deprecated)]
+pub fn instruction_name(data: &[u8]) -> Option<&'static str> {
+    let name = match TokenInstruction::unpack(data).ok()? {
+        TokenInstruction::InitializeMint { .. } => "InitializeMint",
+        TokenInstruction::InitializeAccount { .. } => "InitializeAccount",
+        TokenInstruction::InitializeMultisig { .. } => "InitializeMultisig",
+        TokenInstruction::Transfer { .. } => "Transfer",
+        TokenInstruction::Approve { .. } => "Approve",
+        TokenInstruction::Revoke { .. } => "Revoke",
+        TokenInstruction::SetAuthority { .. } => "SetAuthority",
+        TokenInstruction::MintTo { .. } => "MintTo",
+        TokenInstruction::Burn { .. } => "Burn",
+        TokenInstruction::CloseAccount { .. } => "CloseAccount",
+        TokenInstruction::FreezeAccount { .. } => "FreezeAccount",
+        TokenInstruction::ThawAccount { .. } => "ThawAccount",
+        TokenInstruction::TransferChecked { .. } => "TransferChecked",
+        TokenInstruction::ApproveChecked { .. } => "ApproveChecked",
+        TokenInstruction::MintToChecked { .. } => "MintToChecked",
+        TokenInstruction::BurnChecked { .. } => "BurnChecked",
+        TokenInstruction::InitializeAccount2 { .. } => "InitializeAccount2",
+        TokenInstruction::SyncNative { .. } => "SyncNative",
+        TokenInstruction::InitializeAccount3 { .. } => "InitializeAccount3",
+        TokenInstruction::InitializeMultisig2 { .. } => "InitializeMultisig2",
+        TokenInstruction::InitializeMint2 { .. } => "InitializeMint2",
+        TokenInstruction::GetAccountDataSize { .. } => "GetAccountDataSize",
+        TokenInstruction::InitializeImmutableOwner { .. } => "InitializeImmutableOwner",
+        TokenInstruction::AmountToUiAmount { .. } => "AmountToUiAmount",
+        TokenInstruction::UiAmountToAmount { .. } => "UiAmountToAmount",
+        TokenInstruction::InitializeMintCloseAuthority { .. } => "InitializeMintCloseAuthority",
+        TokenInstruction::TransferFeeExtension { .. } => "TransferFeeExtension",
+        TokenInstruction::ConfidentialTransferExtension { .. } => "ConfidentialTransferExtension",
+        TokenInstruction::DefaultAccountStateExtension { .. } => "DefaultAccountStateExtension",
+        TokenInstruction::Reallocate { .. } => "Reallocate",
+        TokenInstruction::MemoTransferExtension { .. } => "MemoTransferExtension",
+        TokenInstruction::CreateNativeMint { .. } => "CreateNativeMint",
+        TokenInstruction::InitializeNonTransferableMint { .. } => "InitializeNonTransferableMint",
+        TokenInstruction::InterestBearingMintExtension { .. } => "InterestBearingMintExtension",
+        TokenInstruction::CpiGuardExtension { .. } => "CpiGuardExtension",
+        TokenInstruction::InitializePermanentDelegate { .. } => "InitializePermanentDelegate",
+        TokenInstruction::TransferHookExtension { .. } => "TransferHookExtension",
+        TokenInstruction::ConfidentialTransferFeeExtension { .. } => {
+            "ConfidentialTransferFeeExtension"
+        }
+        TokenInstruction::WithdrawExcessLamports { .. } => "WithdrawExcessLamports",
+        TokenInstruction::MetadataPointerExtension { .. } => "MetadataPointerExtension",
+        TokenInstruction::GroupPointerExtension { .. } => "GroupPointerExtension",
+        TokenInstruction::GroupMemberPointerExtension { .. } => "GroupMemberPointerExtension",
+    };
+    Some(name)
+}
+
 pub struct Token2022ProgramParser;
 
 #[allow(clippy::large_enum_variant)]
@@ -1,13 +1,14 @@
 use crate::{
     error::BlockbusterError,
-    program_handler::{ParseResult, ProgramParser},
+    instruction::InstructionBundle,
+    program_handler::{NotUsed, ParseResult, ProgramParser},
     programs::ProgramParseResult,
 };
 use borsh::BorshDeserialize;
 use mpl_token_metadata::{
     accounts::{
         CollectionAuthorityRecord, DeprecatedMasterEditionV1, Edition, EditionMarker,
-        MasterEdition, Metadata, UseAuthorityRecord,
+        MasterEdition, Metadata, MetadataDelegateRecord, TokenRecord, UseAuthorityRecord,
     },
     types::Key,
 };
@@ -20,6 +21,76 @@ pubkeys!(
     "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
 );
 
+const APPROVE_USE_AUTHORITY_DISCRIMINATOR: u8 = 20;
+const APPROVE_COLLECTION_AUTHORITY_DISCRIMINATOR: u8 = 23;
+
+/// Name of a Token Metadata instruction, from its discriminator. Versioned instructions such as
+/// `BurnV1` share the discriminator of their base instruction and are named after it.
+pub fn instruction_name(data: &[u8]) -> Option<&'static str> {
+    let name = match *data.first()? {
+        0 => "CreateMetadataAccount",
+        1 => "UpdateMetadataAccount",
+        2 => "DeprecatedCreateMasterEdition",
+        3 => "DeprecatedMintNewEditionFromMasterEditionViaPrintingToken",
+        4 => "UpdatePrimarySaleHappenedViaToken",
+        5 => "DeprecatedSetReservationList",
+        6 => "DeprecatedCreateReservationList",
+        7 => "SignMetadata",
+        8 => "DeprecatedMintPrintingTokensViaToken",
+        9 => "DeprecatedMintPrintingTokens",
+        10 => "CreateMasterEdition",
+        11 => "MintNewEditionFromMasterEditionViaToken",
+        12 => "ConvertMasterEditionV1ToV2",
+        13 => "MintNewEditionFromMasterEditionViaVaultProxy",
+        14 => "PuffMetadata",
+        15 => "UpdateMetadataAccountV2",
+        16 => "CreateMetadataAccountV2",
+        17 => "CreateMasterEditionV3",
+        18 => "VerifyCollection",
+        19 => "Utilize",
+        APPROVE_USE_AUTHORITY_DISCRIMINATOR => "ApproveUseAuthority",
+        21 => "RevokeUseAuthority",
+        22 => "UnverifyCollection",
+        APPROVE_COLLECTION_AUTHORITY_DISCRIMINATOR => "ApproveCollectionAuthority",
+        24 => "RevokeCollectionAuthority",
+        25 => "SetAndVerifyCollection",
+        26 => "FreezeDelegatedAccount",
+        27 => "ThawDelegatedAccount",
+        28 => "RemoveCreatorVerification",
+        29 => "BurnNft",
+        30 => "VerifySizedCollectionItem",
+        31 => "UnverifySizedCollectionItem",
+        32 => "SetAndVerifySizedCollectionItem",
+        33 => "CreateMetadataAccountV3",
+        34 => "SetCollectionSize",
+        35 => "SetTokenStandard",
+        36 => "BubblegumSetCollectionSize",
+        37 => "BurnEditionNft",
+        38 => "CreateEscrowAccount",
+        39 => "CloseEscrowAccount",
+        40 => "TransferOutOfEscrow",
+        41 => "Burn",
+        42 => "Create",
+        43 => "Mint",
+        44 => "Delegate",
+        45 => "Revoke",
+        46 => "Lock",
+        47 => "Unlock",
+        48 => "Migrate",
+        49 => "Transfer",
+        50 => "Update",
+        51 => "Use",
+        52 => "Verify",
+        53 => "Unverify",
+        54 => "Collect",
+        55 => "Print",
+        56 => "Resize",
+        57 => "CloseAccounts",
+        _ => return None,
+    };
+    Some(name)
+}
+
 #[allow(clippy::large_enum_variant)]
 pub enum TokenMetadataAccountData {
     EditionV1(Edition),
@@ -29,6 +100,8 @@ pub enum TokenMetadataAccountData {
     EditionMarker(EditionMarker),
     UseAuthorityRecord(UseAuthorityRecord),
     CollectionAuthorityRecord(CollectionAuthorityRecord),
+    TokenRecord(TokenRecord),
+    MetadataDelegateRecord(MetadataDelegateRecord),
     EmptyAccount,
 }
 
@@ -49,6 +122,33 @@ impl ParseResult for TokenMetadataAccountState {
     }
 }
 
+/// Token Metadata instructions that link an authority record to its mint and authority. The
+/// record accounts themselves only store a bump and a few settings.
+pub enum TokenMetadataInstruction {
+    ApproveUseAuthority {
+        use_authority_record: Pubkey,
+        mint: Pubkey,
+        user: Pubkey,
+    },
+    ApproveCollectionAuthority {
+        collection_authority_record: Pubkey,
+        mint: Pubkey,
+        new_collection_authority: Pubkey,
+    },
+}
+
+impl ParseResult for TokenMetadataInstruction {
+    fn result(&self) -> &Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+    fn result_type(&self) -> ProgramParseResult {
+        ProgramParseResult::TokenMetadataInstruction(self)
+    }
+}
+
 pub struct TokenMetadataParser;
 
 impl ProgramParser for TokenMetadataParser {
@@ -64,7 +164,7 @@ impl ProgramParser for TokenMetadataParser {
     }
 
     fn handles_instructions(&self) -> bool {
-        false
+        true
     }
 
     fn handle_account(
@@ -133,6 +233,24 @@ impl ProgramParser for TokenMetadataParser {
                     data: TokenMetadataAccountData::CollectionAuthorityRecord(account),
                 }
             }
+            Key::TokenRecord => {
+                #[allow(deprecated)]
+                let account: TokenRecord = try_from_slice_unchecked(account_data)?;
+
+                TokenMetadataAccountState {
+                    key: account.key,
+                    data: TokenMetadataAccountData::TokenRecord(account),
+                }
+            }
+            Key::MetadataDelegate => {
+                #[allow(deprecated)]
+                let account: MetadataDelegateRecord = try_from_slice_unchecked(account_data)?;
+
+                TokenMetadataAccountState {
+                    key: account.key,
+                    data: TokenMetadataAccountData::MetadataDelegateRecord(account),
+                }
+            }
             Key::MetadataV1 => {
                 let account = Metadata::safe_deserialize(account_data)?;
 
@@ -151,4 +269,38 @@ impl ProgramParser for TokenMetadataParser {
 
         Ok(Box::new(token_metadata_account_state))
     }
+
+    fn handle_instruction(
+        &self,
+        bundle: &InstructionBundle,
+    ) -> Result<Box<dyn ParseResult>, BlockbusterError> {
+        let InstructionBundle {
+            keys, instruction, ..
+        } = bundle;
+
+        let discriminator = match instruction.and_then(|ix| ix.data.first()) {
+            Some(discriminator) => *discriminator,
+            None => return Ok(Box::new(NotUsed::new())),
+        };
+
+        let ix = match discriminator {
+            APPROVE_USE_AUTHORITY_DISCRIMINATOR if keys.len() >= 7 => {
+                TokenMetadataInstruction::ApproveUseAuthority {
+                    use_authority_record: keys[0],
+                    user: keys[3],
+                    mint: keys[6],
+                }
+            }
+            APPROVE_COLLECTION_AUTHORITY_DISCRIMINATOR if keys.len() >= 6 => {
+                TokenMetadataInstruction::ApproveCollectionAuthority {
+                    collection_authority_record: keys[0],
+                    new_collection_authority: keys[1],
+                    mint: keys[5],
+                }
+            }
+            _ => return Ok(Box::new(NotUsed::new())),
+        };
+
+        Ok(Box::new(ix))
+    }
 }
@@ -0,0 +1,92 @@
+#[cfg(test)]
+use blockbuster::{
+    program_handler::ProgramParser,
+    programs::{
+        account_compression::{MplAccountCompressionParser, SplAccountCompressionParser},
+        ProgramParseResult,
+    },
+};
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use spl_account_compression::state::{
+    merkle_tree_get_size, ConcurrentMerkleTreeHeader, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
+
+fn tree_account_data(
+    max_depth: u32,
+    max_buffer_size: u32,
+    creation_slot: u64,
+    num_leaves: u32,
+) -> Vec<u8> {
+    let mut data = vec![1, 0];
+    data.extend_from_slice(&max_buffer_size.to_le_bytes());
+    data.extend_from_slice(&max_depth.to_le_bytes());
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(&creation_slot.to_le_bytes());
+    data.resize(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1, 0);
+
+    let header = ConcurrentMerkleTreeHeader::try_from_slice(&data).unwrap();
+    let tree_size = merkle_tree_get_size(&header).unwrap();
+    data.resize(data.len() + tree_size, 0);
+    // Index of the rightmost proof, the last field of the tree before its padding.
+    let index_offset = data.len() - 8;
+    data[index_offset..index_offset + 4].copy_from_slice(&num_leaves.to_le_bytes());
+    data
+}
+
+#[test]
+fn test_setup() {
+    let spl = SplAccountCompressionParser {};
+    assert_eq!(spl.key(), spl_account_compression::id());
+    assert!(spl.key_match(&spl_account_compression::id()));
+
+    let mpl = MplAccountCompressionParser {};
+    assert_eq!(mpl.key(), mpl_account_compression::id());
+    assert!(mpl.key_match(&mpl_account_compression::id()));
+}
+
+#[test]
+fn test_parse_tree_header_with_canopy() {
+    let subject = SplAccountCompressionParser {};
+
+    let mut data = tree_account_data(14, 64, 42, 5);
+    // A canopy of depth 3 stores 2 + 4 + 8 nodes.
+    data.extend_from_slice(&[0; 14 * 32]);
+
+    let result = subject.handle_account(&data).unwrap();
+    if let ProgramParseResult::ConcurrentMerkleTree(tree) = result.result_type() {
+        assert_eq!(tree.max_depth, 14);
+        assert_eq!(tree.max_buffer_size, 64);
+        assert_eq!(tree.creation_slot, 42);
+        assert_eq!(tree.canopy_depth, 3);
+        assert_eq!(tree.num_leaves, 5);
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
+#[test]
+fn test_parse_tree_header_without_canopy() {
+    let subject = MplAccountCompressionParser {};
+
+    let data = tree_account_data(20, 256, 7, 0);
+
+    let result = subject.handle_account(&data).unwrap();
+    if let ProgramParseResult::ConcurrentMerkleTree(tree) = result.result_type() {
+        assert_eq!(tree.max_depth, 20);
+        assert_eq!(tree.canopy_depth, 0);
+        assert_eq!(tree.num_leaves, 0);
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
+#[test]
+fn test_uninitialized_tree_is_rejected() {
+    let subject = SplAccountCompressionParser {};
+
+    let mut data = tree_account_data(14, 64, 42, 0);
+    data[0] = 0;
+
+    assert!(subject.handle_account(&data).is_err());
+}
@@ -1,12 +1,17 @@
 #[cfg(test)]
 use blockbuster::{
     program_handler::ProgramParser,
-    programs::{bubblegum::BubblegumParser, ProgramParseResult},
+    programs::{
+        bubblegum::{BubblegumParser, Payload},
+        ProgramParseResult,
+    },
 };
 use flatbuffers::FlatBufferBuilder;
 use helpers::*;
 use mpl_bubblegum::{
-    instructions::{MintV1InstructionArgs, TransferInstructionArgs},
+    instructions::{
+        CreateTreeConfigV2InstructionArgs, MintV1InstructionArgs, TransferInstructionArgs,
+    },
     types::{BubblegumEventType, Creator, LeafSchema, MetadataArgs, TokenProgramVersion, Version},
     LeafSchemaEvent,
 };
@@ -129,6 +134,93 @@ fn test_mint() {
     }
 }
 
+#[test]
+fn test_create_tree_v2_defaults_creator_to_payer() {
+    let subject = BubblegumParser {};
+
+    let mut accounts = random_list_of(8, |_i| random_pubkey());
+    // An omitted optional tree creator is passed as the Bubblegum program ID.
+    accounts[3] = mpl_bubblegum::ID;
+    let fb_account_indexes: Vec<u8> = accounts.iter().enumerate().map(|(i, _)| i as u8).collect();
+
+    let ix = mpl_bubblegum::instructions::CreateTreeConfigV2 {
+        tree_config: accounts[0],
+        merkle_tree: accounts[1],
+        payer: accounts[2],
+        tree_creator: None,
+        log_wrapper: accounts[4],
+        compression_program: accounts[5],
+        system_program: accounts[6],
+    };
+    let ix_data = ix
+        .instruction(CreateTreeConfigV2InstructionArgs {
+            max_depth: 14,
+            max_buffer_size: 64,
+            public: Some(true),
+        })
+        .data;
+
+    let lse = LeafSchemaEvent {
+        event_type: BubblegumEventType::LeafSchemaEvent,
+        version: Version::V1,
+        schema: LeafSchema::V1 {
+            id: random_pubkey(),
+            owner: random_pubkey(),
+            delegate: random_pubkey(),
+            nonce: 0,
+            data_hash: [0; 32],
+            creator_hash: [0; 32],
+        },
+        leaf_hash: [0; 32],
+    };
+    let cs_event =
+        AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(accounts[1], vec![], 0, 0));
+
+    let mut fbb1 = FlatBufferBuilder::new();
+    let mut fbb2 = FlatBufferBuilder::new();
+    let mut fbb3 = FlatBufferBuilder::new();
+    let mut fbb4 = FlatBufferBuilder::new();
+
+    let ix_b = build_bubblegum_bundle(
+        &mut fbb1,
+        &mut fbb2,
+        &mut fbb3,
+        &mut fbb4,
+        &accounts,
+        &fb_account_indexes,
+        &ix_data,
+        lse,
+        cs_event,
+    );
+
+    let result = subject.handle_instruction(&ix_b);
+
+    if let ProgramParseResult::Bubblegum(b) = result.unwrap().result_type() {
+        assert!(matches!(
+            b.instruction,
+            mpl_bubblegum::InstructionName::CreateTreeV2
+        ));
+        match &b.payload {
+            Some(Payload::CreateTree {
+                tree_id,
+                tree_creator,
+                max_depth,
+                max_buffer_size,
+                public,
+            }) => {
+                assert_eq!(*tree_id, accounts[1]);
+                assert_eq!(*tree_creator, accounts[2]);
+                assert_eq!(*max_depth, 14);
+                assert_eq!(*max_buffer_size, 64);
+                assert!(*public);
+            }
+            _ => panic!("Expected CreateTree payload"),
+        }
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
 #[test]
 fn test_basic_success_parsing() {
     let subject = BubblegumParser {};
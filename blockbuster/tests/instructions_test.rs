@@ -122,6 +122,8 @@ fn helium_nested() {
                 args: _,
                 authority: _,
                 tree_id: _,
+                asset_data: _,
+                asset_data_schema: _,
             }),
         ) = (
             &parse_result.leaf_update,
@@ -0,0 +1,274 @@
+#[cfg(test)]
+use blockbuster::{
+    instruction::InstructionBundle,
+    program_handler::ProgramParser,
+    programs::{
+        token_account,
+        token_metadata::{
+            self, TokenMetadataAccountData, TokenMetadataInstruction, TokenMetadataParser,
+        },
+        ProgramParseResult,
+    },
+};
+use borsh::BorshSerialize;
+use mpl_token_metadata::{
+    accounts::{MetadataDelegateRecord, TokenRecord},
+    instructions::{
+        ApproveCollectionAuthority, ApproveUseAuthority, ApproveUseAuthorityInstructionArgs,
+    },
+    types::{Key, TokenDelegateRole, TokenState},
+};
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+
+fn compile(data: Vec<u8>, keys: &[Pubkey]) -> CompiledInstruction {
+    CompiledInstruction {
+        program_id_index: keys.len() as u8,
+        accounts: (0..keys.len() as u8).collect(),
+        data,
+    }
+}
+
+#[test]
+fn test_setup() {
+    let subject = TokenMetadataParser {};
+    assert_eq!(subject.key(), mpl_token_metadata::ID);
+    assert!(subject.key_match(&mpl_token_metadata::ID));
+    assert!(subject.handles_instructions());
+}
+
+#[test]
+fn test_approve_use_authority() {
+    let subject = TokenMetadataParser {};
+
+    let ix = ApproveUseAuthority {
+        use_authority_record: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        payer: Pubkey::new_unique(),
+        user: Pubkey::new_unique(),
+        owner_token_account: Pubkey::new_unique(),
+        metadata: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        burner: Pubkey::new_unique(),
+        token_program: spl_token::id(),
+        system_program: solana_sdk::system_program::id(),
+        rent: None,
+    };
+    let data = ix
+        .instruction(ApproveUseAuthorityInstructionArgs { number_of_uses: 3 })
+        .data;
+    let keys = [
+        ix.use_authority_record,
+        ix.owner,
+        ix.payer,
+        ix.user,
+        ix.owner_token_account,
+        ix.metadata,
+        ix.mint,
+        ix.burner,
+        ix.token_program,
+        ix.system_program,
+    ];
+    let compiled = compile(data, &keys);
+    let bundle = InstructionBundle {
+        instruction: Some(&compiled),
+        keys: &keys,
+        ..Default::default()
+    };
+
+    let result = subject.handle_instruction(&bundle).unwrap();
+    if let ProgramParseResult::TokenMetadataInstruction(
+        TokenMetadataInstruction::ApproveUseAuthority {
+            use_authority_record,
+            mint,
+            user,
+        },
+    ) = result.result_type()
+    {
+        assert_eq!(*use_authority_record, ix.use_authority_record);
+        assert_eq!(*mint, ix.mint);
+        assert_eq!(*user, ix.user);
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
+#[test]
+fn test_approve_collection_authority() {
+    let subject = TokenMetadataParser {};
+
+    let ix = ApproveCollectionAuthority {
+        collection_authority_record: Pubkey::new_unique(),
+        new_collection_authority: Pubkey::new_unique(),
+        update_authority: Pubkey::new_unique(),
+        payer: Pubkey::new_unique(),
+        metadata: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        system_program: solana_sdk::system_program::id(),
+        rent: None,
+    };
+    let data = ix.instruction().data;
+    let keys = [
+        ix.collection_authority_record,
+        ix.new_collection_authority,
+        ix.update_authority,
+        ix.payer,
+        ix.metadata,
+        ix.mint,
+        ix.system_program,
+    ];
+    let compiled = compile(data, &keys);
+    let bundle = InstructionBundle {
+        instruction: Some(&compiled),
+        keys: &keys,
+        ..Default::default()
+    };
+
+    let result = subject.handle_instruction(&bundle).unwrap();
+    if let ProgramParseResult::TokenMetadataInstruction(
+        TokenMetadataInstruction::ApproveCollectionAuthority {
+            collection_authority_record,
+            mint,
+            new_collection_authority,
+        },
+    ) = result.result_type()
+    {
+        assert_eq!(*collection_authority_record, ix.collection_authority_record);
+        assert_eq!(*mint, ix.mint);
+        assert_eq!(*new_collection_authority, ix.new_collection_authority);
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
+#[test]
+fn test_other_instructions_are_not_used() {
+    let subject = TokenMetadataParser {};
+
+    let keys = [Pubkey::new_unique(); 3];
+    // `SignMetadata` is not tracked by the parser.
+    let compiled = compile(vec![7], &keys);
+    let bundle = InstructionBundle {
+        instruction: Some(&compiled),
+        keys: &keys,
+        ..Default::default()
+    };
+
+    let result = subject.handle_instruction(&bundle).unwrap();
+    assert!(matches!(result.result_type(), ProgramParseResult::Unknown));
+}
+
+#[test]
+fn test_parse_edition_marker() {
+    let subject = TokenMetadataParser {};
+
+    let mut data = vec![7];
+    let mut ledger = [0; 31];
+    ledger[0] = 0b0110_0000;
+    data.extend_from_slice(&ledger);
+
+    let result = subject.handle_account(&data).unwrap();
+    if let ProgramParseResult::TokenMetadata(state) = result.result_type() {
+        if let TokenMetadataAccountData::EditionMarker(marker) = &state.data {
+            assert_eq!(marker.ledger, ledger);
+        } else {
+            panic!("Unexpected TokenMetadataAccountData variant");
+        }
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
+#[test]
+fn test_parse_token_record() {
+    let subject = TokenMetadataParser {};
+
+    let record = TokenRecord {
+        key: Key::TokenRecord,
+        bump: 254,
+        state: TokenState::Locked,
+        rule_set_revision: Some(2),
+        delegate: Some(Pubkey::new_unique()),
+        delegate_role: Some(TokenDelegateRole::Staking),
+        locked_transfer: None,
+    };
+    let mut data = record.try_to_vec().unwrap();
+    data.resize(TokenRecord::LEN, 0);
+
+    let result = subject.handle_account(&data).unwrap();
+    if let ProgramParseResult::TokenMetadata(state) = result.result_type() {
+        if let TokenMetadataAccountData::TokenRecord(parsed) = &state.data {
+            assert_eq!(*parsed, record);
+        } else {
+            panic!("Unexpected TokenMetadataAccountData variant");
+        }
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
+#[test]
+fn test_parse_metadata_delegate_record() {
+    let subject = TokenMetadataParser {};
+
+    let record = MetadataDelegateRecord {
+        key: Key::MetadataDelegate,
+        bump: 253,
+        mint: Pubkey::new_unique(),
+        delegate: Pubkey::new_unique(),
+        update_authority: Pubkey::new_unique(),
+    };
+    let data = record.try_to_vec().unwrap();
+
+    let result = subject.handle_account(&data).unwrap();
+    if let ProgramParseResult::TokenMetadata(state) = result.result_type() {
+        if let TokenMetadataAccountData::MetadataDelegateRecord(parsed) = &state.data {
+            assert_eq!(*parsed, record);
+        } else {
+            panic!("Unexpected TokenMetadataAccountData variant");
+        }
+    } else {
+        panic!("Unexpected ProgramParseResult variant");
+    }
+}
+
+#[test]
+fn test_instruction_name() {
+    let ix = ApproveCollectionAuthority {
+        collection_authority_record: Pubkey::new_unique(),
+        new_collection_authority: Pubkey::new_unique(),
+        update_authority: Pubkey::new_unique(),
+        payer: Pubkey::new_unique(),
+        metadata: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        system_program: solana_sdk::system_program::id(),
+        rent: None,
+    };
+    assert_eq!(
+        token_metadata::instruction_name(&ix.instruction().data),
+        Some("ApproveCollectionAuthority")
+    );
+    assert_eq!(token_metadata::instruction_name(&[56]), Some("Resize"));
+    assert_eq!(
+        token_metadata::instruction_name(&[57]),
+        Some("CloseAccounts")
+    );
+    assert_eq!(token_metadata::instruction_name(&[58]), None);
+    assert_eq!(token_metadata::instruction_name(&[]), None);
+
+    let ix = spl_token::instruction::transfer_checked(
+        &spl_token::id(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &[],
+        1,
+        0,
+    )
+    .unwrap();
+    assert_eq!(
+        token_account::instruction_name(&ix.data),
+        Some("TransferChecked")
+    );
+    assert_eq!(token_account::instruction_name(&[255]), None);
+}
@@ -1,27 +1,37 @@
+use cadence_macros::{is_global_default_set, statsd_count};
 use digital_asset_types::{
     dao::{
-        scopes::asset::{get_grouping, get_nft_editions},
-        sea_orm_active_enums::{
-            OwnerType, RoyaltyTargetType, SpecificationAssetClass, SpecificationVersions,
-        },
-        Cursor, PageOptions, SearchAssetsQuery,
+        scopes::asset::{get_grouping, get_nft_editions, get_pg_trgm_schema},
+        sea_orm_active_enums::{OwnerType, SpecificationAssetClass, SpecificationVersions},
+        Cursor, PageOptions, SearchAssetsQuery, SignatureCursor, TokenExtensionFilters,
+        TransferFeeAbove,
     },
     dapi::{
-        common::create_pagination, get_asset, get_asset_proofs, get_asset_signatures, get_assets,
+        common::create_pagination, get_asset, get_asset_delegated_authorities,
+        get_asset_ownership_history, get_asset_proofs, get_asset_signatures, get_assets,
         get_assets_by_authority, get_assets_by_creator, get_assets_by_group, get_assets_by_owner,
-        get_proof_for_asset, get_token_accounts, search_assets,
+        get_proof_for_asset, get_proof_for_asset_at_seq, get_token_accounts, get_tree_info,
+        get_trees_by_authority, search_assets, CanopyTruncation,
     },
     rpc::{
         filter::{AssetSortBy, SearchConditionType},
-        response::{GetGroupingResponse, TokenAccountList},
-        OwnershipModel, RoyaltyModel,
+        response::{
+            AssetDelegatedAuthorities, AssetOwnershipHistory, GetGroupingResponse,
+            TokenAccountList, TreeInfo, TreeInfoList,
+        },
+        OwnershipModel,
     },
 };
 use open_rpc_derive::document_rpc;
 use sea_orm::{sea_query::ConditionType, ConnectionTrait, DbBackend, Statement};
 
+use crate::asset_changes::{AssetChangeFilter, AssetChangeListener};
 use crate::error::DasApiError;
-use crate::validation::{validate_opt_pubkey, validate_search_with_name};
+use crate::validation::{
+    validate_attributes, validate_canopy_truncation, validate_exclusive, validate_groupings,
+    validate_name_relevance, validate_opt_pubkey, validate_opt_pubkeys, validate_range,
+    validate_search_filter, validate_search_with_name,
+};
 use open_rpc_schema::document::OpenrpcDocument;
 use {
     crate::api::*,
@@ -30,11 +40,18 @@ use {
     async_trait::async_trait,
     digital_asset_types::rpc::{response::AssetList, Asset, AssetProof},
     sea_orm::{DatabaseConnection, DbErr, SqlxPostgresConnector},
+    solana_client::nonblocking::rpc_client::RpcClient,
     sqlx::postgres::PgPoolOptions,
+    std::sync::OnceLock,
 };
 
 pub struct DasApi {
     db_connection: DatabaseConnection,
+    asset_changes: AssetChangeListener,
+    /// Looked up on the first name search, the migrations may not have installed pg_trgm yet
+    /// when the API starts.
+    pg_trgm_schema: OnceLock<String>,
+    rpc_client: Option<RpcClient>,
 }
 
 impl DasApi {
@@ -44,23 +61,51 @@ impl DasApi {
             .connect(&config.database_url)
             .await?;
 
-        let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+        let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone());
+        let asset_changes = AssetChangeListener::spawn(
+            pool.clone(),
+            SqlxPostgresConnector::from_sqlx_postgres_pool(pool),
+        );
         Ok(DasApi {
             db_connection: conn,
+            asset_changes,
+            pg_trgm_schema: OnceLock::new(),
+            rpc_client: config.rpc_url.map(RpcClient::new),
         })
     }
 
+    async fn pg_trgm_schema(&self) -> Result<String, DbErr> {
+        if let Some(schema) = self.pg_trgm_schema.get() {
+            return Ok(schema.clone());
+        }
+        let schema = get_pg_trgm_schema(&self.db_connection).await?;
+        Ok(self.pg_trgm_schema.get_or_init(|| schema).clone())
+    }
+
+    async fn transfer_fee_above(
+        &self,
+        basis_points: Option<u16>,
+    ) -> Result<Option<TransferFeeAbove>, DasApiError> {
+        let basis_points = match basis_points {
+            Some(basis_points) => basis_points,
+            None => return Ok(None),
+        };
+        let rpc_client = self.rpc_client.as_ref().ok_or_else(|| {
+            DasApiError::ValidationError(
+                "`transferFeeBpsAbove` is not supported without an RPC url".to_string(),
+            )
+        })?;
+        let epoch = rpc_client.get_epoch_info().await?.epoch;
+        Ok(Some(TransferFeeAbove {
+            basis_points,
+            epoch,
+        }))
+    }
+
     fn get_cursor(&self, cursor: &Option<String>) -> Result<Cursor, DasApiError> {
         match cursor {
-            Some(cursor_b64) => {
-                let cursor_vec = bs58::decode(cursor_b64)
-                    .into_vec()
-                    .map_err(|_| DasApiError::CursorValidationError(cursor_b64.clone()))?;
-                let cursor_struct = Cursor {
-                    id: Some(cursor_vec),
-                };
-                Ok(cursor_struct)
-            }
+            Some(cursor_b58) => Cursor::decode(cursor_b58)
+                .ok_or_else(|| DasApiError::CursorValidationError(cursor_b58.clone())),
             None => Ok(Cursor::default()),
         }
     }
@@ -126,7 +171,8 @@ impl DasApi {
         page_opt.limit = limit.map(|x| x as u64).unwrap_or(1000);
         if is_cursor_enabled {
             if let Some(sort) = &sorting {
-                if sort.sort_by != AssetSortBy::Id {
+                // Without a stable order there is nothing for the cursor to resume from.
+                if matches!(sort.sort_by, AssetSortBy::None | AssetSortBy::NameRelevance) {
                     return Err(DasApiError::PaginationSortingValidationError);
                 }
                 page_opt.cursor = Some(self.get_cursor(cursor)?);
@@ -148,6 +194,13 @@ pub fn not_found(asset_id: &String) -> DbErr {
     DbErr::RecordNotFound(format!("Asset Proof for {} Not Found", asset_id))
 }
 
+fn record_proof_verification(proof: &AssetProof) {
+    if is_global_default_set() {
+        let verified = proof.verified.to_string();
+        statsd_count!("asset_proof_verification", 1, "verified" => &verified);
+    }
+}
+
 #[document_rpc]
 #[async_trait]
 impl ApiContract for DasApi {
@@ -167,12 +220,25 @@ impl ApiContract for DasApi {
     ) -> Result<AssetProof, DasApiError> {
         let id = validate_pubkey(payload.id.clone())?;
         let id_bytes = id.to_bytes().to_vec();
-        get_proof_for_asset(&self.db_connection, id_bytes)
-            .await
+        let canopy_truncation =
+            validate_canopy_truncation(payload.truncate_canopy, payload.canopy_depth)?;
+        let proof = match payload.at_seq {
+            Some(seq) => {
+                let seq = i64::try_from(seq).map_err(|_| {
+                    DasApiError::ValidationError(format!("Invalid `atSeq`: {}", seq))
+                })?;
+                get_proof_for_asset_at_seq(&self.db_connection, id_bytes, seq, canopy_truncation)
+                    .await
+            }
+            None => get_proof_for_asset(&self.db_connection, id_bytes, canopy_truncation).await,
+        };
+        proof
             .and_then(|p| {
-                if p.proof.is_empty() {
+                // A canopy as deep as the tree leaves nothing to send, which is still a valid proof.
+                if p.proof.is_empty() && canopy_truncation == CanopyTruncation::None {
                     return Err(not_found(&payload.id));
                 }
+                record_proof_verification(&p);
                 Ok(p)
             })
             .map_err(Into::into)
@@ -182,7 +248,11 @@ impl ApiContract for DasApi {
         self: &DasApi,
         payload: GetAssetProofs,
     ) -> Result<HashMap<String, Option<AssetProof>>, DasApiError> {
-        let GetAssetProofs { ids } = payload;
+        let GetAssetProofs {
+            ids,
+            truncate_canopy,
+            canopy_depth,
+        } = payload;
 
         let batch_size = ids.len();
         if batch_size > 1000 {
@@ -194,7 +264,9 @@ impl ApiContract for DasApi {
             .map(|id| validate_pubkey(id.clone()).map(|id| id.to_bytes().to_vec()))
             .collect::<Result<Vec<Vec<u8>>, _>>()?;
 
-        let proofs = get_asset_proofs(&self.db_connection, id_bytes).await?;
+        let canopy_truncation = validate_canopy_truncation(truncate_canopy, canopy_depth)?;
+        let proofs = get_asset_proofs(&self.db_connection, id_bytes, canopy_truncation).await?;
+        proofs.values().for_each(record_proof_verification);
 
         let result: HashMap<String, Option<AssetProof>> = ids
             .iter()
@@ -252,18 +324,32 @@ impl ApiContract for DasApi {
             after,
             options,
             cursor,
+            has_transfer_hook,
+            transfer_hook_program,
+            has_permanent_delegate,
+            non_transferable,
+            transfer_fee_bps_above,
         } = payload;
         let before: Option<String> = before.filter(|before| !before.is_empty());
         let after: Option<String> = after.filter(|after| !after.is_empty());
         let owner_address = validate_pubkey(owner_address.clone())?;
         let owner_address_bytes = owner_address.to_bytes().to_vec();
+        let token_extensions = TokenExtensionFilters {
+            has_transfer_hook,
+            transfer_hook_program: validate_opt_pubkey(&transfer_hook_program)?,
+            has_permanent_delegate,
+            non_transferable,
+            transfer_fee_bps_above: self.transfer_fee_above(transfer_fee_bps_above).await?,
+        };
         let sort_by = sort_by.unwrap_or_default();
+        validate_name_relevance(sort_by, &None)?;
         let options = options.unwrap_or_default();
         let page_options =
             self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
         get_assets_by_owner(
             &self.db_connection,
             owner_address_bytes,
+            token_extensions,
             sort_by,
             &page_options,
             &options,
@@ -291,6 +377,7 @@ impl ApiContract for DasApi {
         let before: Option<String> = before.filter(|before| !before.is_empty());
         let after: Option<String> = after.filter(|after| !after.is_empty());
         let sort_by = sort_by.unwrap_or_default();
+        validate_name_relevance(sort_by, &None)?;
         let options = options.unwrap_or_default();
         let page_options =
             self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
@@ -325,6 +412,7 @@ impl ApiContract for DasApi {
         let creator_address_bytes = creator_address.to_bytes().to_vec();
 
         let sort_by = sort_by.unwrap_or_default();
+        validate_name_relevance(sort_by, &None)?;
         let page_options =
             self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
         let only_verified = only_verified.unwrap_or_default();
@@ -356,6 +444,7 @@ impl ApiContract for DasApi {
             cursor,
         } = payload;
         let sort_by = sort_by.unwrap_or_default();
+        validate_name_relevance(sort_by, &None)?;
         let authority_address = validate_pubkey(authority_address.clone())?;
         let authority_address_bytes = authority_address.to_bytes().to_vec();
         let options = options.unwrap_or_default();
@@ -379,13 +468,17 @@ impl ApiContract for DasApi {
             condition_type,
             interface,
             owner_address,
+            owner_addresses,
             owner_type,
             creator_address,
             creator_verified,
             authority_address,
             grouping,
+            groupings,
             delegate,
             frozen,
+            token_state,
+            token_delegate_role,
             supply,
             supply_mint,
             compressed,
@@ -393,7 +486,10 @@ impl ApiContract for DasApi {
             royalty_target_type,
             royalty_target,
             royalty_amount,
+            royalty_amount_range,
             burnt,
+            slot_updated_range,
+            created_at_range,
             sort_by,
             limit,
             page,
@@ -403,7 +499,15 @@ impl ApiContract for DasApi {
             options,
             cursor,
             name,
+            name_match,
             token_type,
+            attributes,
+            filter,
+            has_transfer_hook,
+            transfer_hook_program,
+            has_permanent_delegate,
+            non_transferable,
+            transfer_fee_bps_above,
         } = payload;
 
         // Deserialize search assets query
@@ -415,24 +519,59 @@ impl ApiContract for DasApi {
             SearchConditionType::Any => ConditionType::Any,
             SearchConditionType::All => ConditionType::All,
         });
+        validate_exclusive(
+            "ownerAddress",
+            &owner_address,
+            "ownerAddresses",
+            &owner_addresses,
+        )?;
+        validate_exclusive("grouping", &grouping, "groupings", &groupings)?;
+        validate_exclusive(
+            "royaltyAmount",
+            &royalty_amount,
+            "royaltyAmountRange",
+            &royalty_amount_range,
+        )?;
         let owner_address = validate_opt_pubkey(&owner_address)?;
-        let name = validate_search_with_name(&name, &owner_address)?;
+        let owner_addresses = validate_opt_pubkeys("ownerAddresses", &owner_addresses)?;
+        validate_groupings(&groupings)?;
+        validate_range("royaltyAmountRange", &royalty_amount_range)?;
+        validate_range("slotUpdatedRange", &slot_updated_range)?;
+        validate_range("createdAtRange", &created_at_range)?;
+        let name = validate_search_with_name(
+            &name,
+            owner_address.is_some()
+                || owner_addresses.is_some()
+                || grouping.is_some()
+                || groupings.is_some(),
+        )?;
         let creator_address = validate_opt_pubkey(&creator_address)?;
         let delegate = validate_opt_pubkey(&delegate)?;
 
         let authority_address = validate_opt_pubkey(&authority_address)?;
         let supply_mint = validate_opt_pubkey(&supply_mint)?;
         let royalty_target = validate_opt_pubkey(&royalty_target)?;
+        validate_attributes(&attributes)?;
+        let filter = filter.map(validate_search_filter).transpose()?;
+        let token_extensions = TokenExtensionFilters {
+            has_transfer_hook,
+            transfer_hook_program: validate_opt_pubkey(&transfer_hook_program)?,
+            has_permanent_delegate,
+            non_transferable,
+            transfer_fee_bps_above: self.transfer_fee_above(transfer_fee_bps_above).await?,
+        };
 
         let owner_type = owner_type.map(|x| match x {
             OwnershipModel::Single => OwnerType::Single,
             OwnershipModel::Token => OwnerType::Token,
         });
-        let royalty_target_type = royalty_target_type.map(|x| match x {
-            RoyaltyModel::Creators => RoyaltyTargetType::Creators,
-            RoyaltyModel::Fanout => RoyaltyTargetType::Fanout,
-            RoyaltyModel::Single => RoyaltyTargetType::Single,
-        });
+        let royalty_target_type = royalty_target_type.map(Into::into);
+        let token_state = token_state.map(Into::into);
+        let token_delegate_role = token_delegate_role.map(Into::into);
+        let pg_trgm_schema = match name {
+            Some(_) => Some(self.pg_trgm_schema().await?),
+            None => None,
+        };
         let saq = SearchAssetsQuery {
             negate,
             condition_type,
@@ -440,13 +579,18 @@ impl ApiContract for DasApi {
             specification_version,
             specification_asset_class,
             owner_address,
+            owner_addresses,
             owner_type,
             creator_address,
             creator_verified,
             authority_address,
             grouping,
+            groupings,
             delegate,
             frozen,
+            token_extensions,
+            token_state,
+            token_delegate_role,
             supply,
             supply_mint,
             compressed,
@@ -454,13 +598,21 @@ impl ApiContract for DasApi {
             royalty_target_type,
             royalty_target,
             royalty_amount,
+            royalty_amount_range,
             burnt,
+            slot_updated_range,
+            created_at_range,
             json_uri,
             name,
+            name_match,
+            pg_trgm_schema,
             token_type,
+            attributes,
+            filter,
         };
         let options = options.unwrap_or_default();
         let sort_by = sort_by.unwrap_or_default();
+        validate_name_relevance(sort_by, &saq.name)?;
         let page_options =
             self.validate_pagination(limit, page, &before, &after, &cursor, Some(sort_by))?;
         // Execute query
@@ -483,6 +635,8 @@ impl ApiContract for DasApi {
             leaf_index,
             cursor,
             sort_direction,
+            instruction,
+            structured,
         } = payload;
 
         if !((id.is_some() && tree.is_none() && leaf_index.is_none())
@@ -496,19 +650,47 @@ impl ApiContract for DasApi {
         let tree = validate_opt_pubkey(&tree)?;
 
         let page_options = self.validate_pagination(limit, page, &before, &after, &cursor, None)?;
+        let cursor = cursor
+            .map(|cursor| {
+                SignatureCursor::decode(&cursor).ok_or(DasApiError::CursorValidationError(cursor))
+            })
+            .transpose()?;
 
         get_asset_signatures(
             &self.db_connection,
             id,
             tree,
             leaf_index,
+            instruction,
             page_options,
+            cursor,
             sort_direction,
+            structured.unwrap_or(false),
         )
         .await
         .map_err(Into::into)
     }
 
+    async fn get_asset_ownership_history(
+        self: &DasApi,
+        payload: GetAssetOwnershipHistory,
+    ) -> Result<AssetOwnershipHistory, DasApiError> {
+        let id_bytes = validate_pubkey(payload.id)?.to_bytes().to_vec();
+        get_asset_ownership_history(&self.db_connection, id_bytes)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_asset_delegated_authorities(
+        self: &DasApi,
+        payload: GetAssetDelegatedAuthorities,
+    ) -> Result<AssetDelegatedAuthorities, DasApiError> {
+        let id_bytes = validate_pubkey(payload.id)?.to_bytes().to_vec();
+        get_asset_delegated_authorities(&self.db_connection, id_bytes)
+            .await
+            .map_err(Into::into)
+    }
+
     async fn get_grouping(
         self: &DasApi,
         payload: GetGrouping,
@@ -522,6 +704,7 @@ impl ApiContract for DasApi {
             group_key,
             group_name: group_value,
             group_size: gs.size,
+            group_max_size: gs.max_size,
         })
     }
 
@@ -579,4 +762,68 @@ impl ApiContract for DasApi {
         .await
         .map_err(Into::into)
     }
+
+    async fn get_tree_info(self: &DasApi, payload: GetTreeInfo) -> Result<TreeInfo, DasApiError> {
+        let tree = validate_pubkey(payload.tree)?;
+        get_tree_info(&self.db_connection, tree.to_bytes().to_vec())
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_trees_by_authority(
+        self: &DasApi,
+        payload: GetTreesByAuthority,
+    ) -> Result<TreeInfoList, DasApiError> {
+        let GetTreesByAuthority {
+            authority,
+            limit,
+            page,
+            before,
+            after,
+            cursor,
+        } = payload;
+        let authority = validate_pubkey(authority)?;
+        let page_options = self.validate_pagination(limit, page, &before, &after, &cursor, None)?;
+        get_trees_by_authority(
+            &self.db_connection,
+            authority.to_bytes().to_vec(),
+            &page_options,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn asset_subscribe(
+        self: &DasApi,
+        payload: AssetSubscribe,
+    ) -> Result<AssetNotificationStream, DasApiError> {
+        let id = validate_pubkey(payload.id)?;
+        Ok(self
+            .asset_changes
+            .subscribe(AssetChangeFilter::Asset(id.to_string())))
+    }
+
+    async fn owner_subscribe(
+        self: &DasApi,
+        payload: OwnerSubscribe,
+    ) -> Result<AssetNotificationStream, DasApiError> {
+        let owner_address = validate_pubkey(payload.owner_address)?;
+        Ok(self
+            .asset_changes
+            .subscribe(AssetChangeFilter::Owner(owner_address.to_string())))
+    }
+
+    async fn group_subscribe(
+        self: &DasApi,
+        payload: GroupSubscribe,
+    ) -> Result<AssetNotificationStream, DasApiError> {
+        let GroupSubscribe {
+            group_key,
+            group_value,
+        } = payload;
+        validate_pubkey(group_value.clone())?;
+        Ok(self
+            .asset_changes
+            .subscribe(AssetChangeFilter::Group(group_key, group_value)))
+    }
 }
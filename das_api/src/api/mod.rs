@@ -1,12 +1,19 @@
 use crate::error::DasApiError;
 use async_trait::async_trait;
-use digital_asset_types::rpc::filter::{AssetSortDirection, SearchConditionType, TokenTypeClass};
+use digital_asset_types::rpc::filter::{
+    AssetSortDirection, AttributeFilters, NameMatch, RangeFilter, SearchConditionType,
+    SearchFilter, TokenTypeClass,
+};
 use digital_asset_types::rpc::options::Options;
 use digital_asset_types::rpc::response::{
-    AssetList, NftEditions, TokenAccountList, TransactionSignatureList,
+    AssetDelegatedAuthorities, AssetList, AssetNotification, AssetOwnershipHistory, NftEditions,
+    TokenAccountList, TransactionSignatureList, TreeInfo, TreeInfoList,
 };
 use digital_asset_types::rpc::{filter::AssetSorting, response::GetGroupingResponse};
-use digital_asset_types::rpc::{Asset, AssetProof, Interface, OwnershipModel, RoyaltyModel};
+use digital_asset_types::rpc::{
+    Asset, AssetProof, Interface, OwnershipModel, RoyaltyModel, TokenDelegateRole, TokenState,
+};
+use futures::stream::BoxStream;
 use open_rpc_derive::{document_rpc, rpc};
 use open_rpc_schema::schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -15,6 +22,8 @@ use std::collections::HashMap;
 mod api_impl;
 pub use api_impl::*;
 
+pub type AssetNotificationStream = BoxStream<'static, AssetNotification>;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct GetAssetsByGroup {
@@ -44,6 +53,16 @@ pub struct GetAssetsByOwner {
     pub options: Option<Options>,
     #[serde(default)]
     pub cursor: Option<String>,
+    #[serde(default)]
+    pub has_transfer_hook: Option<bool>,
+    #[serde(default)]
+    pub transfer_hook_program: Option<String>,
+    #[serde(default)]
+    pub has_permanent_delegate: Option<bool>,
+    #[serde(default)]
+    pub non_transferable: Option<bool>,
+    #[serde(default)]
+    pub transfer_fee_bps_above: Option<u16>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
@@ -66,12 +85,29 @@ pub struct GetAssets {
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct GetAssetProof {
     pub id: String,
+    /// Drop the proof nodes stored in the tree canopy, using the indexed canopy depth.
+    #[serde(default)]
+    pub truncate_canopy: Option<bool>,
+    /// Drop this many proof nodes from the top, cannot be combined with `truncateCanopy`.
+    #[serde(default)]
+    pub canopy_depth: Option<u32>,
+    /// Rebuild the proof and root as of this tree sequence number instead of the latest one.
+    /// Only works while the ingester keeps the tree history for that sequence number, see
+    /// `INGESTER_CL_ITEMS_HISTORY`.
+    #[serde(default)]
+    pub at_seq: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct GetAssetProofs {
     pub ids: Vec<String>,
+    /// Drop the proof nodes stored in the tree canopy, using the indexed canopy depth.
+    #[serde(default)]
+    pub truncate_canopy: Option<bool>,
+    /// Drop this many proof nodes from the top, cannot be combined with `truncateCanopy`.
+    #[serde(default)]
+    pub canopy_depth: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -97,13 +133,21 @@ pub struct SearchAssets {
     pub condition_type: Option<SearchConditionType>,
     pub interface: Option<Interface>,
     pub owner_address: Option<String>,
+    #[serde(default)]
+    pub owner_addresses: Option<Vec<String>>,
     pub owner_type: Option<OwnershipModel>,
     pub creator_address: Option<String>,
     pub creator_verified: Option<bool>,
     pub authority_address: Option<String>,
     pub grouping: Option<(String, String)>,
+    #[serde(default)]
+    pub groupings: Option<Vec<(String, String)>>,
     pub delegate: Option<String>,
     pub frozen: Option<bool>,
+    #[serde(default)]
+    pub token_state: Option<TokenState>,
+    #[serde(default)]
+    pub token_delegate_role: Option<TokenDelegateRole>,
     pub supply: Option<u64>,
     pub supply_mint: Option<String>,
     pub compressed: Option<bool>,
@@ -111,7 +155,14 @@ pub struct SearchAssets {
     pub royalty_target_type: Option<RoyaltyModel>,
     pub royalty_target: Option<String>,
     pub royalty_amount: Option<u32>,
+    #[serde(default)]
+    pub royalty_amount_range: Option<RangeFilter<u32>>,
     pub burnt: Option<bool>,
+    #[serde(default)]
+    pub slot_updated_range: Option<RangeFilter<u64>>,
+    /// Unix timestamps in seconds.
+    #[serde(default)]
+    pub created_at_range: Option<RangeFilter<i64>>,
     pub sort_by: Option<AssetSorting>,
     pub limit: Option<u32>,
     pub page: Option<u32>,
@@ -125,7 +176,23 @@ pub struct SearchAssets {
     pub cursor: Option<String>,
     #[serde(default)]
     pub name: Option<String>,
+    #[serde(default)]
+    pub name_match: Option<NameMatch>,
     pub token_type: Option<TokenTypeClass>,
+    #[serde(default)]
+    pub attributes: Option<AttributeFilters>,
+    #[serde(default)]
+    pub filter: Option<SearchFilter>,
+    #[serde(default)]
+    pub has_transfer_hook: Option<bool>,
+    #[serde(default)]
+    pub transfer_hook_program: Option<String>,
+    #[serde(default)]
+    pub has_permanent_delegate: Option<bool>,
+    #[serde(default)]
+    pub non_transferable: Option<bool>,
+    #[serde(default)]
+    pub transfer_fee_bps_above: Option<u16>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -176,6 +243,12 @@ pub struct GetAssetSignatures {
     pub cursor: Option<String>,
     #[serde(default)]
     pub sort_direction: Option<AssetSortDirection>,
+    /// Only return signatures of this instruction, e.g. `Transfer` or `Burn`.
+    #[serde(default)]
+    pub instruction: Option<String>,
+    /// Return items as objects with slot, sequence and leaf details instead of pairs.
+    #[serde(default)]
+    pub structured: Option<bool>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -193,6 +266,55 @@ pub struct GetTokenAccounts {
     pub cursor: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetAssetOwnershipHistory {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetAssetDelegatedAuthorities {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetTreeInfo {
+    pub tree: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GetTreesByAuthority {
+    pub authority: String,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct AssetSubscribe {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct OwnerSubscribe {
+    pub owner_address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GroupSubscribe {
+    pub group_key: String,
+    pub group_value: String,
+}
+
 #[document_rpc]
 #[async_trait]
 pub trait ApiContract: Send + Sync + 'static {
@@ -275,6 +397,24 @@ pub trait ApiContract: Send + Sync + 'static {
         &self,
         payload: GetAssetSignatures,
     ) -> Result<TransactionSignatureList, DasApiError>;
+    #[rpc(
+        name = "getAssetOwnershipHistory",
+        params = "named",
+        summary = "Get the owners and delegates a compressed asset has had over time"
+    )]
+    async fn get_asset_ownership_history(
+        &self,
+        payload: GetAssetOwnershipHistory,
+    ) -> Result<AssetOwnershipHistory, DasApiError>;
+    #[rpc(
+        name = "getAssetDelegatedAuthorities",
+        params = "named",
+        summary = "Get the use and collection authorities delegated for an asset"
+    )]
+    async fn get_asset_delegated_authorities(
+        &self,
+        payload: GetAssetDelegatedAuthorities,
+    ) -> Result<AssetDelegatedAuthorities, DasApiError>;
     #[rpc(
         name = "getGrouping",
         params = "named",
@@ -297,4 +437,32 @@ pub trait ApiContract: Send + Sync + 'static {
         summary = "Get all printable editions for a master edition NFT mint"
     )]
     async fn get_nft_editions(&self, payload: GetNftEditions) -> Result<NftEditions, DasApiError>;
+    #[rpc(
+        name = "getTreeInfo",
+        params = "named",
+        summary = "Get the configuration and mint count of a merkle tree"
+    )]
+    async fn get_tree_info(&self, payload: GetTreeInfo) -> Result<TreeInfo, DasApiError>;
+    #[rpc(
+        name = "getTreesByAuthority",
+        params = "named",
+        summary = "Get a list of merkle trees created or delegated to an address"
+    )]
+    async fn get_trees_by_authority(
+        &self,
+        payload: GetTreesByAuthority,
+    ) -> Result<TreeInfoList, DasApiError>;
+
+    async fn asset_subscribe(
+        &self,
+        payload: AssetSubscribe,
+    ) -> Result<AssetNotificationStream, DasApiError>;
+    async fn owner_subscribe(
+        &self,
+        payload: OwnerSubscribe,
+    ) -> Result<AssetNotificationStream, DasApiError>;
+    async fn group_subscribe(
+        &self,
+        payload: GroupSubscribe,
+    ) -> Result<AssetNotificationStream, DasApiError>;
 }
@@ -0,0 +1,196 @@
+use digital_asset_types::{
+    dapi::get_assets,
+    rpc::{options::Options, response::AssetNotification},
+};
+use futures::{future, stream::BoxStream, StreamExt};
+use log::{debug, error, warn};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use sqlx::postgres::{PgListener, PgPool};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::BroadcastStream;
+
+/// Postgres channel the `asset` and `asset_grouping` triggers publish to.
+pub const ASSET_CHANGES_CHANNEL: &str = "asset_changes";
+
+const ASSET_CHANGES_BUFFER: usize = 10_000;
+const LOAD_BATCH_SIZE: usize = 100;
+const LISTENER_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Deserialize)]
+struct AssetChangePayload {
+    id: String,
+    slot: Option<i64>,
+    previous_owner: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetChange {
+    pub notification: AssetNotification,
+    /// Owner before the change, used to notify the sender of a transfer.
+    pub previous_owner: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetChangeFilter {
+    Asset(String),
+    Owner(String),
+    Group(String, String),
+}
+
+impl AssetChangeFilter {
+    pub fn matches(&self, change: &AssetChange) -> bool {
+        let asset = &change.notification.asset;
+        match self {
+            AssetChangeFilter::Asset(id) => asset.id == *id,
+            AssetChangeFilter::Owner(owner) => {
+                asset.ownership.as_ref().map(|o| &o.owner) == Some(owner)
+                    || change.previous_owner.as_ref() == Some(owner)
+            }
+            AssetChangeFilter::Group(group_key, group_value) => {
+                asset.grouping.iter().flatten().any(|g| {
+                    g.group_key == *group_key && g.group_value.as_ref() == Some(group_value)
+                })
+            }
+        }
+    }
+}
+
+pub struct AssetChangeListener {
+    sender: broadcast::Sender<AssetChange>,
+}
+
+impl AssetChangeListener {
+    /// Starts listening on [`ASSET_CHANGES_CHANNEL`] in the background.
+    pub fn spawn(pool: PgPool, db: DatabaseConnection) -> Self {
+        let (sender, _) = broadcast::channel(ASSET_CHANGES_BUFFER);
+        let listener_sender = sender.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = listen(&pool, &db, &listener_sender).await {
+                    error!("Asset change listener failed: {}", err);
+                }
+                tokio::time::sleep(LISTENER_RETRY_DELAY).await;
+            }
+        });
+        Self { sender }
+    }
+
+    pub fn subscribe(&self, filter: AssetChangeFilter) -> BoxStream<'static, AssetNotification> {
+        BroadcastStream::new(self.sender.subscribe())
+            .filter_map(move |change| {
+                future::ready(match change {
+                    Ok(change) if filter.matches(&change) => Some(change.notification),
+                    Ok(_) => None,
+                    Err(err) => {
+                        warn!("Asset change subscriber fell behind: {}", err);
+                        None
+                    }
+                })
+            })
+            .boxed()
+    }
+}
+
+async fn listen(
+    pool: &PgPool,
+    db: &DatabaseConnection,
+    sender: &broadcast::Sender<AssetChange>,
+) -> Result<(), sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(ASSET_CHANGES_CHANNEL).await?;
+
+    // Notifications are read on their own so that the ones arriving while assets are being
+    // loaded queue up and get loaded together.
+    let (payloads_sender, mut payloads) = mpsc::channel(ASSET_CHANGES_BUFFER);
+    let read = async move {
+        loop {
+            let notification = listener.recv().await?;
+            if payloads_sender
+                .send(notification.payload().to_string())
+                .await
+                .is_err()
+            {
+                return Ok(());
+            }
+        }
+    };
+    let load = async {
+        while let Some(payload) = payloads.recv().await {
+            let mut batch = vec![payload];
+            while batch.len() < LOAD_BATCH_SIZE {
+                match payloads.try_recv() {
+                    Ok(payload) => batch.push(payload),
+                    Err(_) => break,
+                }
+            }
+            // Loading the assets is the expensive part, skip it when nobody is subscribed.
+            if sender.receiver_count() == 0 {
+                continue;
+            }
+            match load_asset_changes(db, &batch).await {
+                Ok(changes) => {
+                    for change in changes {
+                        let _ = sender.send(change);
+                    }
+                }
+                Err(err) => error!("Failed to load changed assets: {}", err),
+            }
+        }
+    };
+
+    tokio::select! {
+        res = read => res,
+        _ = load => Ok(()),
+    }
+}
+
+/// Loads the assets of a batch of notifications with a single query. Notifications that can't
+/// be parsed or whose asset can't be served, e.g. without metadata yet, are skipped.
+async fn load_asset_changes(
+    db: &DatabaseConnection,
+    payloads: &[String],
+) -> Result<Vec<AssetChange>, DbErr> {
+    let payloads: Vec<AssetChangePayload> = payloads
+        .iter()
+        .filter_map(|payload| match serde_json::from_str(payload) {
+            Ok(payload) => Some(payload),
+            Err(err) => {
+                debug!("Skipping asset change notification: {}", err);
+                None
+            }
+        })
+        .collect();
+
+    let mut ids: Vec<Vec<u8>> = payloads
+        .iter()
+        .filter_map(|payload| hex::decode(&payload.id).ok())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let limit = ids.len() as u64;
+    let assets = get_assets(db, ids, limit, &Options::default()).await?;
+
+    Ok(payloads
+        .into_iter()
+        .filter_map(|payload| {
+            let id = hex::decode(&payload.id).ok()?;
+            let asset = assets.get(&bs58::encode(id).into_string())?.clone();
+            let previous_owner = payload
+                .previous_owner
+                .and_then(|owner| hex::decode(owner).ok())
+                .map(|owner| bs58::encode(owner).into_string());
+            Some(AssetChange {
+                notification: AssetNotification {
+                    slot: payload.slot.unwrap_or_default() as u64,
+                    asset,
+                },
+                previous_owner,
+            })
+        })
+        .collect())
+}
@@ -1,4 +1,4 @@
-use jsonrpsee::RpcModule;
+use jsonrpsee::{core::Error as RpcError, RpcModule, SubscriptionSink};
 use log::debug;
 
 use crate::{api::*, error::DasApiError};
@@ -108,6 +108,33 @@ impl RpcApiBuilder {
         )?;
         module.register_alias("getSignaturesForAsset", "getAssetSignatures")?;
 
+        module.register_async_method(
+            "get_asset_ownership_history",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetAssetOwnershipHistory>()?;
+                rpc_context
+                    .get_asset_ownership_history(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getAssetOwnershipHistory", "get_asset_ownership_history")?;
+
+        module.register_async_method(
+            "get_asset_delegated_authorities",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetAssetDelegatedAuthorities>()?;
+                rpc_context
+                    .get_asset_delegated_authorities(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias(
+            "getAssetDelegatedAuthorities",
+            "get_asset_delegated_authorities",
+        )?;
+
         module.register_async_method("search_assets", |rpc_params, rpc_context| async move {
             let payload = rpc_params.parse::<SearchAssets>()?;
             rpc_context.search_assets(payload).await.map_err(Into::into)
@@ -139,6 +166,86 @@ impl RpcApiBuilder {
         })?;
         module.register_alias("getNftEditions", "get_nft_editions")?;
 
+        module.register_async_method("get_tree_info", |rpc_params, rpc_context| async move {
+            let payload = rpc_params.parse::<GetTreeInfo>()?;
+            rpc_context.get_tree_info(payload).await.map_err(Into::into)
+        })?;
+        module.register_alias("getTreeInfo", "get_tree_info")?;
+
+        module.register_async_method(
+            "get_trees_by_authority",
+            |rpc_params, rpc_context| async move {
+                let payload = rpc_params.parse::<GetTreesByAuthority>()?;
+                rpc_context
+                    .get_trees_by_authority(payload)
+                    .await
+                    .map_err(Into::into)
+            },
+        )?;
+        module.register_alias("getTreesByAuthority", "get_trees_by_authority")?;
+
+        module.register_subscription(
+            "assetSubscribe",
+            "assetNotification",
+            "assetUnsubscribe",
+            |rpc_params, mut sink, rpc_context| {
+                let payload = match rpc_params.parse::<AssetSubscribe>() {
+                    Ok(payload) => payload,
+                    Err(err) => return sink.reject(RpcError::Call(err)).map_err(Into::into),
+                };
+                tokio::spawn(async move {
+                    pipe_notifications(sink, rpc_context.asset_subscribe(payload).await).await
+                });
+                Ok(())
+            },
+        )?;
+
+        module.register_subscription(
+            "ownerSubscribe",
+            "ownerNotification",
+            "ownerUnsubscribe",
+            |rpc_params, mut sink, rpc_context| {
+                let payload = match rpc_params.parse::<OwnerSubscribe>() {
+                    Ok(payload) => payload,
+                    Err(err) => return sink.reject(RpcError::Call(err)).map_err(Into::into),
+                };
+                tokio::spawn(async move {
+                    pipe_notifications(sink, rpc_context.owner_subscribe(payload).await).await
+                });
+                Ok(())
+            },
+        )?;
+
+        module.register_subscription(
+            "groupSubscribe",
+            "groupNotification",
+            "groupUnsubscribe",
+            |rpc_params, mut sink, rpc_context| {
+                let payload = match rpc_params.parse::<GroupSubscribe>() {
+                    Ok(payload) => payload,
+                    Err(err) => return sink.reject(RpcError::Call(err)).map_err(Into::into),
+                };
+                tokio::spawn(async move {
+                    pipe_notifications(sink, rpc_context.group_subscribe(payload).await).await
+                });
+                Ok(())
+            },
+        )?;
+
         Ok(module)
     }
 }
+
+async fn pipe_notifications(
+    mut sink: SubscriptionSink,
+    stream: Result<AssetNotificationStream, DasApiError>,
+) {
+    match stream {
+        Ok(stream) => {
+            sink.pipe_from_stream(stream).await;
+        }
+        Err(err) => {
+            let _ = sink.reject(RpcError::from(err));
+        }
+    }
+}
@@ -11,6 +11,8 @@ pub struct Config {
     pub metrics_host: Option<String>,
     pub server_port: u16,
     pub env: Option<String>,
+    /// Resolves the current epoch for the transfer fee filter.
+    pub rpc_url: Option<String>,
 }
 
 pub fn load_config() -> Result<Config, DasApiError> {
@@ -17,6 +17,8 @@ pub enum DasApiError {
     ValidationError(String),
     #[error("Database Error: {0}")]
     DatabaseError(#[from] sea_orm::DbErr),
+    #[error("RPC Error: {0}")]
+    RpcClientError(#[from] solana_client::client_error::ClientError),
     #[error("Pagination Error. Only one pagination parameter supported per query.")]
     PaginationError,
     #[error("Pagination Error. No Pagination Method Selected")]
@@ -29,7 +31,7 @@ pub enum DasApiError {
     PaginationExceededError,
     #[error("Cursor Validation Err: {0} is invalid")]
     CursorValidationError(String),
-    #[error("Pagination Sorting Error. Only sorting based on id is supported for this pagination option.")]
+    #[error("Pagination Sorting Error. This sorting is not supported for this pagination option.")]
     PaginationSortingValidationError,
 }
 
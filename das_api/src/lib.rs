@@ -1,5 +1,6 @@
 pub mod api;
 
+pub mod asset_changes;
 pub mod builder;
 pub mod config;
 pub mod error;
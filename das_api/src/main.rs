@@ -1,4 +1,5 @@
 pub mod api;
+mod asset_changes;
 mod builder;
 mod config;
 mod error;
@@ -1,4 +1,9 @@
 use crate::error::DasApiError;
+use digital_asset_types::{
+    dao::SearchAssetsFilter,
+    dapi::CanopyTruncation,
+    rpc::filter::{AssetSortBy, AssetSorting, AttributeFilters, RangeFilter, SearchFilter},
+};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
@@ -6,21 +11,230 @@ pub fn validate_pubkey(str_pubkey: String) -> Result<Pubkey, DasApiError> {
     Pubkey::from_str(&str_pubkey).map_err(|_| DasApiError::PubkeyValidationError(str_pubkey))
 }
 
+/// Shorter names have no trigrams to narrow the search with, so they are only allowed when
+/// another filter already limits the assets scanned.
+const MIN_UNSCOPED_NAME_LEN: usize = 3;
+
+/// `scoped` is set when an owner or grouping filter is also present.
 pub fn validate_search_with_name(
     name: &Option<String>,
-    owner: &Option<Vec<u8>>,
-) -> Result<Option<Vec<u8>>, DasApiError> {
-    let opt_name = if let Some(n) = name {
-        if owner.is_none() {
+    scoped: bool,
+) -> Result<Option<String>, DasApiError> {
+    if let Some(n) = name {
+        if n.is_empty() {
             return Err(DasApiError::ValidationError(
-                "Owner address must be provided in order to search assets by name".to_owned(),
+                "`name` must not be empty".to_owned(),
             ));
         }
-        Some(n.clone().into_bytes())
-    } else {
-        None
+        if !scoped && n.chars().count() < MIN_UNSCOPED_NAME_LEN {
+            return Err(DasApiError::ValidationError(format!(
+                "`name` must be at least {} characters long unless an owner or grouping is provided",
+                MIN_UNSCOPED_NAME_LEN
+            )));
+        }
+    }
+    Ok(name.clone())
+}
+
+pub fn validate_name_relevance(
+    sorting: AssetSorting,
+    name: &Option<String>,
+) -> Result<(), DasApiError> {
+    if sorting.sort_by == AssetSortBy::NameRelevance && name.is_none() {
+        return Err(DasApiError::ValidationError(
+            "Sorting by `name_relevance` requires a `name` filter".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Upper bound on the trait values of an `attributes` filter, each one is a separate predicate.
+const MAX_ATTRIBUTE_VALUES: usize = 100;
+
+pub fn validate_attributes(attributes: &Option<AttributeFilters>) -> Result<(), DasApiError> {
+    if let Some(attributes) = attributes {
+        if attributes.traits.is_empty() {
+            return Err(DasApiError::ValidationError(
+                "`attributes` must contain at least one trait".to_owned(),
+            ));
+        }
+        for filter in &attributes.traits {
+            if filter.trait_type.is_empty() {
+                return Err(DasApiError::ValidationError(
+                    "Attribute `traitType` must not be empty".to_owned(),
+                ));
+            }
+            if filter.values.is_empty() {
+                return Err(DasApiError::ValidationError(format!(
+                    "Attribute `{}` must have at least one value",
+                    filter.trait_type
+                )));
+            }
+        }
+        let values: usize = attributes.traits.iter().map(|f| f.values.len()).sum();
+        if values > MAX_ATTRIBUTE_VALUES {
+            return Err(DasApiError::ValidationError(format!(
+                "`attributes` can contain at most {} values",
+                MAX_ATTRIBUTE_VALUES
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Bounds on `filter` trees, every node becomes part of the query.
+const MAX_FILTER_DEPTH: usize = 8;
+const MAX_FILTER_NODES: usize = 64;
+
+pub fn validate_search_filter(filter: SearchFilter) -> Result<SearchAssetsFilter, DasApiError> {
+    let mut nodes = 0;
+    convert_search_filter(filter, 1, &mut nodes)
+}
+
+fn convert_search_filter(
+    filter: SearchFilter,
+    depth: usize,
+    nodes: &mut usize,
+) -> Result<SearchAssetsFilter, DasApiError> {
+    *nodes += 1;
+    if depth > MAX_FILTER_DEPTH || *nodes > MAX_FILTER_NODES {
+        return Err(DasApiError::ValidationError(format!(
+            "`filter` can be at most {} levels deep and have at most {} nodes",
+            MAX_FILTER_DEPTH, MAX_FILTER_NODES
+        )));
+    }
+    let filter = match filter {
+        SearchFilter::And(filters) => {
+            SearchAssetsFilter::And(convert_search_filters(filters, depth, nodes)?)
+        }
+        SearchFilter::Or(filters) => {
+            SearchAssetsFilter::Or(convert_search_filters(filters, depth, nodes)?)
+        }
+        SearchFilter::Not(filter) => {
+            SearchAssetsFilter::Not(Box::new(convert_search_filter(*filter, depth + 1, nodes)?))
+        }
+        SearchFilter::OwnerAddress(owner) => {
+            SearchAssetsFilter::Owner(validate_pubkey(owner)?.to_bytes().to_vec())
+        }
+        SearchFilter::CreatorAddress(creator) => {
+            SearchAssetsFilter::Creator(validate_pubkey(creator)?.to_bytes().to_vec())
+        }
+        SearchFilter::Grouping((key, value)) => SearchAssetsFilter::Grouping(key, value),
+        SearchFilter::Delegate(delegate) => {
+            SearchAssetsFilter::Delegate(validate_pubkey(delegate)?.to_bytes().to_vec())
+        }
+        SearchFilter::Frozen(frozen) => SearchAssetsFilter::Frozen(frozen),
+        SearchFilter::Supply(supply) => SearchAssetsFilter::Supply(supply),
+        SearchFilter::Burnt(burnt) => SearchAssetsFilter::Burnt(burnt),
+        SearchFilter::RoyaltyTargetType(model) => {
+            SearchAssetsFilter::RoyaltyTargetType(model.into())
+        }
+        SearchFilter::RoyaltyTarget(target) => {
+            SearchAssetsFilter::RoyaltyTarget(validate_pubkey(target)?.to_bytes().to_vec())
+        }
+        SearchFilter::RoyaltyAmount(amount) => SearchAssetsFilter::RoyaltyAmount(amount),
+        SearchFilter::Interface(interface) => SearchAssetsFilter::Interface(interface),
+        SearchFilter::TokenType(token_type) => SearchAssetsFilter::TokenType(token_type),
+        SearchFilter::JsonUri(uri) => SearchAssetsFilter::JsonUri(uri),
     };
-    Ok(opt_name)
+    Ok(filter)
+}
+
+fn convert_search_filters(
+    filters: Vec<SearchFilter>,
+    depth: usize,
+    nodes: &mut usize,
+) -> Result<Vec<SearchAssetsFilter>, DasApiError> {
+    if filters.is_empty() {
+        return Err(DasApiError::ValidationError(
+            "`and` and `or` filters must not be empty".to_owned(),
+        ));
+    }
+    filters
+        .into_iter()
+        .map(|filter| convert_search_filter(filter, depth + 1, nodes))
+        .collect()
+}
+
+/// Upper bound on the values of list filters such as `ownerAddresses`.
+const MAX_LIST_FILTER_LEN: usize = 100;
+
+fn validate_list_len(field: &str, len: usize) -> Result<(), DasApiError> {
+    if len == 0 {
+        return Err(DasApiError::ValidationError(format!(
+            "`{}` must not be empty",
+            field
+        )));
+    }
+    if len > MAX_LIST_FILTER_LEN {
+        return Err(DasApiError::ValidationError(format!(
+            "`{}` can contain at most {} values",
+            field, MAX_LIST_FILTER_LEN
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_opt_pubkeys(
+    field: &str,
+    pubkeys: &Option<Vec<String>>,
+) -> Result<Option<Vec<Vec<u8>>>, DasApiError> {
+    if let Some(pubkeys) = pubkeys {
+        validate_list_len(field, pubkeys.len())?;
+        let pubkeys = pubkeys
+            .iter()
+            .map(|pubkey| Ok(validate_pubkey(pubkey.clone())?.to_bytes().to_vec()))
+            .collect::<Result<_, DasApiError>>()?;
+        return Ok(Some(pubkeys));
+    }
+    Ok(None)
+}
+
+pub fn validate_groupings(groupings: &Option<Vec<(String, String)>>) -> Result<(), DasApiError> {
+    if let Some(groupings) = groupings {
+        validate_list_len("groupings", groupings.len())?;
+    }
+    Ok(())
+}
+
+pub fn validate_range<T: PartialOrd>(
+    field: &str,
+    range: &Option<RangeFilter<T>>,
+) -> Result<(), DasApiError> {
+    match range {
+        Some(RangeFilter {
+            min: None,
+            max: None,
+        }) => Err(DasApiError::ValidationError(format!(
+            "`{}` must set `min`, `max` or both",
+            field
+        ))),
+        Some(RangeFilter {
+            min: Some(min),
+            max: Some(max),
+        }) if min > max => Err(DasApiError::ValidationError(format!(
+            "`{}` has `min` greater than `max`",
+            field
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Single value fields and their list variants cannot be combined, e.g. `ownerAddress` and
+/// `ownerAddresses`.
+pub fn validate_exclusive<A, B>(
+    field: &str,
+    value: &Option<A>,
+    other_field: &str,
+    other_value: &Option<B>,
+) -> Result<(), DasApiError> {
+    if value.is_some() && other_value.is_some() {
+        return Err(DasApiError::ValidationError(format!(
+            "`{}` and `{}` cannot be used together",
+            field, other_field
+        )));
+    }
+    Ok(())
 }
 
 pub fn validate_opt_pubkey(pubkey: &Option<String>) -> Result<Option<Vec<u8>>, DasApiError> {
@@ -33,3 +247,28 @@ pub fn validate_opt_pubkey(pubkey: &Option<String>) -> Result<Option<Vec<u8>>, D
     };
     Ok(opt_bytes)
 }
+
+/// Deepest tree supported by the account compression program.
+const MAX_TREE_DEPTH: u32 = 30;
+
+pub fn validate_canopy_truncation(
+    truncate_canopy: Option<bool>,
+    canopy_depth: Option<u32>,
+) -> Result<CanopyTruncation, DasApiError> {
+    let truncate_canopy = truncate_canopy.filter(|truncate| *truncate);
+    validate_exclusive(
+        "truncateCanopy",
+        &truncate_canopy,
+        "canopyDepth",
+        &canopy_depth,
+    )?;
+    match (truncate_canopy, canopy_depth) {
+        (_, Some(depth)) if depth > MAX_TREE_DEPTH => Err(DasApiError::ValidationError(format!(
+            "`canopyDepth` must be at most {}",
+            MAX_TREE_DEPTH
+        ))),
+        (_, Some(depth)) => Ok(CanopyTruncation::Depth(depth)),
+        (Some(_), None) => Ok(CanopyTruncation::Indexed),
+        (None, None) => Ok(CanopyTruncation::None),
+    }
+}
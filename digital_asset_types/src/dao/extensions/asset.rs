@@ -127,6 +127,8 @@ impl Default for asset::Model {
             asset_data_hash: None,
             bubblegum_flags: None,
             non_transferable: None,
+            token_state: None,
+            token_delegate_role: None,
         }
     }
 }
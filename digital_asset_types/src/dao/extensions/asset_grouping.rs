@@ -2,6 +2,10 @@ use sea_orm::{EntityTrait, EnumIter, Related, RelationDef, RelationTrait};
 
 use crate::dao::{asset, asset_authority, asset_data, asset_grouping};
 
+/// Group key of the `asset_grouping` rows written for Token-2022 group members, whose group
+/// size is tracked on chain.
+pub const TOKEN_GROUP_KEY: &str = "token_group";
+
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
     Asset,
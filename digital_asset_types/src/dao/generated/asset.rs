@@ -4,6 +4,8 @@ use super::sea_orm_active_enums::OwnerType;
 use super::sea_orm_active_enums::RoyaltyTargetType;
 use super::sea_orm_active_enums::SpecificationAssetClass;
 use super::sea_orm_active_enums::SpecificationVersions;
+use super::sea_orm_active_enums::TokenDelegateRole;
+use super::sea_orm_active_enums::TokenState;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -62,6 +64,8 @@ pub struct Model {
     pub asset_data_hash: Option<String>,
     pub bubblegum_flags: Option<i16>,
     pub non_transferable: Option<bool>,
+    pub token_state: Option<TokenState>,
+    pub token_delegate_role: Option<TokenDelegateRole>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -110,6 +114,8 @@ pub enum Column {
     AssetDataHash,
     BubblegumFlags,
     NonTransferable,
+    TokenState,
+    TokenDelegateRole,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -175,6 +181,8 @@ impl ColumnTrait for Column {
             Self::AssetDataHash => ColumnType::Char(Some(50u32)).def().null(),
             Self::BubblegumFlags => ColumnType::SmallInteger.def().null(),
             Self::NonTransferable => ColumnType::Boolean.def().null(),
+            Self::TokenState => TokenState::db_type().null(),
+            Self::TokenDelegateRole => TokenDelegateRole::db_type().null(),
         }
     }
 }
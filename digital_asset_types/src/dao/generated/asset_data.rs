@@ -1,5 +1,6 @@
 //! SeaORM Entity. Generated by sea-orm-codegen 0.9.3
 
+use super::sea_orm_active_enums::AssetDataSchema;
 use super::sea_orm_active_enums::ChainMutability;
 use super::sea_orm_active_enums::Mutability;
 use sea_orm::entity::prelude::*;
@@ -27,6 +28,9 @@ pub struct Model {
     pub raw_name: Option<Vec<u8>>,
     pub raw_symbol: Option<Vec<u8>>,
     pub base_info_seq: Option<i64>,
+    pub bubblegum_asset_data: Option<Vec<u8>>,
+    pub bubblegum_asset_data_schema: Option<AssetDataSchema>,
+    pub bubblegum_asset_data_seq: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -42,6 +46,9 @@ pub enum Column {
     RawName,
     RawSymbol,
     BaseInfoSeq,
+    BubblegumAssetData,
+    BubblegumAssetDataSchema,
+    BubblegumAssetDataSeq,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -74,6 +81,9 @@ impl ColumnTrait for Column {
             Self::RawName => ColumnType::Binary.def().null(),
             Self::RawSymbol => ColumnType::Binary.def().null(),
             Self::BaseInfoSeq => ColumnType::BigInteger.def().null(),
+            Self::BubblegumAssetData => ColumnType::Binary.def().null(),
+            Self::BubblegumAssetDataSchema => AssetDataSchema::db_type().null(),
+            Self::BubblegumAssetDataSeq => ColumnType::BigInteger.def().null(),
         }
     }
 }
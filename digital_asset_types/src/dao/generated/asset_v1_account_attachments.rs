@@ -21,6 +21,7 @@ pub struct Model {
     pub initialized: bool,
     pub data: Option<Json>,
     pub slot_updated: i64,
+    pub authority: Option<Vec<u8>>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -31,6 +32,7 @@ pub enum Column {
     Initialized,
     Data,
     SlotUpdated,
+    Authority,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -58,6 +60,7 @@ impl ColumnTrait for Column {
             Self::Initialized => ColumnType::Boolean.def(),
             Self::Data => ColumnType::JsonBinary.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
+            Self::Authority => ColumnType::Binary.def().null(),
         }
     }
 }
@@ -7,12 +7,19 @@ pub mod asset_authority;
 pub mod asset_creators;
 pub mod asset_data;
 pub mod asset_grouping;
+pub mod asset_leaf_history;
+pub mod asset_signatures;
 pub mod asset_v1_account_attachments;
 pub mod backfill_items;
 pub mod cl_audits_v2;
 pub mod cl_items;
+pub mod cl_items_history;
+pub mod failed_messages;
+pub mod merkle_tree;
 pub mod raw_txn;
 pub mod sea_orm_active_enums;
 pub mod tasks;
 pub mod token_accounts;
+pub mod token_groups;
+pub mod token_records;
 pub mod tokens;
@@ -7,11 +7,18 @@ pub use super::asset_authority::Entity as AssetAuthority;
 pub use super::asset_creators::Entity as AssetCreators;
 pub use super::asset_data::Entity as AssetData;
 pub use super::asset_grouping::Entity as AssetGrouping;
+pub use super::asset_leaf_history::Entity as AssetLeafHistory;
+pub use super::asset_signatures::Entity as AssetSignatures;
 pub use super::asset_v1_account_attachments::Entity as AssetV1AccountAttachments;
 pub use super::backfill_items::Entity as BackfillItems;
 pub use super::cl_audits_v2::Entity as ClAuditsV2;
 pub use super::cl_items::Entity as ClItems;
+pub use super::cl_items_history::Entity as ClItemsHistory;
+pub use super::failed_messages::Entity as FailedMessages;
+pub use super::merkle_tree::Entity as MerkleTree;
 pub use super::raw_txn::Entity as RawTxn;
 pub use super::tasks::Entity as Tasks;
 pub use super::token_accounts::Entity as TokenAccounts;
+pub use super::token_groups::Entity as TokenGroups;
+pub use super::token_records::Entity as TokenRecords;
 pub use super::tokens::Entity as Tokens;
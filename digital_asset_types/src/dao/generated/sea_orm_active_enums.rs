@@ -78,6 +78,16 @@ pub enum SpecificationVersions {
     V2,
 }
 #[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "asset_data_schema")]
+pub enum AssetDataSchema {
+    #[sea_orm(string_value = "binary")]
+    Binary,
+    #[sea_orm(string_value = "json")]
+    Json,
+    #[sea_orm(string_value = "msg_pack")]
+    MsgPack,
+}
+#[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "instruction")]
 pub enum Instruction {
     #[sea_orm(string_value = "burn")]
@@ -164,6 +174,14 @@ pub enum TaskStatus {
     Success,
 }
 #[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tree_version")]
+pub enum TreeVersion {
+    #[sea_orm(string_value = "v1")]
+    V1,
+    #[sea_orm(string_value = "v2")]
+    V2,
+}
+#[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(
     rs_type = "String",
     db_type = "Enum",
@@ -182,6 +200,12 @@ pub enum V1AccountAttachments {
     TokenInscription,
     #[sea_orm(string_value = "unknown")]
     Unknown,
+    #[sea_orm(string_value = "use_authority_record")]
+    UseAuthorityRecord,
+    #[sea_orm(string_value = "collection_authority_record")]
+    CollectionAuthorityRecord,
+    #[sea_orm(string_value = "metadata_delegate_record")]
+    MetadataDelegateRecord,
 }
 #[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "chain_mutability")]
@@ -193,3 +217,47 @@ pub enum ChainMutability {
     #[sea_orm(string_value = "unknown")]
     Unknown,
 }
+#[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "token_state")]
+pub enum TokenState {
+    #[sea_orm(string_value = "unlocked")]
+    Unlocked,
+    #[sea_orm(string_value = "locked")]
+    Locked,
+    #[sea_orm(string_value = "listed")]
+    Listed,
+}
+#[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "token_delegate_role"
+)]
+pub enum TokenDelegateRole {
+    #[sea_orm(string_value = "sale")]
+    Sale,
+    #[sea_orm(string_value = "transfer")]
+    Transfer,
+    #[sea_orm(string_value = "utility")]
+    Utility,
+    #[sea_orm(string_value = "staking")]
+    Staking,
+    #[sea_orm(string_value = "standard")]
+    Standard,
+    #[sea_orm(string_value = "locked_transfer")]
+    LockedTransfer,
+    #[sea_orm(string_value = "migration")]
+    Migration,
+}
+#[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "failed_message_type"
+)]
+pub enum FailedMessageType {
+    #[sea_orm(string_value = "transaction")]
+    Transaction,
+    #[sea_orm(string_value = "account")]
+    Account,
+}
@@ -24,6 +24,12 @@ pub struct Model {
     pub extension_data: Option<Vec<u8>>,
     pub slot_updated: i64,
     pub extensions: Option<Json>,
+    pub transfer_hook_program: Option<Vec<u8>>,
+    pub permanent_delegate: Option<Vec<u8>>,
+    pub non_transferable: bool,
+    pub older_transfer_fee_basis_points: Option<i32>,
+    pub newer_transfer_fee_basis_points: Option<i32>,
+    pub newer_transfer_fee_epoch: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -38,6 +44,12 @@ pub enum Column {
     ExtensionData,
     SlotUpdated,
     Extensions,
+    TransferHookProgram,
+    PermanentDelegate,
+    NonTransferable,
+    OlderTransferFeeBasisPoints,
+    NewerTransferFeeBasisPoints,
+    NewerTransferFeeEpoch,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -69,6 +81,12 @@ impl ColumnTrait for Column {
             Self::ExtensionData => ColumnType::Binary.def().null(),
             Self::SlotUpdated => ColumnType::BigInteger.def(),
             Self::Extensions => ColumnType::JsonBinary.def().null(),
+            Self::TransferHookProgram => ColumnType::Binary.def().null(),
+            Self::PermanentDelegate => ColumnType::Binary.def().null(),
+            Self::NonTransferable => ColumnType::Boolean.def(),
+            Self::OlderTransferFeeBasisPoints => ColumnType::Integer.def().null(),
+            Self::NewerTransferFeeBasisPoints => ColumnType::Integer.def().null(),
+            Self::NewerTransferFeeEpoch => ColumnType::BigInteger.def().null(),
         }
     }
 }
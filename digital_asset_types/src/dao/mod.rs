@@ -2,25 +2,41 @@
 mod full_asset;
 mod generated;
 pub mod scopes;
-use crate::rpc::{filter::TokenTypeClass, Interface};
+use crate::rpc::{
+    filter::{
+        AttributeFilter, AttributeFilters, NameMatch, RangeFilter, SearchConditionType,
+        TokenTypeClass,
+    },
+    Interface,
+};
 
 use self::sea_orm_active_enums::{
     OwnerType, RoyaltyTargetType, SpecificationAssetClass, SpecificationVersions,
+    TokenDelegateRole, TokenState,
 };
 pub use full_asset::*;
 pub use generated::*;
 pub mod extensions;
 
+use chrono::{DateTime, Utc};
 use sea_orm::{
     entity::*,
     sea_query::Expr,
-    sea_query::{ConditionType, IntoCondition, SimpleExpr},
+    sea_query::{ConditionType, IntoCondition, Query, SimpleExpr},
     Condition, DbErr, RelationDef,
 };
 use serde::{Deserialize, Serialize};
 
 pub struct GroupingSize {
     pub size: u64,
+    /// Only known for groups that cap their size on chain.
+    pub max_size: Option<u64>,
+}
+
+pub struct GrandTotal {
+    pub total: u64,
+    /// Set when the exact count timed out and `total` is the query planner's estimate.
+    pub estimated: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -35,6 +51,87 @@ pub struct PageOptions {
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct Cursor {
     pub id: Option<Vec<u8>>,
+    /// Value of the sort column for the row the cursor points at, `None` when sorting by id or
+    /// when the row has no value. Timestamps are stored as unix microseconds.
+    pub sort_value: Option<i64>,
+}
+
+impl Cursor {
+    const ID_LEN: usize = 32;
+
+    /// Encodes the cursor as base58 of the id followed by the big endian sort value. Cursors
+    /// without a sort value are just the base58 id, same as cursors sorted by id always were.
+    pub fn encode(&self) -> Option<String> {
+        let mut bytes = self.id.clone()?;
+        if let Some(sort_value) = self.sort_value {
+            bytes.extend_from_slice(&sort_value.to_be_bytes());
+        }
+        Some(bs58::encode(bytes).into_string())
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let mut bytes = bs58::decode(cursor).into_vec().ok()?;
+        let sort_value = match bytes.len() {
+            len if len == Self::ID_LEN + 8 => {
+                let value = bytes.split_off(Self::ID_LEN);
+                Some(i64::from_be_bytes(value.try_into().ok()?))
+            }
+            _ => None,
+        };
+        Some(Self {
+            id: Some(bytes),
+            sort_value,
+        })
+    }
+
+    pub fn from_asset(asset: &asset::Model, sort_column: Option<asset::Column>) -> Self {
+        let sort_value = match sort_column {
+            Some(asset::Column::CreatedAt) => asset.created_at.map(|t| t.timestamp_micros()),
+            Some(asset::Column::SlotUpdated) => asset.slot_updated,
+            _ => None,
+        };
+        Self {
+            id: Some(asset.id.clone()),
+            sort_value,
+        }
+    }
+}
+
+/// Position after the last signature of a page. Compressed asset signatures are ordered by `seq`,
+/// the signatures of other assets by slot with the row id breaking ties.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureCursor {
+    pub position: i64,
+    pub id: Option<i64>,
+}
+
+impl SignatureCursor {
+    /// Encodes the cursor as base58 of the big endian position, followed by the id if any.
+    pub fn encode(&self) -> String {
+        let mut bytes = self.position.to_be_bytes().to_vec();
+        if let Some(id) = self.id {
+            bytes.extend_from_slice(&id.to_be_bytes());
+        }
+        bs58::encode(bytes).into_string()
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = bs58::decode(cursor).into_vec().ok()?;
+        let value = |range: std::ops::Range<usize>| {
+            Some(i64::from_be_bytes(bytes.get(range)?.try_into().ok()?))
+        };
+        match bytes.len() {
+            8 => Some(Self {
+                position: value(0..8)?,
+                id: None,
+            }),
+            16 => Some(Self {
+                position: value(0..8)?,
+                id: Some(value(8..16)?),
+            }),
+            _ => None,
+        }
+    }
 }
 
 pub enum Pagination {
@@ -58,13 +155,22 @@ pub struct SearchAssetsQuery {
     pub specification_version: Option<SpecificationVersions>,
     pub specification_asset_class: Option<SpecificationAssetClass>,
     pub owner_address: Option<Vec<u8>>,
+    /// Matches assets owned by any of the addresses.
+    pub owner_addresses: Option<Vec<Vec<u8>>>,
     pub owner_type: Option<OwnerType>,
     pub creator_address: Option<Vec<u8>>,
     pub creator_verified: Option<bool>,
     pub authority_address: Option<Vec<u8>>,
     pub grouping: Option<(String, String)>,
+    /// Matches assets in any of the groups.
+    pub groupings: Option<Vec<(String, String)>>,
     pub delegate: Option<Vec<u8>>,
     pub frozen: Option<bool>,
+    pub token_extensions: TokenExtensionFilters,
+    /// Lock state of programmable NFTs.
+    pub token_state: Option<TokenState>,
+    /// Token delegate role of programmable NFTs.
+    pub token_delegate_role: Option<TokenDelegateRole>,
     pub supply: Option<u64>,
     pub supply_mint: Option<Vec<u8>>,
     pub compressed: Option<bool>,
@@ -72,10 +178,250 @@ pub struct SearchAssetsQuery {
     pub royalty_target_type: Option<RoyaltyTargetType>,
     pub royalty_target: Option<Vec<u8>>,
     pub royalty_amount: Option<u32>,
+    pub royalty_amount_range: Option<RangeFilter<u32>>,
     pub burnt: Option<bool>,
+    pub slot_updated_range: Option<RangeFilter<u64>>,
+    /// Unix timestamps in seconds.
+    pub created_at_range: Option<RangeFilter<i64>>,
     pub json_uri: Option<String>,
-    pub name: Option<Vec<u8>>,
+    pub name: Option<String>,
+    /// Defaults to [NameMatch::Contains]
+    pub name_match: Option<NameMatch>,
+    /// Schema pg_trgm is installed in, qualifies the fuzzy `name` match and its relevance rank.
+    /// Looked up by [crate::dapi::search_assets] when it is not set.
+    pub pg_trgm_schema: Option<String>,
     pub token_type: Option<TokenTypeClass>,
+    pub attributes: Option<AttributeFilters>,
+    pub filter: Option<SearchAssetsFilter>,
+}
+
+/// Transfer fee of a mint above `$2` basis points, the newer fee applies from the start of its
+/// epoch and `$1` is the current epoch.
+const TRANSFER_FEE_ABOVE: &str = "tokens.newer_transfer_fee_epoch IS NOT NULL AND CASE \
+    WHEN tokens.newer_transfer_fee_epoch <= $1 THEN tokens.newer_transfer_fee_basis_points \
+    ELSE tokens.older_transfer_fee_basis_points END > $2";
+
+/// Transfer fee filter, evaluated at `epoch` since the fee in effect changes with the epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferFeeAbove {
+    pub basis_points: u16,
+    pub epoch: u64,
+}
+
+/// Filters on the Token-2022 extensions of the asset mint, backed by the indexed extension
+/// columns of `tokens`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenExtensionFilters {
+    pub has_transfer_hook: Option<bool>,
+    pub transfer_hook_program: Option<Vec<u8>>,
+    pub has_permanent_delegate: Option<bool>,
+    pub non_transferable: Option<bool>,
+    pub transfer_fee_bps_above: Option<TransferFeeAbove>,
+}
+
+impl TokenExtensionFilters {
+    /// `None` when no filter is set.
+    pub fn condition(&self) -> Option<Condition> {
+        let mints = |predicate: SimpleExpr| {
+            Query::select()
+                .column(tokens::Column::Mint)
+                .from(tokens::Entity)
+                .and_where(predicate)
+                .to_owned()
+        };
+        // Negative filters also match assets without a mint, like compressed NFTs.
+        let with = |predicate: SimpleExpr, present: bool| {
+            if present {
+                asset::Column::Id.in_subquery(mints(predicate))
+            } else {
+                asset::Column::Id.not_in_subquery(mints(predicate))
+            }
+        };
+
+        let condition = Condition::all()
+            .add_option(
+                self.has_transfer_hook
+                    .map(|x| with(tokens::Column::TransferHookProgram.is_not_null(), x)),
+            )
+            .add_option(
+                self.transfer_hook_program
+                    .clone()
+                    .map(|x| with(tokens::Column::TransferHookProgram.eq(x), true)),
+            )
+            .add_option(
+                self.has_permanent_delegate
+                    .map(|x| with(tokens::Column::PermanentDelegate.is_not_null(), x)),
+            )
+            .add_option(
+                self.non_transferable
+                    .map(|x| with(tokens::Column::NonTransferable.eq(true), x)),
+            )
+            .add_option(self.transfer_fee_bps_above.map(|x| {
+                with(
+                    Expr::cust_with_values(
+                        TRANSFER_FEE_ABOVE,
+                        [x.epoch as i64, x.basis_points as i64],
+                    ),
+                    true,
+                )
+            }));
+        (!condition.is_empty()).then_some(condition)
+    }
+}
+
+/// Expression tree over the search predicates, combined with the other fields of
+/// [SearchAssetsQuery] like any single field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchAssetsFilter {
+    And(Vec<SearchAssetsFilter>),
+    Or(Vec<SearchAssetsFilter>),
+    Not(Box<SearchAssetsFilter>),
+    Owner(Vec<u8>),
+    Creator(Vec<u8>),
+    Grouping(String, String),
+    Delegate(Vec<u8>),
+    Frozen(bool),
+    Supply(u64),
+    Burnt(bool),
+    RoyaltyTargetType(RoyaltyTargetType),
+    RoyaltyTarget(Vec<u8>),
+    RoyaltyAmount(u32),
+    Interface(Interface),
+    TokenType(TokenTypeClass),
+    JsonUri(String),
+}
+
+impl SearchAssetsFilter {
+    /// Predicates on related tables are subqueries rather than joins so they keep their meaning
+    /// under `or` and `not`, a join would drop assets without a matching row before either applies.
+    /// Likewise predicates on nullable columns are false rather than NULL for a NULL column, so
+    /// `not` keeps the assets that have no value.
+    pub fn condition(&self) -> Condition {
+        match self {
+            SearchAssetsFilter::And(filters) => filters
+                .iter()
+                .fold(Condition::all(), |cond, f| cond.add(f.condition())),
+            SearchAssetsFilter::Or(filters) => filters
+                .iter()
+                .fold(Condition::any(), |cond, f| cond.add(f.condition())),
+            SearchAssetsFilter::Not(filter) => filter.condition().not(),
+            SearchAssetsFilter::Owner(owner) => owner_condition(vec![owner.clone()], true),
+            SearchAssetsFilter::Creator(creator) => asset::Column::Id
+                .in_subquery(
+                    Query::select()
+                        .column(asset_creators::Column::AssetId)
+                        .from(asset_creators::Entity)
+                        .and_where(asset_creators::Column::Creator.eq(creator.clone()))
+                        .to_owned(),
+                )
+                .into_condition(),
+            SearchAssetsFilter::Grouping(key, value) => asset::Column::Id
+                .in_subquery(
+                    Query::select()
+                        .column(asset_grouping::Column::AssetId)
+                        .from(asset_grouping::Entity)
+                        .and_where(asset_grouping::Column::GroupKey.eq(key.clone()))
+                        .and_where(asset_grouping::Column::GroupValue.eq(value.clone()))
+                        .to_owned(),
+                )
+                .into_condition(),
+            SearchAssetsFilter::Delegate(delegate) => {
+                not_null_eq(asset::Column::Delegate, delegate.clone())
+            }
+            SearchAssetsFilter::Frozen(frozen) => frozen_condition(*frozen),
+            SearchAssetsFilter::Supply(supply) => {
+                asset::Column::Supply.eq(*supply).into_condition()
+            }
+            SearchAssetsFilter::Burnt(burnt) => asset::Column::Burnt.eq(*burnt).into_condition(),
+            SearchAssetsFilter::RoyaltyTargetType(target_type) => asset::Column::RoyaltyTargetType
+                .eq(target_type.clone())
+                .into_condition(),
+            SearchAssetsFilter::RoyaltyTarget(target) => {
+                not_null_eq(asset::Column::RoyaltyTarget, target.clone())
+            }
+            SearchAssetsFilter::RoyaltyAmount(amount) => {
+                asset::Column::RoyaltyAmount.eq(*amount).into_condition()
+            }
+            SearchAssetsFilter::Interface(interface) => {
+                let (version, class): (SpecificationVersions, SpecificationAssetClass) =
+                    interface.clone().into();
+                Condition::all()
+                    .add(asset::Column::SpecificationVersion.is_not_null())
+                    .add(asset::Column::SpecificationAssetClass.is_not_null())
+                    .add(asset::Column::SpecificationVersion.eq(version))
+                    .add(asset::Column::SpecificationAssetClass.eq(class))
+            }
+            SearchAssetsFilter::TokenType(token_type) => {
+                token_type_condition(token_type).into_condition()
+            }
+            SearchAssetsFilter::JsonUri(uri) => Condition::all()
+                .add(asset::Column::AssetData.is_not_null())
+                .add(
+                    asset::Column::AssetData.in_subquery(
+                        Query::select()
+                            .column(asset_data::Column::Id)
+                            .from(asset_data::Entity)
+                            .and_where(asset_data::Column::MetadataUrl.eq(uri.clone()))
+                            .to_owned(),
+                    ),
+                ),
+        }
+    }
+}
+
+/// `column = value`, but false instead of NULL when the column is NULL so the predicate can be
+/// negated.
+fn not_null_eq(column: asset::Column, value: Vec<u8>) -> Condition {
+    Condition::all()
+        .add(column.is_not_null())
+        .add(column.eq(value))
+}
+
+/// Assets owned by one of `owners`. Fungible tokens have no single owner, with `fungibles` they
+/// also match when one of the owners holds a token account of the mint.
+fn owner_condition(owners: Vec<Vec<u8>>, fungibles: bool) -> Condition {
+    let condition = Condition::any().add(
+        Condition::all()
+            .add(asset::Column::Owner.is_not_null())
+            .add(asset::Column::Owner.is_in(owners.clone())),
+    );
+    if !fungibles {
+        return condition;
+    }
+    condition.add(
+        Condition::all()
+            .add(token_type_condition(&TokenTypeClass::Fungible))
+            .add(
+                asset::Column::Id.in_subquery(
+                    Query::select()
+                        .column(token_accounts::Column::Mint)
+                        .from(token_accounts::Entity)
+                        .and_where(token_accounts::Column::Owner.is_in(owners))
+                        .to_owned(),
+                ),
+            ),
+    )
+}
+
+/// Whether the asset is frozen the way the API reports it: a programmable NFT is frozen when its
+/// token record is not unlocked, since its token account always is.
+fn frozen_condition(frozen: bool) -> Condition {
+    let locked = if frozen {
+        asset::Column::TokenState.ne(TokenState::Unlocked)
+    } else {
+        asset::Column::TokenState.eq(TokenState::Unlocked)
+    };
+    Condition::any()
+        .add(
+            Condition::all()
+                .add(asset::Column::TokenState.is_null())
+                .add(asset::Column::Frozen.eq(frozen)),
+        )
+        .add(
+            Condition::all()
+                .add(asset::Column::TokenState.is_not_null())
+                .add(locked),
+        )
 }
 
 impl SearchAssetsQuery {
@@ -86,9 +432,10 @@ impl SearchAssetsQuery {
                     "`ownerType` is not supported when using `tokenType` field".to_string(),
                 ));
             }
-            if self.owner_address.is_none() {
+            if self.owner_address.is_none() && self.owner_addresses.is_none() {
                 return Err(DbErr::Custom(
-                    "Must provide `ownerAddress` when using `tokenType` field".to_string(),
+                    "Must provide `ownerAddress` or `ownerAddresses` when using `tokenType` field"
+                        .to_string(),
                 ));
             }
             if self.interface.is_some() {
@@ -109,6 +456,13 @@ impl SearchAssetsQuery {
             Some(ConditionType::Any) => Condition::any(),
         };
 
+        let owners: Vec<Vec<u8>> = self
+            .owner_address
+            .iter()
+            .chain(self.owner_addresses.iter().flatten())
+            .cloned()
+            .collect();
+
         // Joins
         let mut joins = Vec::new();
         conditions = conditions
@@ -117,67 +471,37 @@ impl SearchAssetsQuery {
                     .clone()
                     .map(|x| asset::Column::SpecificationVersion.eq(x)),
             )
-            .add_option(self.token_type.as_ref().map(|x| {
-                match x {
-                    TokenTypeClass::Compressed => asset::Column::TreeId.is_not_null(),
-                    TokenTypeClass::Nft | TokenTypeClass::NonFungible => {
-                        asset::Column::TreeId.is_null().and(
-                            asset::Column::SpecificationAssetClass
-                                .eq(SpecificationAssetClass::Nft)
-                                .or(asset::Column::SpecificationAssetClass
-                                    .eq(SpecificationAssetClass::MplCoreAsset))
-                                .or(asset::Column::SpecificationAssetClass
-                                    .eq(SpecificationAssetClass::ProgrammableNft))
-                                .or(asset::Column::SpecificationAssetClass
-                                    .eq(SpecificationAssetClass::MplCoreCollection))
-                                .or(asset::Column::SpecificationAssetClass
-                                    .eq(SpecificationAssetClass::NonTransferableNft))
-                                .or(asset::Column::SpecificationAssetClass
-                                    .eq(SpecificationAssetClass::IdentityNft))
-                                .or(asset::Column::SpecificationAssetClass
-                                    .eq(SpecificationAssetClass::Print))
-                                .or(asset::Column::SpecificationAssetClass
-                                    .eq(SpecificationAssetClass::PrintableNft))
-                                .or(asset::Column::SpecificationAssetClass
-                                    .eq(SpecificationAssetClass::TransferRestrictedNft)),
-                        )
-                    }
-                    TokenTypeClass::Fungible => asset::Column::SpecificationAssetClass
-                        .eq(SpecificationAssetClass::FungibleAsset)
-                        .or(asset::Column::SpecificationAssetClass
-                            .eq(SpecificationAssetClass::FungibleToken)),
-                    TokenTypeClass::All => asset::Column::SpecificationAssetClass.is_not_null(),
-                }
-            }))
+            .add_option(self.token_type.as_ref().map(token_type_condition))
             .add_option(
                 self.specification_asset_class
                     .clone()
                     .map(|x| asset::Column::SpecificationAssetClass.eq(x)),
             )
-            .add_option(self.owner_address.as_ref().map(|o| match self.token_type {
-                Some(TokenTypeClass::Fungible) | Some(TokenTypeClass::All) => {
-                    let rel = extensions::token_accounts::Relation::Asset
-                        .def()
-                        .rev()
-                        .on_condition(|left, right| {
-                            Expr::tbl(right, token_accounts::Column::Mint)
-                                .eq(Expr::tbl(left, asset::Column::Id))
-                                .into_condition()
-                        });
-                    joins.push(rel);
-
-                    asset::Column::Owner
-                        .eq(o.clone())
-                        .or(token_accounts::Column::Owner.eq(o.clone()))
-                }
-                _ => asset::Column::Owner.eq(o.clone()),
+            .add_option((!owners.is_empty()).then(|| {
+                owner_condition(
+                    owners,
+                    matches!(
+                        self.token_type,
+                        Some(TokenTypeClass::Fungible) | Some(TokenTypeClass::All)
+                    ),
+                )
             }))
             .add_option(
                 self.delegate
                     .to_owned()
                     .map(|x| asset::Column::Delegate.eq(x)),
             )
-            .add_option(self.frozen.map(|x| asset::Column::Frozen.eq(x)))
+            .add_option(self.frozen.map(frozen_condition))
+            .add_option(
+                self.token_state
+                    .clone()
+                    .map(|x| asset::Column::TokenState.eq(x)),
+            )
+            .add_option(
+                self.token_delegate_role
+                    .clone()
+                    .map(|x| asset::Column::TokenDelegateRole.eq(x)),
+            )
             .add_option(
                 self.supply_mint
                     .to_owned()
@@ -199,7 +523,27 @@ impl SearchAssetsQuery {
                 self.royalty_amount
                     .map(|x| asset::Column::RoyaltyAmount.eq(x)),
             )
-            .add_option(self.burnt.map(|x| asset::Column::Burnt.eq(x)));
+            .add_option(
+                self.royalty_amount_range
+                    .map(|x| range_condition(asset::Column::RoyaltyAmount, x)),
+            )
+            .add_option(self.burnt.map(|x| asset::Column::Burnt.eq(x)))
+            .add_option(
+                self.slot_updated_range
+                    .map(|x| range_condition(asset::Column::SlotUpdated, x)),
+            );
+
+        if let Some(range) = self.created_at_range {
+            let timestamp = |secs: i64| {
+                DateTime::<Utc>::from_timestamp(secs, 0)
+                    .ok_or_else(|| DbErr::Custom(format!("Invalid timestamp {}", secs)))
+            };
+            let range = RangeFilter {
+                min: range.min.map(timestamp).transpose()?,
+                max: range.max.map(timestamp).transpose()?,
+            };
+            conditions = conditions.add(range_condition(asset::Column::CreatedAt, range));
+        }
 
         if let Some(s) = self.supply {
             conditions = conditions.add(asset::Column::Supply.eq(s));
@@ -285,47 +629,56 @@ impl SearchAssetsQuery {
             joins.push(rel);
         }
 
-        if let Some(g) = self.grouping.to_owned() {
-            let cond = Condition::all()
-                .add(asset_grouping::Column::GroupKey.eq(g.0))
-                .add(asset_grouping::Column::GroupValue.eq(g.1));
-            conditions = conditions.add(cond);
-            let rel = extensions::asset_grouping::Relation::Asset
-                .def()
-                .rev()
-                .on_condition(|left, right| {
-                    Expr::tbl(right, asset_grouping::Column::AssetId)
-                        .eq(Expr::tbl(left, asset::Column::Id))
-                        .into_condition()
+        let groupings: Vec<&(String, String)> = self
+            .grouping
+            .iter()
+            .chain(self.groupings.iter().flatten())
+            .collect();
+        if !groupings.is_empty() {
+            let cond = groupings
+                .into_iter()
+                .fold(Condition::any(), |cond, (key, value)| {
+                    cond.add(
+                        Condition::all()
+                            .add(asset_grouping::Column::GroupKey.eq(key.clone()))
+                            .add(asset_grouping::Column::GroupValue.eq(value.clone())),
+                    )
                 });
-            joins.push(rel);
+            // A subquery rather than a join, so an asset in several of the groups is returned once.
+            conditions = conditions.add(
+                asset::Column::Id.in_subquery(
+                    Query::select()
+                        .column(asset_grouping::Column::AssetId)
+                        .from(asset_grouping::Entity)
+                        .cond_where(cond)
+                        .to_owned(),
+                ),
+            );
         }
 
         if let Some(ju) = self.json_uri.to_owned() {
-            let cond = Condition::all().add(asset_data::Column::MetadataUrl.eq(ju));
-            conditions = conditions.add(cond);
-            let rel = extensions::asset_data::Relation::Asset
-                .def()
-                .rev()
-                .on_condition(|left, right| {
-                    Expr::tbl(right, asset_data::Column::Id)
-                        .eq(Expr::tbl(left, asset::Column::AssetData))
-                        .into_condition()
-                });
-            joins.push(rel);
+            conditions = conditions.add(asset_data::Column::MetadataUrl.eq(ju));
         }
 
-        if let Some(n) = self.name.to_owned() {
-            let name_as_str = std::str::from_utf8(&n).map_err(|_| {
-                DbErr::Custom(
-                    "Could not convert raw name bytes into string for comparison".to_owned(),
-                )
-            })?;
+        if let Some(n) = self.name.as_deref() {
+            conditions = conditions.add(name_condition(
+                n,
+                self.name_match.unwrap_or_default(),
+                self.pg_trgm_schema.as_deref(),
+            ));
+        }
 
-            let name_expr =
-                SimpleExpr::Custom(format!("chain_data->>'name' LIKE '%{}%'", name_as_str));
+        if let Some(attributes) = self.attributes.as_ref() {
+            conditions = conditions.add(attributes_condition(attributes));
+        }
+
+        if let Some(filter) = self.filter.as_ref() {
+            conditions = conditions.add(filter.condition());
+        }
+
+        conditions = conditions.add_option(self.token_extensions.condition());
 
-            conditions = conditions.add(name_expr);
+        if self.json_uri.is_some() || self.name.is_some() || self.attributes.is_some() {
             let rel = extensions::asset_data::Relation::Asset
                 .def()
                 .rev()
@@ -345,4 +698,139 @@ impl SearchAssetsQuery {
             joins,
         ))
     }
+
+    /// How well an asset name matches the `name` filter, higher is better.
+    pub fn name_rank(&self) -> Option<SimpleExpr> {
+        self.name.as_ref().map(|n| {
+            Expr::cust_with_values(
+                &format!(
+                    "{}similarity(asset_data.chain_data ->> 'name', $1)",
+                    schema_prefix(self.pg_trgm_schema.as_deref())
+                ),
+                [n.clone()],
+            )
+        })
+    }
+}
+
+fn range_condition<T>(column: asset::Column, range: RangeFilter<T>) -> Condition
+where
+    T: Into<sea_orm::Value>,
+{
+    Condition::all()
+        .add_option(range.min.map(|min| column.gte(min)))
+        .add_option(range.max.map(|max| column.lte(max)))
+}
+
+fn token_type_condition(token_type: &TokenTypeClass) -> SimpleExpr {
+    // False rather than NULL for a NULL class, so the checks can be negated.
+    let has_class = asset::Column::SpecificationAssetClass.is_not_null();
+    match token_type {
+        TokenTypeClass::Compressed => asset::Column::TreeId.is_not_null(),
+        TokenTypeClass::Nft | TokenTypeClass::NonFungible => asset::Column::TreeId.is_null().and(
+            has_class.and(
+                asset::Column::SpecificationAssetClass
+                    .eq(SpecificationAssetClass::Nft)
+                    .or(asset::Column::SpecificationAssetClass
+                        .eq(SpecificationAssetClass::MplCoreAsset))
+                    .or(asset::Column::SpecificationAssetClass
+                        .eq(SpecificationAssetClass::ProgrammableNft))
+                    .or(asset::Column::SpecificationAssetClass
+                        .eq(SpecificationAssetClass::MplCoreCollection))
+                    .or(asset::Column::SpecificationAssetClass
+                        .eq(SpecificationAssetClass::NonTransferableNft))
+                    .or(asset::Column::SpecificationAssetClass
+                        .eq(SpecificationAssetClass::IdentityNft))
+                    .or(asset::Column::SpecificationAssetClass.eq(SpecificationAssetClass::Print))
+                    .or(asset::Column::SpecificationAssetClass
+                        .eq(SpecificationAssetClass::PrintableNft))
+                    .or(asset::Column::SpecificationAssetClass
+                        .eq(SpecificationAssetClass::TransferRestrictedNft)),
+            ),
+        ),
+        TokenTypeClass::Fungible => has_class.and(
+            asset::Column::SpecificationAssetClass
+                .eq(SpecificationAssetClass::FungibleAsset)
+                .or(asset::Column::SpecificationAssetClass
+                    .eq(SpecificationAssetClass::FungibleToken)),
+        ),
+        TokenTypeClass::All => has_class,
+    }
+}
+
+/// Name predicates are written against `asset_data.chain_data ->> 'name'` so they can use the
+/// `asset_data_name_trgm_idx` trigram index.
+fn name_condition(name: &str, name_match: NameMatch, pg_trgm_schema: Option<&str>) -> SimpleExpr {
+    match name_match {
+        NameMatch::Exact => {
+            Expr::cust_with_values("asset_data.chain_data ->> 'name' = $1", [name.to_owned()])
+        }
+        NameMatch::Prefix => Expr::cust_with_values(
+            "asset_data.chain_data ->> 'name' ILIKE $1",
+            [format!("{}%", escape_like(name))],
+        ),
+        NameMatch::Contains => Expr::cust_with_values(
+            "asset_data.chain_data ->> 'name' ILIKE $1",
+            [format!("%{}%", escape_like(name))],
+        ),
+        NameMatch::Fuzzy => Expr::cust_with_values(
+            &format!(
+                "(asset_data.chain_data ->> 'name') OPERATOR({}%) $1",
+                schema_prefix(pg_trgm_schema)
+            ),
+            [name.to_owned()],
+        ),
+    }
+}
+
+fn schema_prefix(schema: Option<&str>) -> String {
+    schema
+        .map(|schema| format!("{}.", schema))
+        .unwrap_or_default()
+}
+
+/// Escapes the `LIKE` wildcards so user input only ever matches literally.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn attributes_condition(attributes: &AttributeFilters) -> Condition {
+    let condition = match attributes.condition_type {
+        None | Some(SearchConditionType::All) => Condition::all(),
+        Some(SearchConditionType::Any) => Condition::any(),
+    };
+    attributes
+        .traits
+        .iter()
+        .fold(condition, |condition, filter| {
+            condition.add(attribute_condition(filter))
+        })
+}
+
+/// Matches any of the trait values through JSONB containment on the off-chain `attributes`
+/// array, which is backed by the `asset_data_attributes_idx` GIN index. Numeric values are
+/// also matched against their JSON number form since metadata uses both.
+fn attribute_condition(filter: &AttributeFilter) -> Condition {
+    filter
+        .values
+        .iter()
+        .flat_map(|value| {
+            let number = serde_json::from_str::<serde_json::Number>(value)
+                .ok()
+                .map(serde_json::Value::Number);
+            std::iter::once(serde_json::Value::String(value.clone())).chain(number)
+        })
+        .fold(Condition::any(), |condition, value| {
+            let attribute = serde_json::json!([{
+                "trait_type": filter.trait_type,
+                "value": value,
+            }]);
+            condition.add(Expr::cust_with_values(
+                "asset_data.metadata -> 'attributes' @> $1",
+                [attribute],
+            ))
+        })
 }
@@ -1,27 +1,47 @@
 use crate::{
     dao::{
         asset::{self},
-        asset_authority, asset_creators, asset_data, asset_grouping, asset_v1_account_attachments,
-        cl_audits_v2,
-        extensions::{self, instruction::PascalCase},
+        asset_authority, asset_creators, asset_data, asset_grouping, asset_leaf_history,
+        asset_signatures, asset_v1_account_attachments, cl_audits_v2,
+        extensions::{self, asset_grouping::TOKEN_GROUP_KEY, instruction::PascalCase},
         sea_orm_active_enums::{Instruction, V1AccountAttachments},
-        token_accounts, tokens, Cursor, FullAsset, GroupingSize, Pagination,
+        token_accounts, token_groups, tokens, Cursor, FullAsset, GrandTotal, GroupingSize,
+        Pagination, SignatureCursor, TokenExtensionFilters,
     },
     rpc::{
         filter::AssetSortDirection,
         options::Options,
-        response::{NftEdition, NftEditions},
+        response::{NftEdition, NftEditions, TransactionSignature},
     },
 };
+use chrono::{DateTime, Utc};
 use indexmap::IndexMap;
-use mpl_token_metadata::accounts::{Edition, MasterEdition};
+use mpl_token_metadata::accounts::{Edition, EditionMarker, MasterEdition};
 use sea_orm::{
-    entity::*, prelude::Decimal, query::*, sea_query::Expr, ConnectionTrait, DbErr, Order,
+    entity::*,
+    prelude::Decimal,
+    query::*,
+    sea_query::{Expr, SimpleExpr},
+    ColumnType, ConnectionTrait, DbErr, Order, RuntimeErr, Statement, TransactionTrait,
 };
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// Upper bound on the time spent counting matches for `showGrandTotal`.
+const GRAND_TOTAL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// SQLSTATE Postgres reports when a statement is cancelled by `statement_timeout`.
+const QUERY_CANCELED: &str = "57014";
+
+/// Number of editions tracked by a single edition marker account.
+const EDITION_MARKER_BIT_SIZE: u64 = 248;
+
+/// Upper bound on the edition markers looked up for a master edition, which covers the first
+/// 63,488 edition numbers.
+const MAX_EDITION_MARKERS: u64 = 256;
 
 pub fn paginate<T, C>(
     pagination: &Pagination,
@@ -29,6 +49,7 @@ pub fn paginate<T, C>(
     stmt: T,
     sort_direction: Order,
     column: C,
+    sort_column: Option<C>,
 ) -> T
 where
     T: QueryFilter + QuerySelect,
@@ -51,17 +72,93 @@ where
         }
         Pagination::Cursor(cursor) => {
             if *cursor != Cursor::default() {
-                if sort_direction == sea_orm::Order::Asc {
-                    stmt = stmt.filter(column.gt(cursor.id.clone()));
-                } else {
-                    stmt = stmt.filter(column.lt(cursor.id.clone()));
-                }
+                stmt = match sort_column {
+                    Some(sort_column) => stmt.filter(sort_keyset_condition(
+                        cursor,
+                        sort_direction,
+                        column,
+                        sort_column,
+                    )),
+                    None if sort_direction == sea_orm::Order::Asc => {
+                        stmt.filter(column.gt(cursor.id.clone()))
+                    }
+                    None => stmt.filter(column.lt(cursor.id.clone())),
+                };
             }
         }
     }
     stmt.limit(limit)
 }
 
+/// Rows after `cursor` when ordering by `(sort_column, column)`. Postgres sorts nulls last when
+/// ascending and first when descending, so rows without a sort value need their own branch.
+fn sort_keyset_condition<C: ColumnTrait>(
+    cursor: &Cursor,
+    sort_direction: Order,
+    column: C,
+    sort_column: C,
+) -> Condition {
+    let asc = sort_direction == sea_orm::Order::Asc;
+    let id = cursor.id.clone();
+    let after_id = if asc { column.gt(id) } else { column.lt(id) };
+
+    match cursor
+        .sort_value
+        .map(|v| cursor_sort_value(&sort_column, v))
+    {
+        Some(value) => {
+            let after_value = if asc {
+                sort_column.gt(value.clone())
+            } else {
+                sort_column.lt(value.clone())
+            };
+            let mut condition = Condition::any()
+                .add(after_value)
+                .add(sort_column.eq(value).and(after_id));
+            if asc {
+                condition = condition.add(sort_column.is_null());
+            }
+            condition
+        }
+        None => {
+            let mut condition = Condition::any().add(sort_column.is_null().and(after_id));
+            if !asc {
+                condition = condition.add(sort_column.is_not_null());
+            }
+            condition
+        }
+    }
+}
+
+fn cursor_sort_value<C: ColumnTrait>(sort_column: &C, sort_value: i64) -> sea_orm::Value {
+    match sort_column.def().get_column_type() {
+        ColumnType::TimestampWithTimeZone | ColumnType::Timestamp => {
+            DateTime::<Utc>::from_timestamp_micros(sort_value)
+                .map(Into::into)
+                .unwrap_or_else(|| sort_value.into())
+        }
+        _ => sort_value.into(),
+    }
+}
+
+/// The sort column to use for cursor pagination, sorting by id only needs the id itself.
+const fn keyset_sort_column(sort_by: Option<asset::Column>) -> Option<asset::Column> {
+    match sort_by {
+        Some(asset::Column::Id) | None => None,
+        Some(col) => Some(col),
+    }
+}
+
+fn creator_condition(creator: Vec<u8>, only_verified: bool) -> Condition {
+    let mut condition = Condition::all()
+        .add(asset_creators::Column::Creator.eq(creator))
+        .add(asset::Column::Supply.gt(0));
+    if only_verified {
+        condition = condition.add(asset_creators::Column::Verified.eq(true));
+    }
+    condition
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn get_by_creator(
     conn: &impl ConnectionTrait,
@@ -73,15 +170,9 @@ pub async fn get_by_creator(
     limit: u64,
     options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
-    let mut condition = Condition::all()
-        .add(asset_creators::Column::Creator.eq(creator.clone()))
-        .add(asset::Column::Supply.gt(0));
-    if only_verified {
-        condition = condition.add(asset_creators::Column::Verified.eq(true));
-    }
     get_by_related_condition(
         conn,
-        condition,
+        creator_condition(creator.clone(), only_verified),
         extensions::asset::Relation::AssetCreators,
         sort_by,
         sort_direction,
@@ -93,11 +184,37 @@ pub async fn get_by_creator(
     .await
 }
 
+pub async fn count_by_creator(
+    conn: &(impl ConnectionTrait + TransactionTrait),
+    creator: Vec<u8>,
+    only_verified: bool,
+) -> Result<GrandTotal, DbErr> {
+    count_assets(
+        conn,
+        related_condition_query(
+            creator_condition(creator, only_verified),
+            extensions::asset::Relation::AssetCreators,
+        ),
+    )
+    .await
+}
+
 pub async fn get_grouping(
     conn: &impl ConnectionTrait,
     group_key: String,
     group_value: String,
 ) -> Result<GroupingSize, DbErr> {
+    if group_key == TOKEN_GROUP_KEY {
+        if let Ok(group) = bs58::decode(&group_value).into_vec() {
+            if let Some(group) = token_groups::Entity::find_by_id(group).one(conn).await? {
+                return Ok(GroupingSize {
+                    size: group.size as u64,
+                    max_size: Some(group.max_size as u64),
+                });
+            }
+        }
+    }
+
     let size = asset_grouping::Entity::find()
         .filter(
             Condition::all()
@@ -111,20 +228,13 @@ pub async fn get_grouping(
         )
         .count(conn)
         .await?;
-    Ok(GroupingSize { size })
+    Ok(GroupingSize {
+        size,
+        max_size: None,
+    })
 }
 
-#[allow(clippy::too_many_arguments)]
-pub async fn get_by_grouping(
-    conn: &impl ConnectionTrait,
-    group_key: String,
-    group_value: String,
-    sort_by: Option<asset::Column>,
-    sort_direction: Order,
-    pagination: &Pagination,
-    limit: u64,
-    options: &Options,
-) -> Result<Vec<FullAsset>, DbErr> {
+fn grouping_condition(group_key: String, group_value: String, options: &Options) -> Condition {
     let mut condition = asset_grouping::Column::GroupKey
         .eq(group_key)
         .and(asset_grouping::Column::GroupValue.eq(group_value));
@@ -137,11 +247,25 @@ pub async fn get_by_grouping(
         );
     }
 
+    Condition::all()
+        .add(condition)
+        .add(asset::Column::Supply.gt(0))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn get_by_grouping(
+    conn: &impl ConnectionTrait,
+    group_key: String,
+    group_value: String,
+    sort_by: Option<asset::Column>,
+    sort_direction: Order,
+    pagination: &Pagination,
+    limit: u64,
+    options: &Options,
+) -> Result<Vec<FullAsset>, DbErr> {
     get_by_related_condition(
         conn,
-        Condition::all()
-            .add(condition)
-            .add(asset::Column::Supply.gt(0)),
+        grouping_condition(group_key, group_value, options),
         extensions::asset::Relation::AssetGrouping,
         sort_by,
         sort_direction,
@@ -153,22 +277,42 @@ pub async fn get_by_grouping(
     .await
 }
 
+pub async fn count_by_grouping(
+    conn: &(impl ConnectionTrait + TransactionTrait),
+    group_key: String,
+    group_value: String,
+    options: &Options,
+) -> Result<GrandTotal, DbErr> {
+    count_assets(
+        conn,
+        related_condition_query(
+            grouping_condition(group_key, group_value, options),
+            extensions::asset::Relation::AssetGrouping,
+        ),
+    )
+    .await
+}
+
+fn owner_condition(owner: Vec<u8>) -> Condition {
+    Condition::all()
+        .add(asset::Column::Owner.eq(owner))
+        .add(asset::Column::Supply.gt(0))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn get_assets_by_owner(
     conn: &impl ConnectionTrait,
     owner: Vec<u8>,
+    token_extensions: &TokenExtensionFilters,
     sort_by: Option<asset::Column>,
     sort_direction: Order,
     pagination: &Pagination,
     limit: u64,
     options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
-    let cond = Condition::all()
-        .add(asset::Column::Owner.eq(owner.clone()))
-        .add(asset::Column::Supply.gt(0));
-
     get_assets_by_condition(
         conn,
-        cond,
+        owner_condition(owner).add_option(token_extensions.condition()),
         vec![],
         sort_by,
         sort_direction,
@@ -179,6 +323,19 @@ pub async fn get_assets_by_owner(
     .await
 }
 
+pub async fn count_assets_by_owner(
+    conn: &(impl ConnectionTrait + TransactionTrait),
+    owner: Vec<u8>,
+    token_extensions: &TokenExtensionFilters,
+) -> Result<GrandTotal, DbErr> {
+    count_assets_by_condition(
+        conn,
+        owner_condition(owner).add_option(token_extensions.condition()),
+        vec![],
+    )
+    .await
+}
+
 pub async fn get_assets(
     conn: &impl ConnectionTrait,
     asset_ids: Vec<Vec<u8>>,
@@ -204,6 +361,12 @@ pub async fn get_assets(
     .await
 }
 
+fn authority_condition(authority: Vec<u8>) -> Condition {
+    Condition::all()
+        .add(asset_authority::Column::Authority.eq(authority))
+        .add(asset::Column::Supply.gt(0))
+}
+
 pub async fn get_by_authority(
     conn: &impl ConnectionTrait,
     authority: Vec<u8>,
@@ -213,12 +376,9 @@ pub async fn get_by_authority(
     limit: u64,
     options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
-    let cond = Condition::all()
-        .add(asset_authority::Column::Authority.eq(authority))
-        .add(asset::Column::Supply.gt(0));
     get_by_related_condition(
         conn,
-        cond,
+        authority_condition(authority),
         extensions::asset::Relation::AssetAuthority,
         sort_by,
         sort_direction,
@@ -230,6 +390,29 @@ pub async fn get_by_authority(
     .await
 }
 
+pub async fn count_by_authority(
+    conn: &(impl ConnectionTrait + TransactionTrait),
+    authority: Vec<u8>,
+) -> Result<GrandTotal, DbErr> {
+    count_assets(
+        conn,
+        related_condition_query(
+            authority_condition(authority),
+            extensions::asset::Relation::AssetAuthority,
+        ),
+    )
+    .await
+}
+
+fn related_condition_query<E>(condition: Condition, relation: E) -> Select<asset::Entity>
+where
+    E: RelationTrait,
+{
+    asset::Entity::find()
+        .filter(condition)
+        .join(JoinType::LeftJoin, relation.def())
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn get_by_related_condition<E>(
     conn: &impl ConnectionTrait,
@@ -245,9 +428,7 @@ async fn get_by_related_condition<E>(
 where
     E: RelationTrait,
 {
-    let mut stmt = asset::Entity::find()
-        .filter(condition)
-        .join(JoinType::LeftJoin, relation.def());
+    let mut stmt = related_condition_query(condition, relation);
 
     if let Some(col) = sort_by {
         stmt = stmt
@@ -255,9 +436,16 @@ where
             .order_by(asset::Column::Id, sort_direction.clone());
     }
 
-    let assets = paginate(pagination, limit, stmt, sort_direction, asset::Column::Id)
-        .all(conn)
-        .await?;
+    let assets = paginate(
+        pagination,
+        limit,
+        stmt,
+        sort_direction,
+        asset::Column::Id,
+        keyset_sort_column(sort_by),
+    )
+    .all(conn)
+    .await?;
     get_related_for_assets(conn, assets, options, required_creator).await
 }
 
@@ -403,6 +591,14 @@ pub async fn get_related_for_assets(
     Ok(assets_map.into_iter().map(|(_, v)| v).collect())
 }
 
+fn condition_query(condition: Condition, joins: Vec<RelationDef>) -> Select<asset::Entity> {
+    let mut stmt = asset::Entity::find();
+    for def in joins {
+        stmt = stmt.join(JoinType::LeftJoin, def);
+    }
+    stmt.filter(condition)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn get_assets_by_condition(
     conn: &impl ConnectionTrait,
@@ -414,24 +610,156 @@ pub async fn get_assets_by_condition(
     limit: u64,
     options: &Options,
 ) -> Result<Vec<FullAsset>, DbErr> {
-    let mut stmt = asset::Entity::find();
-    for def in joins {
-        stmt = stmt.join(JoinType::LeftJoin, def);
-    }
-    stmt = stmt.filter(condition);
+    let mut stmt = condition_query(condition, joins);
     if let Some(col) = sort_by {
         stmt = stmt
             .order_by(col, sort_direction.clone())
             .order_by(asset::Column::Id, sort_direction.clone());
     }
 
-    let assets = paginate(pagination, limit, stmt, sort_direction, asset::Column::Id)
-        .all(conn)
-        .await?;
+    let assets = paginate(
+        pagination,
+        limit,
+        stmt,
+        sort_direction,
+        asset::Column::Id,
+        keyset_sort_column(sort_by),
+    )
+    .all(conn)
+    .await?;
     let full_assets = get_related_for_assets(conn, assets, options, None).await?;
     Ok(full_assets)
 }
 
+/// Orders by `rank` with the asset id as tie breaker. Ranks are not stable across requests, so
+/// only page based pagination is supported.
+#[allow(clippy::too_many_arguments)]
+pub async fn get_assets_by_rank(
+    conn: &impl ConnectionTrait,
+    condition: Condition,
+    joins: Vec<RelationDef>,
+    rank: SimpleExpr,
+    sort_direction: Order,
+    pagination: &Pagination,
+    limit: u64,
+    options: &Options,
+) -> Result<Vec<FullAsset>, DbErr> {
+    let stmt = condition_query(condition, joins)
+        .order_by(rank, sort_direction.clone())
+        .order_by(asset::Column::Id, sort_direction.clone());
+
+    let assets = paginate(
+        pagination,
+        limit,
+        stmt,
+        sort_direction,
+        asset::Column::Id,
+        None,
+    )
+    .all(conn)
+    .await?;
+    let full_assets = get_related_for_assets(conn, assets, options, None).await?;
+    Ok(full_assets)
+}
+
+/// Schema pg_trgm is installed in, as an identifier that can qualify its functions and operators.
+pub async fn get_pg_trgm_schema(conn: &impl ConnectionTrait) -> Result<String, DbErr> {
+    conn.query_one(Statement::from_string(
+        conn.get_database_backend(),
+        "SELECT extnamespace::regnamespace::text AS schema FROM pg_extension WHERE extname = 'pg_trgm'"
+            .to_string(),
+    ))
+    .await?
+    .ok_or_else(|| DbErr::Custom("pg_trgm is not installed".to_string()))?
+    .try_get("", "schema")
+}
+
+pub async fn count_assets_by_condition(
+    conn: &(impl ConnectionTrait + TransactionTrait),
+    condition: Condition,
+    joins: Vec<RelationDef>,
+) -> Result<GrandTotal, DbErr> {
+    count_assets(conn, condition_query(condition, joins)).await
+}
+
+/// Counts the distinct assets matched by `stmt`. The count runs with a statement timeout and
+/// falls back to the planner estimate when it takes too long, so large owners and collections
+/// cannot stall the request.
+async fn count_assets(
+    conn: &(impl ConnectionTrait + TransactionTrait),
+    stmt: Select<asset::Entity>,
+) -> Result<GrandTotal, DbErr> {
+    let backend = conn.get_database_backend();
+    let count_stmt = stmt
+        .clone()
+        .select_only()
+        .column_as(Expr::cust("COUNT(DISTINCT asset.id)"), "count")
+        .build(backend);
+
+    let txn = conn.begin().await?;
+    txn.execute(Statement::from_string(
+        backend,
+        format!(
+            "SET LOCAL statement_timeout = {}",
+            GRAND_TOTAL_TIMEOUT.as_millis()
+        ),
+    ))
+    .await?;
+    match txn.query_one(count_stmt).await {
+        Ok(row) => {
+            let count: i64 = row
+                .map(|row| row.try_get("", "count"))
+                .transpose()?
+                .unwrap_or_default();
+            txn.commit().await?;
+            Ok(GrandTotal {
+                total: count as u64,
+                estimated: false,
+            })
+        }
+        Err(err) if is_query_canceled(&err) => {
+            txn.rollback().await?;
+            estimate_assets(conn, stmt).await
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn is_query_canceled(err: &DbErr) -> bool {
+    match err {
+        DbErr::Query(RuntimeErr::SqlxError(err)) | DbErr::Exec(RuntimeErr::SqlxError(err)) => err
+            .as_database_error()
+            .and_then(|err| err.code())
+            .map_or(false, |code| code == QUERY_CANCELED),
+        _ => false,
+    }
+}
+
+async fn estimate_assets(
+    conn: &impl ConnectionTrait,
+    stmt: Select<asset::Entity>,
+) -> Result<GrandTotal, DbErr> {
+    let backend = conn.get_database_backend();
+    let query = stmt.build(backend);
+    let explain = Statement {
+        sql: format!("EXPLAIN (FORMAT JSON) {}", query.sql),
+        ..query
+    };
+    let plan: Value = conn
+        .query_one(explain)
+        .await?
+        .ok_or_else(|| DbErr::Custom("Query plan not found".to_string()))?
+        .try_get("", "QUERY PLAN")?;
+    let rows = plan
+        .pointer("/0/Plan/Plan Rows")
+        .and_then(Value::as_f64)
+        .unwrap_or_default();
+    Ok(GrandTotal {
+        total: rows as u64,
+        estimated: true,
+    })
+}
+
 pub async fn get_by_id(
     conn: &impl ConnectionTrait,
     asset_id: Vec<u8>,
@@ -515,60 +843,171 @@ pub async fn get_by_id(
     })
 }
 
+fn signature_sort_order(sort_direction: Option<AssetSortDirection>) -> Order {
+    // Default sort direction is Desc
+    // Similar to GetSignaturesForAddress in the Solana API
+    match sort_direction.unwrap_or(AssetSortDirection::Desc) {
+        AssetSortDirection::Asc => Order::Asc,
+        AssetSortDirection::Desc => Order::Desc,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn fetch_transactions(
     conn: &impl ConnectionTrait,
     tree: Vec<u8>,
     leaf_idx: i64,
+    instruction: Option<String>,
     pagination: &Pagination,
+    cursor: Option<SignatureCursor>,
     limit: u64,
     sort_direction: Option<AssetSortDirection>,
-) -> Result<Vec<(String, String)>, DbErr> {
-    // Default sort direction is Desc
-    // Similar to GetSignaturesForAddress in the Solana API
-    let sort_direction = sort_direction.unwrap_or(AssetSortDirection::Desc);
-    let sort_order = match sort_direction {
-        AssetSortDirection::Asc => sea_orm::Order::Asc,
-        AssetSortDirection::Desc => sea_orm::Order::Desc,
-    };
+) -> Result<(Vec<TransactionSignature>, Option<SignatureCursor>), DbErr> {
+    let sort_order = signature_sort_order(sort_direction);
 
     let mut stmt = cl_audits_v2::Entity::find().filter(cl_audits_v2::Column::Tree.eq(tree));
     stmt = stmt.filter(cl_audits_v2::Column::LeafIdx.eq(leaf_idx));
+    if let Some(instruction) = instruction {
+        let ix = Instruction::from(instruction.as_str());
+        // Names of other programs' instructions never match a Bubblegum change.
+        if ix == Instruction::Unknown && instruction != "Unknown" {
+            return Ok((Vec::new(), None));
+        }
+        stmt = stmt.filter(cl_audits_v2::Column::Instruction.eq(ix));
+    }
     stmt = stmt.order_by(cl_audits_v2::Column::Seq, sort_order.clone());
 
-    stmt = paginate(
-        pagination,
-        limit,
-        stmt,
-        sort_order,
-        cl_audits_v2::Column::Seq,
-    );
+    stmt = match cursor {
+        Some(cursor) => {
+            let seq = cl_audits_v2::Column::Seq;
+            let stmt = match sort_order {
+                Order::Asc => stmt.filter(seq.gt(cursor.position)),
+                _ => stmt.filter(seq.lt(cursor.position)),
+            };
+            stmt.limit(limit)
+        }
+        None => paginate(
+            pagination,
+            limit,
+            stmt,
+            sort_order,
+            cl_audits_v2::Column::Seq,
+            None,
+        ),
+    };
     let transactions = stmt.all(conn).await?;
+    let next_cursor = transactions.last().map(|transaction| SignatureCursor {
+        position: transaction.seq,
+        id: None,
+    });
     let transaction_list = transactions
         .into_iter()
-        .map(|transaction| {
-            let tx = bs58::encode(transaction.tx).into_string();
-            let ix = Instruction::to_pascal_case(&transaction.instruction).to_string();
-            (tx, ix)
+        .map(|transaction| TransactionSignature {
+            signature: bs58::encode(transaction.tx).into_string(),
+            instruction: Instruction::to_pascal_case(&transaction.instruction).to_string(),
+            slot: transaction.slot.map(|slot| slot as u64),
+            seq: Some(transaction.seq as u64),
+            created_at: Some(transaction.created_at.and_utc().to_rfc3339()),
+            tree: Some(bs58::encode(transaction.tree).into_string()),
+            leaf_index: Some(transaction.leaf_idx as u64),
         })
         .collect();
 
-    Ok(transaction_list)
+    Ok((transaction_list, next_cursor))
 }
 
+/// Signatures recorded for an uncompressed asset, ordered by slot with the row id breaking ties.
+pub async fn fetch_asset_signatures(
+    conn: &impl ConnectionTrait,
+    asset_id: Vec<u8>,
+    instruction: Option<String>,
+    pagination: &Pagination,
+    cursor: Option<SignatureCursor>,
+    limit: u64,
+    sort_direction: Option<AssetSortDirection>,
+) -> Result<(Vec<TransactionSignature>, Option<SignatureCursor>), DbErr> {
+    let sort_order = signature_sort_order(sort_direction);
+
+    let mut stmt =
+        asset_signatures::Entity::find().filter(asset_signatures::Column::AssetId.eq(asset_id));
+    if let Some(instruction) = instruction {
+        stmt = stmt.filter(asset_signatures::Column::Instruction.eq(instruction));
+    }
+    stmt = stmt
+        .order_by(asset_signatures::Column::Slot, sort_order.clone())
+        .order_by(asset_signatures::Column::Id, sort_order.clone());
+
+    stmt = match cursor {
+        Some(cursor) => {
+            let (slot, id) = (asset_signatures::Column::Slot, asset_signatures::Column::Id);
+            let id_value = cursor.id.unwrap_or_default();
+            let condition = match sort_order {
+                Order::Asc => Condition::any()
+                    .add(slot.gt(cursor.position))
+                    .add(slot.eq(cursor.position).and(id.gt(id_value))),
+                _ => Condition::any()
+                    .add(slot.lt(cursor.position))
+                    .add(slot.eq(cursor.position).and(id.lt(id_value))),
+            };
+            stmt.filter(condition).limit(limit)
+        }
+        None => paginate(
+            pagination,
+            limit,
+            stmt,
+            sort_order,
+            asset_signatures::Column::Slot,
+            None,
+        ),
+    };
+    let signatures = stmt.all(conn).await?;
+    let next_cursor = signatures.last().map(|signature| SignatureCursor {
+        position: signature.slot,
+        id: Some(signature.id),
+    });
+    let signature_list = signatures
+        .into_iter()
+        .map(|signature| TransactionSignature {
+            signature: bs58::encode(signature.signature).into_string(),
+            instruction: signature
+                .instruction
+                .unwrap_or_else(|| "Unknown".to_string()),
+            slot: Some(signature.slot as u64),
+            seq: None,
+            created_at: Some(signature.created_at.and_utc().to_rfc3339()),
+            tree: None,
+            leaf_index: None,
+        })
+        .collect();
+
+    Ok((signature_list, next_cursor))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn get_asset_signatures(
     conn: &impl ConnectionTrait,
     asset_id: Option<Vec<u8>>,
     tree_id: Option<Vec<u8>>,
     leaf_idx: Option<i64>,
+    instruction: Option<String>,
     pagination: &Pagination,
+    cursor: Option<SignatureCursor>,
     limit: u64,
     sort_direction: Option<AssetSortDirection>,
-) -> Result<Vec<(String, String)>, DbErr> {
+) -> Result<(Vec<TransactionSignature>, Option<SignatureCursor>), DbErr> {
     // if tree_id and leaf_idx are provided, use them directly to fetch transactions
     if let (Some(tree_id), Some(leaf_idx)) = (tree_id, leaf_idx) {
-        let transactions =
-            fetch_transactions(conn, tree_id, leaf_idx, pagination, limit, sort_direction).await?;
-        return Ok(transactions);
+        return fetch_transactions(
+            conn,
+            tree_id,
+            leaf_idx,
+            instruction,
+            pagination,
+            cursor,
+            limit,
+            sort_direction,
+        )
+        .await;
     }
 
     if asset_id.is_none() {
@@ -586,6 +1025,18 @@ pub async fn get_asset_signatures(
         .limit(1);
     let asset = stmt.one(conn).await?;
     if let Some(asset) = asset {
+        if !asset.compressed {
+            return fetch_asset_signatures(
+                conn,
+                asset.id,
+                instruction,
+                pagination,
+                cursor,
+                limit,
+                sort_direction,
+            )
+            .await;
+        }
         let tree = asset
             .tree_id
             .ok_or(DbErr::RecordNotFound("Tree not found".to_string()))?;
@@ -595,14 +1046,86 @@ pub async fn get_asset_signatures(
         let leaf_idx = asset
             .nonce
             .ok_or(DbErr::RecordNotFound("Leaf ID does not exist".to_string()))?;
-        let transactions =
-            fetch_transactions(conn, tree, leaf_idx, pagination, limit, sort_direction).await?;
-        Ok(transactions)
+        fetch_transactions(
+            conn,
+            tree,
+            leaf_idx,
+            instruction,
+            pagination,
+            cursor,
+            limit,
+            sort_direction,
+        )
+        .await
     } else {
-        Ok(Vec::new())
+        Ok((Vec::new(), None))
     }
 }
 
+/// Leaf states recorded for a compressed asset, oldest first.
+pub async fn get_asset_leaf_history(
+    conn: &impl ConnectionTrait,
+    asset_id: Vec<u8>,
+) -> Result<Vec<asset_leaf_history::Model>, DbErr> {
+    asset_leaf_history::Entity::find()
+        .filter(asset_leaf_history::Column::AssetId.eq(asset_id))
+        .order_by_asc(asset_leaf_history::Column::Seq)
+        .all(conn)
+        .await
+}
+
+/// Use, collection and metadata delegate records approved for `asset_id` that have not been
+/// revoked.
+pub async fn get_authority_records(
+    conn: &impl ConnectionTrait,
+    asset_id: Vec<u8>,
+) -> Result<Vec<asset_v1_account_attachments::Model>, DbErr> {
+    asset_v1_account_attachments::Entity::find()
+        .filter(asset_v1_account_attachments::Column::AssetId.eq(asset_id))
+        .filter(
+            Condition::any()
+                .add(
+                    asset_v1_account_attachments::Column::AttachmentType
+                        .eq(V1AccountAttachments::UseAuthorityRecord),
+                )
+                .add(
+                    asset_v1_account_attachments::Column::AttachmentType
+                        .eq(V1AccountAttachments::CollectionAuthorityRecord),
+                )
+                .add(
+                    asset_v1_account_attachments::Column::AttachmentType
+                        .eq(V1AccountAttachments::MetadataDelegateRecord),
+                ),
+        )
+        .filter(asset_v1_account_attachments::Column::Data.is_not_null())
+        .order_by_asc(asset_v1_account_attachments::Column::Id)
+        .all(conn)
+        .await
+}
+
+/// The first change after `seq` that removed the leaf from its tree, either by burning or by
+/// redeeming it.
+pub async fn get_leaf_removal(
+    conn: &impl ConnectionTrait,
+    tree: Vec<u8>,
+    leaf_idx: i64,
+    seq: i64,
+) -> Result<Option<cl_audits_v2::Model>, DbErr> {
+    cl_audits_v2::Entity::find()
+        .filter(cl_audits_v2::Column::Tree.eq(tree))
+        .filter(cl_audits_v2::Column::LeafIdx.eq(leaf_idx))
+        .filter(cl_audits_v2::Column::Seq.gt(seq))
+        .filter(
+            Condition::any()
+                .add(cl_audits_v2::Column::Instruction.eq(Instruction::Burn))
+                .add(cl_audits_v2::Column::Instruction.eq(Instruction::BurnV2))
+                .add(cl_audits_v2::Column::Instruction.eq(Instruction::Redeem)),
+        )
+        .order_by_asc(cl_audits_v2::Column::Seq)
+        .one(conn)
+        .await
+}
+
 fn filter_out_stale_creators(creators: &mut Vec<asset_creators::Model>) {
     // If the first creator is an empty Vec, it means the creator array is empty (which is allowed
     // for compressed assets in Bubblegum).
@@ -671,6 +1194,7 @@ pub async fn get_token_accounts(
         token_accounts::Entity::find().filter(condition),
         Order::Asc,
         token_accounts::Column::Pubkey,
+        None,
     )
     .all(conn)
     .await?;
@@ -702,6 +1226,52 @@ fn attachment_to_nft_edition(
     })
 }
 
+/// Returns the edition numbers flagged as minted in the edition markers of `mint_address`, for
+/// edition numbers up to `highest_edition`.
+async fn get_minted_edition_numbers(
+    conn: &impl ConnectionTrait,
+    mint_address: &Pubkey,
+    highest_edition: u64,
+) -> Result<Vec<u64>, DbErr> {
+    let markers: HashMap<Vec<u8>, u64> = (0..=highest_edition / EDITION_MARKER_BIT_SIZE)
+        .take(MAX_EDITION_MARKERS as usize)
+        .map(|marker| {
+            let (address, _) = EditionMarker::find_pda(mint_address, &marker.to_string());
+            (address.to_bytes().to_vec(), marker)
+        })
+        .collect();
+
+    let attachments = asset_v1_account_attachments::Entity::find()
+        .filter(asset_v1_account_attachments::Column::Id.is_in(markers.keys().cloned()))
+        .filter(
+            asset_v1_account_attachments::Column::AttachmentType
+                .eq(V1AccountAttachments::EditionMarker),
+        )
+        .all(conn)
+        .await?;
+
+    let mut minted = Vec::new();
+    for attachment in attachments {
+        let (marker, data) = match (markers.get(&attachment.id), attachment.data) {
+            (Some(marker), Some(data)) => (*marker, data),
+            _ => continue,
+        };
+        let ledger = get_edition_data_from_json::<EditionMarker>(data)?.ledger;
+        // Bit `i` of the ledger, counting from the most significant bit of the first byte,
+        // flags edition `marker * 248 + i`. Edition 0 is never minted.
+        for (index, byte) in ledger.iter().enumerate() {
+            for bit in 0..8 {
+                let edition = marker * EDITION_MARKER_BIT_SIZE + index as u64 * 8 + bit;
+                if byte & (1 << (7 - bit)) != 0 && edition > 0 {
+                    minted.push(edition);
+                }
+            }
+        }
+    }
+    minted.sort_unstable();
+    Ok(minted)
+}
+
 pub async fn get_nft_editions(
     conn: &impl ConnectionTrait,
     mint_address: Pubkey,
@@ -747,6 +1317,7 @@ pub async fn get_nft_editions(
         stmt,
         Order::Asc,
         asset_v1_account_attachments::Column::Id,
+        None,
     )
     .all(conn)
     .await?
@@ -771,12 +1342,22 @@ pub async fn get_nft_editions(
         }
     };
 
+    let minted_editions = get_minted_edition_numbers(
+        conn,
+        &mint_address,
+        master_edition_data
+            .max_supply
+            .unwrap_or(master_edition_data.supply),
+    )
+    .await?;
+
     Ok(NftEditions {
         total: nft_editions.len() as u32,
         master_edition_address: master_edition_pubkey.to_string(),
         supply: master_edition_data.supply,
         max_supply: master_edition_data.max_supply,
         editions: nft_editions,
+        minted_editions,
         limit: limit as u32,
         page,
         before,
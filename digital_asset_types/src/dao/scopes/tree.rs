@@ -0,0 +1,39 @@
+use crate::dao::{merkle_tree, Pagination};
+use sea_orm::{entity::*, query::*, ConnectionTrait, DbErr, Order};
+
+use super::asset::paginate;
+
+pub async fn get_tree(
+    conn: &impl ConnectionTrait,
+    tree: Vec<u8>,
+) -> Result<merkle_tree::Model, DbErr> {
+    merkle_tree::Entity::find_by_id(tree)
+        .one(conn)
+        .await?
+        .ok_or(DbErr::RecordNotFound("Tree Not Found".to_string()))
+}
+
+/// Trees the address can mint to, either as the tree creator or as its delegate.
+pub async fn get_trees_by_authority(
+    conn: &impl ConnectionTrait,
+    authority: Vec<u8>,
+    pagination: &Pagination,
+    limit: u64,
+) -> Result<Vec<merkle_tree::Model>, DbErr> {
+    let condition = Condition::any()
+        .add(merkle_tree::Column::TreeCreator.eq(authority.clone()))
+        .add(merkle_tree::Column::TreeDelegate.eq(authority));
+
+    paginate(
+        pagination,
+        limit,
+        merkle_tree::Entity::find()
+            .filter(condition)
+            .order_by(merkle_tree::Column::Id, Order::Asc),
+        Order::Asc,
+        merkle_tree::Column::Id,
+        None,
+    )
+    .all(conn)
+    .await
+}
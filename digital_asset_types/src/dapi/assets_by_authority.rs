@@ -17,6 +17,11 @@ pub async fn get_assets_by_authority(
 ) -> Result<AssetList, DbErr> {
     let pagination = create_pagination(page_options)?;
     let (sort_direction, sort_column) = create_sorting(sorting);
+    let grand_total = if options.show_grand_total {
+        Some(scopes::asset::count_by_authority(db, authority.clone()).await?)
+    } else {
+        None
+    };
     let assets = scopes::asset::get_by_authority(
         db,
         authority,
@@ -30,7 +35,9 @@ pub async fn get_assets_by_authority(
     Ok(build_asset_response(
         assets,
         page_options.limit,
+        sort_column,
         &pagination,
+        grand_total,
         options,
     ))
 }
@@ -19,6 +19,11 @@ pub async fn get_assets_by_creator(
 ) -> Result<AssetList, DbErr> {
     let pagination = create_pagination(page_options)?;
     let (sort_direction, sort_column) = create_sorting(sorting);
+    let grand_total = if options.show_grand_total {
+        Some(scopes::asset::count_by_creator(db, creator.clone(), only_verified).await?)
+    } else {
+        None
+    };
     let assets = scopes::asset::get_by_creator(
         db,
         creator,
@@ -33,7 +38,9 @@ pub async fn get_assets_by_creator(
     Ok(build_asset_response(
         assets,
         page_options.limit,
+        sort_column,
         &pagination,
+        grand_total,
         options,
     ))
 }
@@ -19,6 +19,14 @@ pub async fn get_assets_by_group(
 ) -> Result<AssetList, DbErr> {
     let pagination = create_pagination(page_options)?;
     let (sort_direction, sort_column) = create_sorting(sorting);
+    let grand_total = if options.show_grand_total {
+        Some(
+            scopes::asset::count_by_grouping(db, group_key.clone(), group_value.clone(), options)
+                .await?,
+        )
+    } else {
+        None
+    };
     let assets = scopes::asset::get_by_grouping(
         db,
         group_key,
@@ -33,7 +41,9 @@ pub async fn get_assets_by_group(
     Ok(build_asset_response(
         assets,
         page_options.limit,
+        sort_column,
         &pagination,
+        grand_total,
         options,
     ))
 }
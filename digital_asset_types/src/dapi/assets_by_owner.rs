@@ -1,5 +1,6 @@
 use crate::dao::scopes;
 use crate::dao::PageOptions;
+use crate::dao::TokenExtensionFilters;
 use crate::rpc::filter::AssetSorting;
 use crate::rpc::options::Options;
 use crate::rpc::response::AssetList;
@@ -11,15 +12,25 @@ use super::common::{build_asset_response, create_pagination, create_sorting};
 pub async fn get_assets_by_owner(
     db: &DatabaseConnection,
     owner_address: Vec<u8>,
+    token_extensions: TokenExtensionFilters,
     sort_by: AssetSorting,
     page_options: &PageOptions,
     options: &Options,
 ) -> Result<AssetList, DbErr> {
     let pagination = create_pagination(page_options)?;
     let (sort_direction, sort_column) = create_sorting(sort_by);
+    let grand_total = if options.show_grand_total {
+        Some(
+            scopes::asset::count_assets_by_owner(db, owner_address.clone(), &token_extensions)
+                .await?,
+        )
+    } else {
+        None
+    };
     let assets = scopes::asset::get_assets_by_owner(
         db,
         owner_address,
+        &token_extensions,
         sort_column,
         sort_direction,
         &pagination,
@@ -30,7 +41,9 @@ pub async fn get_assets_by_owner(
     Ok(build_asset_response(
         assets,
         page_options.limit,
+        sort_column,
         &pagination,
+        grand_total,
         options,
     ))
 }
@@ -1,14 +1,30 @@
+use log::error;
 use sea_orm::sea_query::Expr;
-use sea_orm::{DatabaseConnection, DbBackend};
-use std::collections::HashMap;
+use sea_orm::{DatabaseConnection, DbBackend, Statement};
+use std::collections::{HashMap, HashSet};
 use {
     crate::dao::asset,
     crate::dao::cl_items,
+    crate::dao::cl_items_history,
+    crate::dao::merkle_tree,
     crate::rpc::AssetProof,
     sea_orm::{entity::*, query::*, DbErr, FromQueryResult},
-    spl_concurrent_merkle_tree::node::empty_node,
+    spl_concurrent_merkle_tree::{
+        hash::recompute,
+        node::{empty_node, Node},
+    },
 };
 
+/// Top proof nodes to leave out because the tree canopy already stores them on-chain.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum CanopyTruncation {
+    #[default]
+    None,
+    /// Use the canopy depth indexed for the tree, returns the full proof if it is unknown.
+    Indexed,
+    Depth(u32),
+}
+
 #[derive(FromQueryResult, Debug, Default, Clone, Eq, PartialEq)]
 struct SimpleChangeLog {
     hash: Vec<u8>,
@@ -36,27 +52,9 @@ struct Leaf {
 pub async fn get_proof_for_asset(
     db: &DatabaseConnection,
     asset_id: Vec<u8>,
+    canopy_truncation: CanopyTruncation,
 ) -> Result<AssetProof, DbErr> {
-    let sel = cl_items::Entity::find()
-        .join_rev(
-            JoinType::InnerJoin,
-            asset::Entity::belongs_to(cl_items::Entity)
-                .from(asset::Column::Nonce)
-                .to(cl_items::Column::LeafIdx)
-                .into(),
-        )
-        .order_by_desc(cl_items::Column::Seq)
-        .filter(Expr::cust("asset.tree_id = cl_items.tree"))
-        .filter(Expr::cust_with_values(
-            "asset.id = $1::bytea",
-            vec![asset_id],
-        ))
-        .filter(cl_items::Column::Level.eq(0i64));
-    let leaf: Option<cl_items::Model> = sel.one(db).await?;
-    if leaf.is_none() {
-        return Err(DbErr::RecordNotFound("Asset Proof Not Found".to_string()));
-    }
-    let leaf = leaf.unwrap();
+    let leaf = get_leaf(db, asset_id).await?;
     let req_indexes = get_required_nodes_for_proof(leaf.node_idx);
     let mut query = cl_items::Entity::find()
         .select_only()
@@ -79,19 +77,132 @@ pub async fn get_proof_for_asset(
             .map(|q| SimpleChangeLog::from_query_result(q, "").unwrap())
             .collect()
     })?;
-    let asset_proof = build_asset_proof(
+    let canopy_depths = get_canopy_depths(db, vec![leaf.tree.clone()], canopy_truncation).await?;
+    let canopy_depth = canopy_depths.get(&leaf.tree).copied();
+    let mut asset_proof = build_asset_proof(
         leaf.tree,
         leaf.node_idx,
         leaf.hash,
         &req_indexes,
         &required_nodes,
     );
+    if !asset_proof.verified {
+        enqueue_tree_backfill(db, &asset_proof.tree_id).await;
+    }
+    truncate_canopy(&mut asset_proof, canopy_depth);
+    Ok(asset_proof)
+}
+
+/// Rebuilds the proof for `asset_id` from the latest node versions at or below `seq`, failing when
+/// the changelog for that sequence is no longer available.
+pub async fn get_proof_for_asset_at_seq(
+    db: &DatabaseConnection,
+    asset_id: Vec<u8>,
+    seq: i64,
+    canopy_truncation: CanopyTruncation,
+) -> Result<AssetProof, DbErr> {
+    let leaf = get_leaf(db, asset_id).await?;
+    let tree_id = bs58::encode(&leaf.tree).into_string();
+    let req_indexes = get_required_nodes_for_proof(leaf.node_idx);
+    let mut indexes = req_indexes.clone();
+    indexes.push(leaf.node_idx);
+    let mut nodes = get_nodes_at_seq(db, &leaf.tree, indexes, seq).await?;
+
+    // Every changelog event writes the root, so a missing root version means the sequence was
+    // never indexed or its changelog has been pruned.
+    if nodes.get(&1).map(|root| root.seq) != Some(seq) {
+        return Err(DbErr::RecordNotFound(format!(
+            "Changelog for tree {} at seq {} is not available",
+            tree_id, seq
+        )));
+    }
+    let leaf_node = nodes.remove(&leaf.node_idx).ok_or_else(|| {
+        DbErr::RecordNotFound(format!("Asset leaf not found in tree at seq {}", seq))
+    })?;
+    let required_nodes: Vec<SimpleChangeLog> = nodes.into_values().collect();
+
+    let canopy_depths = get_canopy_depths(db, vec![leaf.tree.clone()], canopy_truncation).await?;
+    let canopy_depth = canopy_depths.get(&leaf.tree).copied();
+    let mut asset_proof = build_asset_proof(
+        leaf.tree,
+        leaf.node_idx,
+        leaf_node.hash,
+        &req_indexes,
+        &required_nodes,
+    );
+    if !asset_proof.verified {
+        return Err(DbErr::RecordNotFound(format!(
+            "Changelog for tree {} has been pruned past seq {}",
+            tree_id, seq
+        )));
+    }
+    truncate_canopy(&mut asset_proof, canopy_depth);
     Ok(asset_proof)
 }
 
+/// Latest version of each node at or below `seq`, keyed by node index. Nodes last written before
+/// history was kept are still read from `cl_items`.
+async fn get_nodes_at_seq(
+    db: &DatabaseConnection,
+    tree: &[u8],
+    indexes: Vec<i64>,
+    seq: i64,
+) -> Result<HashMap<i64, SimpleChangeLog>, DbErr> {
+    let mut query = cl_items_history::Entity::find()
+        .select_only()
+        .column(cl_items_history::Column::NodeIdx)
+        .column(cl_items_history::Column::Hash)
+        .column(cl_items_history::Column::Level)
+        .column(cl_items_history::Column::Seq)
+        .column(cl_items_history::Column::Tree)
+        .filter(cl_items_history::Column::Tree.eq(tree.to_vec()))
+        .filter(cl_items_history::Column::NodeIdx.is_in(indexes.clone()))
+        .filter(cl_items_history::Column::Seq.lte(seq))
+        .order_by_desc(cl_items_history::Column::NodeIdx)
+        .order_by_desc(cl_items_history::Column::Seq)
+        .build(DbBackend::Postgres);
+    query.sql = query
+        .sql
+        .replace("SELECT", "SELECT DISTINCT ON (cl_items_history.node_idx)");
+    let history: Vec<SimpleChangeLog> = db.query_all(query).await.map(|qr| {
+        qr.iter()
+            .map(|q| SimpleChangeLog::from_query_result(q, "").unwrap())
+            .collect()
+    })?;
+
+    let query = cl_items::Entity::find()
+        .select_only()
+        .column(cl_items::Column::NodeIdx)
+        .column(cl_items::Column::Hash)
+        .column(cl_items::Column::Level)
+        .column(cl_items::Column::Seq)
+        .column(cl_items::Column::Tree)
+        .filter(cl_items::Column::Tree.eq(tree.to_vec()))
+        .filter(cl_items::Column::NodeIdx.is_in(indexes))
+        .filter(cl_items::Column::Seq.lte(seq))
+        .build(DbBackend::Postgres);
+    let current: Vec<SimpleChangeLog> = db.query_all(query).await.map(|qr| {
+        qr.iter()
+            .map(|q| SimpleChangeLog::from_query_result(q, "").unwrap())
+            .collect()
+    })?;
+
+    let mut nodes: HashMap<i64, SimpleChangeLog> = HashMap::new();
+    for node in history.into_iter().chain(current) {
+        match nodes.get(&node.node_idx) {
+            Some(existing) if existing.seq >= node.seq => {}
+            _ => {
+                nodes.insert(node.node_idx, node);
+            }
+        }
+    }
+    Ok(nodes)
+}
+
 pub async fn get_asset_proofs(
     db: &DatabaseConnection,
     asset_ids: Vec<Vec<u8>>,
+    canopy_truncation: CanopyTruncation,
 ) -> Result<HashMap<String, AssetProof>, DbErr> {
     // get the leaves (JOIN with `asset` table to get the asset ids)
     let q = asset::Entity::find()
@@ -169,8 +280,15 @@ pub async fn get_asset_proofs(
         node_map.insert(key, node);
     }
 
+    let trees = tree_indexes
+        .keys()
+        .map(|leaf| leaf.tree_id.clone())
+        .collect::<Vec<_>>();
+    let canopy_depths = get_canopy_depths(db, trees, canopy_truncation).await?;
+
     // construct the proofs
     let mut asset_proofs: HashMap<String, AssetProof> = HashMap::new();
+    let mut stale_trees: HashSet<String> = HashSet::new();
     for (leaf, req_indexes) in &tree_indexes {
         let required_nodes: Vec<SimpleChangeLog> = req_indexes
             .iter()
@@ -181,21 +299,112 @@ pub async fn get_asset_proofs(
             .collect();
 
         let leaf_info = asset_map.get(leaf).unwrap();
-        let asset_proof = build_asset_proof(
+        let mut asset_proof = build_asset_proof(
             leaf_info.tree_id.clone(),
             leaf_info.node_idx,
             leaf_info.hash.clone(),
             req_indexes,
             &required_nodes,
         );
+        if !asset_proof.verified {
+            stale_trees.insert(asset_proof.tree_id.clone());
+        }
+        truncate_canopy(&mut asset_proof, canopy_depths.get(&leaf.tree_id).copied());
 
         let asset_id = bs58::encode(leaf_info.id.to_owned()).into_string();
         asset_proofs.insert(asset_id, asset_proof);
     }
 
+    for tree_id in &stale_trees {
+        enqueue_tree_backfill(db, tree_id).await;
+    }
+
     Ok(asset_proofs)
 }
 
+/// Queues a forced backfill of `tree_id` so the missing sequences behind a proof that does not
+/// hash to its root get re-fetched. This is best-effort, the proof is still served when the queue
+/// cannot be written, e.g. on a read replica.
+async fn enqueue_tree_backfill(db: &DatabaseConnection, tree_id: &str) {
+    if let Err(e) = insert_tree_backfill(db, tree_id).await {
+        error!("Failed to queue backfill for stale tree {}: {}", tree_id, e);
+    }
+}
+
+async fn insert_tree_backfill(db: &DatabaseConnection, tree_id: &str) -> Result<(), DbErr> {
+    let tree = bs58::decode(tree_id)
+        .into_vec()
+        .map_err(|e| DbErr::Custom(e.to_string()))?;
+    // A forced check backfills from sequence 1, so the slot is only informational. Trees already
+    // waiting on a forced check hit `backfill_items_pending_force_chk_idx` and are not queued twice.
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "INSERT INTO backfill_items (tree, seq, slot, force_chk, backfilled, failed, locked) \
+         VALUES ($1, 0, 0, true, false, false, false) \
+         ON CONFLICT DO NOTHING",
+        [tree.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+async fn get_leaf(db: &DatabaseConnection, asset_id: Vec<u8>) -> Result<cl_items::Model, DbErr> {
+    let sel = cl_items::Entity::find()
+        .join_rev(
+            JoinType::InnerJoin,
+            asset::Entity::belongs_to(cl_items::Entity)
+                .from(asset::Column::Nonce)
+                .to(cl_items::Column::LeafIdx)
+                .into(),
+        )
+        .order_by_desc(cl_items::Column::Seq)
+        .filter(Expr::cust("asset.tree_id = cl_items.tree"))
+        .filter(Expr::cust_with_values(
+            "asset.id = $1::bytea",
+            vec![asset_id],
+        ))
+        .filter(cl_items::Column::Level.eq(0i64));
+    let leaf: Option<cl_items::Model> = sel.one(db).await?;
+    if leaf.is_none() {
+        return Err(DbErr::RecordNotFound("Asset Proof Not Found".to_string()));
+    }
+    Ok(leaf.unwrap())
+}
+
+/// Canopy depth to truncate proofs by, keyed by tree.
+async fn get_canopy_depths(
+    db: &DatabaseConnection,
+    trees: Vec<Vec<u8>>,
+    canopy_truncation: CanopyTruncation,
+) -> Result<HashMap<Vec<u8>, u32>, DbErr> {
+    match canopy_truncation {
+        CanopyTruncation::None => Ok(HashMap::new()),
+        CanopyTruncation::Depth(depth) => Ok(trees.into_iter().map(|tree| (tree, depth)).collect()),
+        CanopyTruncation::Indexed => {
+            let trees = merkle_tree::Entity::find()
+                .filter(merkle_tree::Column::Id.is_in(trees))
+                .filter(merkle_tree::Column::CanopyDepth.is_not_null())
+                .all(db)
+                .await?;
+            Ok(trees
+                .into_iter()
+                .filter_map(|tree| {
+                    tree.canopy_depth
+                        .map(|depth| (tree.id, depth.max(0) as u32))
+                })
+                .collect())
+        }
+    }
+}
+
+// The proof is ordered from the leaf up, so the nodes held by the canopy are at the end.
+fn truncate_canopy(asset_proof: &mut AssetProof, canopy_depth: Option<u32>) {
+    if let Some(depth) = canopy_depth {
+        let len = asset_proof.proof.len().saturating_sub(depth as usize);
+        asset_proof.proof.truncate(len);
+    }
+}
+
 fn build_asset_proof(
     tree_id: Vec<u8>,
     leaf_node_idx: i64,
@@ -214,8 +423,10 @@ fn build_asset_proof(
             *n = make_empty_node(i as i64, *nin, tree_id.clone());
         }
     }
+    let root = final_node_list.pop().unwrap().hash;
+    let verified = verify_proof(leaf_node_idx, &leaf_hash, &final_node_list, &root);
     AssetProof {
-        root: bs58::encode(final_node_list.pop().unwrap().hash).into_string(),
+        root: bs58::encode(root).into_string(),
         leaf: bs58::encode(leaf_hash).into_string(),
         proof: final_node_list
             .iter()
@@ -223,6 +434,26 @@ fn build_asset_proof(
             .collect(),
         node_index: leaf_node_idx,
         tree_id: bs58::encode(tree_id).into_string(),
+        verified,
+    }
+}
+
+// Hashes the leaf up through the proof nodes and checks it lands on the root, a missing
+// sequence in `cl_items` shows up here as a stale sibling.
+fn verify_proof(
+    leaf_node_idx: i64,
+    leaf_hash: &[u8],
+    proof: &[SimpleChangeLog],
+    root: &[u8],
+) -> bool {
+    let to_node = |hash: &[u8]| Node::try_from(hash).ok();
+    let leaf_index = u32::try_from(leaf_node_idx - (1i64 << proof.len())).ok();
+    let proof_nodes: Option<Vec<Node>> = proof.iter().map(|node| to_node(&node.hash)).collect();
+    match (to_node(leaf_hash), proof_nodes, leaf_index) {
+        (Some(leaf), Some(proof_nodes), Some(leaf_index)) => {
+            recompute(leaf, &proof_nodes, leaf_index).as_slice() == root
+        }
+        _ => false,
     }
 }
 
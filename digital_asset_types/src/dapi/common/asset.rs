@@ -1,17 +1,33 @@
+use crate::dao::extensions::instruction::PascalCase;
+use crate::dao::merkle_tree;
+use crate::dao::sea_orm_active_enums::{
+    AssetDataSchema, Instruction, TokenState, V1AccountAttachments,
+};
 use crate::dao::token_accounts;
+use crate::dao::Cursor;
 use crate::dao::FullAsset;
+use crate::dao::GrandTotal;
 use crate::dao::PageOptions;
 use crate::dao::Pagination;
+use crate::dao::SignatureCursor;
 use crate::dao::{asset, asset_authority, asset_creators, asset_data, asset_grouping};
+use crate::dao::{asset_leaf_history, asset_v1_account_attachments, cl_audits_v2};
 use crate::rpc::filter::{AssetSortBy, AssetSortDirection, AssetSorting};
 use crate::rpc::options::Options;
 use crate::rpc::response::TokenAccountList;
-use crate::rpc::response::TransactionSignatureList;
-use crate::rpc::response::{AssetList, DasError};
+use crate::rpc::response::{
+    AssetDelegatedAuthorities, DelegatedCollectionAuthority, DelegatedMetadataAuthority,
+    DelegatedUseAuthority,
+};
+use crate::rpc::response::{AssetList, AssetOwnershipHistory, DasError, OwnershipSpan};
+use crate::rpc::response::{
+    TransactionSignature, TransactionSignatureItem, TransactionSignatureList,
+};
+use crate::rpc::response::{TreeInfo, TreeInfoList};
 use crate::rpc::TokenInfo;
 use crate::rpc::TokenInscriptionInfo;
 use crate::rpc::{
-    Asset as RpcAsset, Authority, Compression, Content, Creator, File, Group, Interface,
+    Asset as RpcAsset, AssetData, Authority, Compression, Content, Creator, File, Group, Interface,
     MetadataMap, MplCoreInfo, Ownership, Royalty, Scope, Supply, TokenAccount as RpcTokenAccount,
     Uses,
 };
@@ -19,6 +35,10 @@ use blockbuster::programs::token_inscriptions::InscriptionData;
 use jsonpath_lib::JsonPathError;
 use log::warn;
 use mime_guess::Mime;
+use mpl_token_metadata::accounts::{
+    CollectionAuthorityRecord, MetadataDelegateRecord, UseAuthorityRecord,
+};
+use mpl_token_metadata::types::MetadataDelegateRole;
 
 use sea_orm::DbErr;
 use serde_json::Value;
@@ -55,7 +75,9 @@ pub fn file_from_str(str: String) -> File {
 pub fn build_asset_response(
     assets: Vec<FullAsset>,
     limit: u64,
+    sort_column: Option<asset::Column>,
     pagination: &Pagination,
+    grand_total: Option<GrandTotal>,
     options: &Options,
 ) -> AssetList {
     let total = assets.len() as u32;
@@ -67,12 +89,10 @@ pub fn build_asset_response(
         }
         Pagination::Page { page } => (Some(*page), None, None, None),
         Pagination::Cursor(_) => {
-            if let Some(last_asset) = assets.last() {
-                let cursor_str = bs58::encode(&last_asset.asset.id.clone()).into_string();
-                (None, None, None, Some(cursor_str))
-            } else {
-                (None, None, None, None)
-            }
+            let cursor = assets
+                .last()
+                .and_then(|last_asset| Cursor::from_asset(&last_asset.asset, sort_column).encode());
+            (None, None, None, cursor)
         }
     };
 
@@ -86,13 +106,17 @@ pub fn build_asset_response(
         items,
         errors,
         cursor,
+        grand_total: grand_total.as_ref().map(|g| g.total),
+        grand_total_estimated: grand_total.map(|g| g.estimated),
     }
 }
 
 pub fn build_transaction_signatures_response(
-    items: Vec<(String, String)>,
+    items: Vec<TransactionSignature>,
     limit: u64,
     pagination: &Pagination,
+    cursor: Option<SignatureCursor>,
+    structured: bool,
 ) -> TransactionSignatureList {
     let total = items.len() as u32;
     let (page, before, after) = match pagination {
@@ -104,6 +128,13 @@ pub fn build_transaction_signatures_response(
         Pagination::Page { page } => (Some(*page), None, None),
         Pagination::Cursor { .. } => (None, None, None),
     };
+    let items = items
+        .into_iter()
+        .map(|item| match structured {
+            true => TransactionSignatureItem::Structured(item),
+            false => TransactionSignatureItem::Pair(item.signature, item.instruction),
+        })
+        .collect();
     TransactionSignatureList {
         total,
         limit: limit as u32,
@@ -111,6 +142,7 @@ pub fn build_transaction_signatures_response(
         before,
         after,
         items,
+        cursor: cursor.map(|cursor| cursor.encode()),
     }
 }
 
@@ -120,7 +152,7 @@ pub fn create_sorting(sorting: AssetSorting) -> (sea_orm::query::Order, Option<a
         AssetSortBy::Created => Some(asset::Column::CreatedAt),
         AssetSortBy::Updated => Some(asset::Column::SlotUpdated),
         AssetSortBy::RecentAction => Some(asset::Column::SlotUpdated),
-        AssetSortBy::None => None,
+        AssetSortBy::None | AssetSortBy::NameRelevance => None,
     };
     let sort_direction = match sorting.sort_direction.unwrap_or_default() {
         AssetSortDirection::Desc => sea_orm::query::Order::Desc,
@@ -203,6 +235,19 @@ pub fn v1_content_from_json(asset_data: &asset_data::Model) -> Result<Content, D
     if let Some(token_standard) = token_standard {
         meta.set_item("token_standard", token_standard.clone());
     }
+    // Token-2022 metadata stores extra fields as key/value pairs.
+    if let Some(Value::Array(pairs)) = asset_data.chain_data.get("additional_metadata") {
+        let additional_metadata: serde_json::Map<String, Value> = pairs
+            .iter()
+            .filter_map(|pair| match pair.as_array().map(Vec::as_slice) {
+                Some([Value::String(key), value]) => Some((key.clone(), value.clone())),
+                _ => None,
+            })
+            .collect();
+        if !additional_metadata.is_empty() {
+            meta.set_item("additional_metadata", Value::Object(additional_metadata));
+        }
+    }
     let mut links = HashMap::new();
     let link_fields = vec!["image", "animation_url", "external_url"];
     for f in link_fields {
@@ -366,6 +411,25 @@ pub fn get_interface(asset: &asset::Model) -> Result<Interface, DbErr> {
     )))
 }
 
+pub fn to_asset_data(data: &asset_data::Model) -> Option<AssetData> {
+    let (bytes, schema) = match (
+        &data.bubblegum_asset_data,
+        &data.bubblegum_asset_data_schema,
+    ) {
+        (Some(bytes), Some(schema)) => (bytes, schema),
+        _ => return None,
+    };
+    let json = match schema {
+        AssetDataSchema::Json => serde_json::from_slice(bytes).ok(),
+        _ => None,
+    };
+    Some(AssetData {
+        schema: schema.clone().into(),
+        data: bs58::encode(bytes).into_string(),
+        json,
+    })
+}
+
 //TODO -> impl custom error type
 pub fn asset_to_rpc(asset: FullAsset, options: &Options) -> Result<RpcAsset, DbErr> {
     let FullAsset {
@@ -486,6 +550,7 @@ pub fn asset_to_rpc(asset: FullAsset, options: &Options) -> Result<RpcAsset, DbE
                 .asset_data_hash
                 .map(|e| if asset.compressed { e.trim() } else { "" }.to_string()),
             flags: asset.bubblegum_flags.and_then(|val| val.try_into().ok()),
+            asset_data: to_asset_data(&data),
         }),
         grouping: Some(rpc_groups),
         royalty: Some(Royalty {
@@ -498,10 +563,17 @@ pub fn asset_to_rpc(asset: FullAsset, options: &Options) -> Result<RpcAsset, DbE
         }),
         creators: Some(rpc_creators),
         ownership: Some(Ownership {
-            frozen: asset.frozen,
+            // Programmable NFT token accounts are always frozen, the token record tells whether
+            // the asset is actually locked.
+            frozen: asset
+                .token_state
+                .as_ref()
+                .map_or(asset.frozen, |state| *state != TokenState::Unlocked),
             non_transferable: asset.non_transferable,
             delegated: asset.delegate.is_some(),
             delegate: asset.delegate.map(|s| bs58::encode(s).into_string()),
+            token_state: asset.token_state.map(Into::into),
+            delegate_role: asset.token_delegate_role.map(Into::into),
             ownership_model: asset.owner_type.into(),
             owner: asset
                 .owner
@@ -635,3 +707,186 @@ pub fn build_token_list_response(
         errors,
     }
 }
+
+pub fn tree_to_rpc(tree: merkle_tree::Model) -> TreeInfo {
+    TreeInfo {
+        tree: bs58::encode(tree.id).into_string(),
+        max_depth: tree.max_depth.map(|d| d as u32),
+        max_buffer_size: tree.max_buffer_size.map(|s| s as u32),
+        canopy_depth: tree.canopy_depth.map(|d| d as u32),
+        capacity: tree.max_depth.map(|d| 1u64 << d),
+        creation_slot: tree.creation_slot.map(|s| s as u64),
+        tree_creator: tree.tree_creator.map(|c| bs58::encode(c).into_string()),
+        tree_delegate: tree.tree_delegate.map(|d| bs58::encode(d).into_string()),
+        is_public: tree.is_public,
+        version: tree.version.map(Into::into),
+        num_minted: tree.num_minted as u64,
+        slot_updated: tree.slot_updated as u64,
+    }
+}
+
+pub fn build_tree_list_response(
+    trees: Vec<merkle_tree::Model>,
+    limit: u64,
+    pagination: &Pagination,
+) -> TreeInfoList {
+    let total = trees.len() as u32;
+    let (page, before, after, cursor) = match pagination {
+        Pagination::Keyset { before, after } => {
+            let bef = before.clone().and_then(|x| String::from_utf8(x).ok());
+            let aft = after.clone().and_then(|x| String::from_utf8(x).ok());
+            (None, bef, aft, None)
+        }
+        Pagination::Page { page } => (Some(*page as u32), None, None, None),
+        Pagination::Cursor(_) => {
+            if let Some(last_tree) = trees.last() {
+                let cursor_str = bs58::encode(&last_tree.id.clone()).into_string();
+                (None, None, None, Some(cursor_str))
+            } else {
+                (None, None, None, None)
+            }
+        }
+    };
+
+    TreeInfoList {
+        total,
+        limit: limit as u32,
+        page,
+        before,
+        after,
+        trees: trees.into_iter().map(tree_to_rpc).collect(),
+        cursor,
+    }
+}
+
+/// Collapses consecutive leaf states with the same owner and delegate into a single span. Each
+/// span ends where the next one starts, and the last one ends at `removal` when the leaf has since
+/// been burnt or redeemed.
+pub fn build_ownership_history_response(
+    id: Vec<u8>,
+    history: Vec<asset_leaf_history::Model>,
+    removal: Option<cl_audits_v2::Model>,
+) -> AssetOwnershipHistory {
+    let mut items: Vec<OwnershipSpan> = Vec::new();
+    for leaf in history {
+        let owner = bs58::encode(leaf.owner).into_string();
+        let delegate = leaf.delegate.map(|d| bs58::encode(d).into_string());
+        if let Some(current) = items.last_mut() {
+            if current.owner == owner && current.delegate == delegate {
+                continue;
+            }
+            current.to_seq = Some(leaf.seq as u64);
+            current.to_slot = Some(leaf.slot as u64);
+        }
+        items.push(OwnershipSpan {
+            owner,
+            delegate,
+            from_seq: leaf.seq as u64,
+            from_slot: leaf.slot as u64,
+            to_seq: None,
+            to_slot: None,
+            instruction: Instruction::to_pascal_case(&leaf.instruction),
+        });
+    }
+
+    if let (Some(current), Some(removal)) = (items.last_mut(), removal) {
+        current.to_seq = Some(removal.seq as u64);
+        current.to_slot = removal.slot.map(|slot| slot as u64);
+    }
+
+    AssetOwnershipHistory {
+        id: bs58::encode(id).into_string(),
+        items,
+    }
+}
+
+// The role is only part of the record address, so it is recovered by deriving the address for
+// every known role.
+fn metadata_delegate_role(address: &str, record: &MetadataDelegateRecord) -> Option<String> {
+    [
+        MetadataDelegateRole::AuthorityItem,
+        MetadataDelegateRole::Collection,
+        MetadataDelegateRole::Use,
+        MetadataDelegateRole::Data,
+        MetadataDelegateRole::ProgrammableConfig,
+        MetadataDelegateRole::DataItem,
+        MetadataDelegateRole::CollectionItem,
+        MetadataDelegateRole::ProgrammableConfigItem,
+    ]
+    .into_iter()
+    .find(|role| {
+        MetadataDelegateRecord::find_pda(
+            &record.mint,
+            role.clone(),
+            &record.update_authority,
+            &record.delegate,
+        )
+        .0
+        .to_string()
+            == address
+    })
+    .map(|role| format!("{:?}", role))
+}
+
+pub fn build_delegated_authorities_response(
+    id: Vec<u8>,
+    records: Vec<asset_v1_account_attachments::Model>,
+) -> Result<AssetDelegatedAuthorities, DbErr> {
+    let mut response = AssetDelegatedAuthorities {
+        id: bs58::encode(id).into_string(),
+        ..Default::default()
+    };
+    for record in records {
+        let (authority, data) = match (record.authority, record.data) {
+            (Some(authority), Some(data)) => (bs58::encode(authority).into_string(), data),
+            _ => continue,
+        };
+        let address = bs58::encode(record.id).into_string();
+        match record.attachment_type {
+            V1AccountAttachments::UseAuthorityRecord => {
+                let data: UseAuthorityRecord = serde_json::from_value(data).map_err(|e| {
+                    DbErr::Custom(format!("Failed to deserialize use authority record: {}", e))
+                })?;
+                response.use_authorities.push(DelegatedUseAuthority {
+                    address,
+                    authority,
+                    allowed_uses: data.allowed_uses,
+                });
+            }
+            V1AccountAttachments::CollectionAuthorityRecord => {
+                let data: CollectionAuthorityRecord =
+                    serde_json::from_value(data).map_err(|e| {
+                        DbErr::Custom(format!(
+                            "Failed to deserialize collection authority record: {}",
+                            e
+                        ))
+                    })?;
+                response
+                    .collection_authorities
+                    .push(DelegatedCollectionAuthority {
+                        address,
+                        authority,
+                        update_authority: data.update_authority.map(|a| a.to_string()),
+                    });
+            }
+            V1AccountAttachments::MetadataDelegateRecord => {
+                let data: MetadataDelegateRecord = serde_json::from_value(data).map_err(|e| {
+                    DbErr::Custom(format!(
+                        "Failed to deserialize metadata delegate record: {}",
+                        e
+                    ))
+                })?;
+                response
+                    .metadata_delegates
+                    .push(DelegatedMetadataAuthority {
+                        role: metadata_delegate_role(&address, &data),
+                        address,
+                        delegate: authority,
+                        update_authority: data.update_authority.to_string(),
+                    });
+            }
+            _ => {}
+        }
+    }
+    Ok(response)
+}
@@ -23,7 +23,7 @@ pub async fn get_assets(
 ) -> Result<HashMap<String, Asset>, DbErr> {
     let pagination = Pagination::Page { page: 1 };
     let assets = scopes::asset::get_assets(db, ids, &pagination, limit, options).await?;
-    let asset_list = build_asset_response(assets, limit, &pagination, options);
+    let asset_list = build_asset_response(assets, limit, None, &pagination, None, options);
     let asset_map = asset_list
         .items
         .into_iter()
@@ -0,0 +1,13 @@
+use sea_orm::{DatabaseConnection, DbErr};
+
+use crate::{dao::scopes, rpc::response::AssetDelegatedAuthorities};
+
+use super::common::build_delegated_authorities_response;
+
+pub async fn get_asset_delegated_authorities(
+    db: &DatabaseConnection,
+    id: Vec<u8>,
+) -> Result<AssetDelegatedAuthorities, DbErr> {
+    let records = scopes::asset::get_authority_records(db, id.clone()).await?;
+    build_delegated_authorities_response(id, records)
+}
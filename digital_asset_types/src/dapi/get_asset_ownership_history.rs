@@ -0,0 +1,23 @@
+use sea_orm::{DatabaseConnection, DbErr};
+
+use crate::{dao::scopes, rpc::response::AssetOwnershipHistory};
+
+use super::common::build_ownership_history_response;
+
+pub async fn get_asset_ownership_history(
+    db: &DatabaseConnection,
+    id: Vec<u8>,
+) -> Result<AssetOwnershipHistory, DbErr> {
+    let history = scopes::asset::get_asset_leaf_history(db, id.clone()).await?;
+    let removal = match history.last() {
+        Some(leaf) => {
+            scopes::asset::get_leaf_removal(db, leaf.tree.clone(), leaf.leaf_idx, leaf.seq).await?
+        }
+        None => {
+            return Err(DbErr::RecordNotFound(
+                "Asset Ownership History Not Found".to_string(),
+            ))
+        }
+    };
+    Ok(build_ownership_history_response(id, history, removal))
+}
@@ -1,5 +1,6 @@
 use crate::dao::scopes;
 use crate::dao::PageOptions;
+use crate::dao::SignatureCursor;
 
 use crate::rpc::filter::AssetSortDirection;
 use crate::rpc::response::TransactionSignatureList;
@@ -8,28 +9,40 @@ use sea_orm::DbErr;
 
 use super::common::{build_transaction_signatures_response, create_pagination};
 
+#[allow(clippy::too_many_arguments)]
 pub async fn get_asset_signatures(
     db: &DatabaseConnection,
     asset_id: Option<Vec<u8>>,
     tree: Option<Vec<u8>>,
     leaf_idx: Option<i64>,
+    instruction: Option<String>,
     page_options: PageOptions,
+    cursor: Option<SignatureCursor>,
     sort_direction: Option<AssetSortDirection>,
+    structured: bool,
 ) -> Result<TransactionSignatureList, DbErr> {
     let pagination = create_pagination(&page_options)?;
-    let transactions = scopes::asset::get_asset_signatures(
+    let (transactions, next_cursor) = scopes::asset::get_asset_signatures(
         db,
         asset_id,
         tree,
         leaf_idx,
+        instruction,
         &pagination,
+        cursor,
         page_options.limit,
         sort_direction,
     )
     .await?;
+    // Cursors are only handed out when the caller is not paging by number or by signature.
+    let next_cursor = next_cursor.filter(|_| {
+        page_options.page.is_none() && page_options.before.is_none() && page_options.after.is_none()
+    });
     Ok(build_transaction_signatures_response(
         transactions,
         page_options.limit,
         &pagination,
+        next_cursor,
+        structured,
     ))
 }
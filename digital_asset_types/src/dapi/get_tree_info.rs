@@ -0,0 +1,29 @@
+use sea_orm::{DatabaseConnection, DbErr};
+
+use crate::{
+    dao::{scopes, PageOptions},
+    rpc::response::{TreeInfo, TreeInfoList},
+};
+
+use super::common::{build_tree_list_response, create_pagination, tree_to_rpc};
+
+pub async fn get_tree_info(db: &DatabaseConnection, tree: Vec<u8>) -> Result<TreeInfo, DbErr> {
+    let tree = scopes::tree::get_tree(db, tree).await?;
+    Ok(tree_to_rpc(tree))
+}
+
+pub async fn get_trees_by_authority(
+    db: &DatabaseConnection,
+    authority: Vec<u8>,
+    page_options: &PageOptions,
+) -> Result<TreeInfoList, DbErr> {
+    let pagination = create_pagination(page_options)?;
+    let trees =
+        scopes::tree::get_trees_by_authority(db, authority, &pagination, page_options.limit)
+            .await?;
+    Ok(build_tree_list_response(
+        trees,
+        page_options.limit,
+        &pagination,
+    ))
+}
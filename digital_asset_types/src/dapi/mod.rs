@@ -4,8 +4,11 @@ mod assets_by_group;
 mod assets_by_owner;
 mod change_logs;
 mod get_asset;
+mod get_asset_delegated_authorities;
+mod get_asset_ownership_history;
 mod get_asset_signatures;
 mod get_token_accounts;
+mod get_tree_info;
 mod search_assets;
 
 pub mod common;
@@ -16,6 +19,9 @@ pub use assets_by_group::*;
 pub use assets_by_owner::*;
 pub use change_logs::*;
 pub use get_asset::*;
+pub use get_asset_delegated_authorities::*;
+pub use get_asset_ownership_history::*;
 pub use get_asset_signatures::*;
 pub use get_token_accounts::*;
+pub use get_tree_info::*;
 pub use search_assets::*;
@@ -1,35 +1,71 @@
 use super::common::{build_asset_response, create_pagination, create_sorting};
 use crate::{
     dao::{scopes, PageOptions, SearchAssetsQuery},
-    rpc::{filter::AssetSorting, options::Options, response::AssetList},
+    rpc::{
+        filter::{AssetSortBy, AssetSorting},
+        options::Options,
+        response::AssetList,
+    },
 };
 use sea_orm::{DatabaseConnection, DbErr};
 
 pub async fn search_assets(
     db: &DatabaseConnection,
-    search_assets_query: SearchAssetsQuery,
+    mut search_assets_query: SearchAssetsQuery,
     sorting: AssetSorting,
     page_options: &PageOptions,
     options: &Options,
 ) -> Result<AssetList, DbErr> {
+    if search_assets_query.name.is_some() && search_assets_query.pg_trgm_schema.is_none() {
+        search_assets_query.pg_trgm_schema = Some(scopes::asset::get_pg_trgm_schema(db).await?);
+    }
     let pagination = create_pagination(page_options)?;
     let (sort_direction, sort_column) = create_sorting(sorting);
+    let grand_total = if options.show_grand_total {
+        let (condition, joins) = search_assets_query.conditions()?;
+        Some(scopes::asset::count_assets_by_condition(db, condition, joins).await?)
+    } else {
+        None
+    };
     let (condition, joins) = search_assets_query.conditions()?;
-    let assets = scopes::asset::get_assets_by_condition(
-        db,
-        condition,
-        joins,
-        sort_column,
-        sort_direction,
-        &pagination,
-        page_options.limit,
-        options,
-    )
-    .await?;
+    let name_rank = match sorting.sort_by {
+        AssetSortBy::NameRelevance => search_assets_query.name_rank(),
+        _ => None,
+    };
+    let assets = match name_rank {
+        Some(rank) => {
+            scopes::asset::get_assets_by_rank(
+                db,
+                condition,
+                joins,
+                rank,
+                sort_direction,
+                &pagination,
+                page_options.limit,
+                options,
+            )
+            .await?
+        }
+        None => {
+            scopes::asset::get_assets_by_condition(
+                db,
+                condition,
+                joins,
+                sort_column,
+                sort_direction,
+                &pagination,
+                page_options.limit,
+                options,
+            )
+            .await?
+        }
+    };
     Ok(build_asset_response(
         assets,
         page_options.limit,
+        sort_column,
         &pagination,
+        grand_total,
         options,
     ))
 }
@@ -19,6 +19,8 @@ pub struct AssetProof {
     pub node_index: i64,
     pub leaf: String,
     pub tree_id: String,
+    /// Whether hashing the leaf up the proof path reproduces `root`.
+    pub verified: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema, Default)]
@@ -223,12 +225,46 @@ pub struct Compression {
     pub asset_data_hash: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_data: Option<AssetData>,
     pub asset_hash: String,
     pub tree: String,
     pub seq: i64,
     pub leaf_id: i64,
 }
 
+/// Data attached to a Bubblegum V2 leaf, its hash is `asset_data_hash`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetData {
+    pub schema: AssetDataSchema,
+    /// The raw bytes, base58 encoded.
+    pub data: String,
+    /// The data decoded as JSON, when the schema is `json` and the bytes are valid JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub enum AssetDataSchema {
+    #[serde(rename = "binary")]
+    Binary,
+    #[serde(rename = "json")]
+    Json,
+    #[serde(rename = "msg_pack")]
+    MsgPack,
+}
+
+#[cfg(feature = "sql_types")]
+impl From<crate::dao::sea_orm_active_enums::AssetDataSchema> for AssetDataSchema {
+    fn from(schema: crate::dao::sea_orm_active_enums::AssetDataSchema) -> Self {
+        match schema {
+            crate::dao::sea_orm_active_enums::AssetDataSchema::Binary => AssetDataSchema::Binary,
+            crate::dao::sea_orm_active_enums::AssetDataSchema::Json => AssetDataSchema::Json,
+            crate::dao::sea_orm_active_enums::AssetDataSchema::MsgPack => AssetDataSchema::MsgPack,
+        }
+    }
+}
+
 pub type GroupKey = String;
 pub type GroupValue = String;
 
@@ -275,6 +311,17 @@ impl From<RoyaltyTargetType> for RoyaltyModel {
     }
 }
 
+#[cfg(feature = "sql_types")]
+impl From<RoyaltyModel> for RoyaltyTargetType {
+    fn from(model: RoyaltyModel) -> Self {
+        match model {
+            RoyaltyModel::Creators => RoyaltyTargetType::Creators,
+            RoyaltyModel::Fanout => RoyaltyTargetType::Fanout,
+            RoyaltyModel::Single => RoyaltyTargetType::Single,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Royalty {
     pub royalty_model: RoyaltyModel,
@@ -325,6 +372,108 @@ impl From<OwnerType> for OwnershipModel {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub enum TreeVersion {
+    #[serde(rename = "v1")]
+    V1,
+    #[serde(rename = "v2")]
+    V2,
+}
+
+#[cfg(feature = "sql_types")]
+impl From<crate::dao::sea_orm_active_enums::TreeVersion> for TreeVersion {
+    fn from(version: crate::dao::sea_orm_active_enums::TreeVersion) -> Self {
+        match version {
+            crate::dao::sea_orm_active_enums::TreeVersion::V1 => TreeVersion::V1,
+            crate::dao::sea_orm_active_enums::TreeVersion::V2 => TreeVersion::V2,
+        }
+    }
+}
+
+/// Lock state of a programmable NFT, read from its token record.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub enum TokenState {
+    #[serde(rename = "unlocked")]
+    Unlocked,
+    #[serde(rename = "locked")]
+    Locked,
+    #[serde(rename = "listed")]
+    Listed,
+}
+
+#[cfg(feature = "sql_types")]
+impl From<crate::dao::sea_orm_active_enums::TokenState> for TokenState {
+    fn from(state: crate::dao::sea_orm_active_enums::TokenState) -> Self {
+        match state {
+            crate::dao::sea_orm_active_enums::TokenState::Unlocked => TokenState::Unlocked,
+            crate::dao::sea_orm_active_enums::TokenState::Locked => TokenState::Locked,
+            crate::dao::sea_orm_active_enums::TokenState::Listed => TokenState::Listed,
+        }
+    }
+}
+
+#[cfg(feature = "sql_types")]
+impl From<TokenState> for crate::dao::sea_orm_active_enums::TokenState {
+    fn from(state: TokenState) -> Self {
+        match state {
+            TokenState::Unlocked => crate::dao::sea_orm_active_enums::TokenState::Unlocked,
+            TokenState::Locked => crate::dao::sea_orm_active_enums::TokenState::Locked,
+            TokenState::Listed => crate::dao::sea_orm_active_enums::TokenState::Listed,
+        }
+    }
+}
+
+/// Role of the token delegate of a programmable NFT, read from its token record.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub enum TokenDelegateRole {
+    #[serde(rename = "sale")]
+    Sale,
+    #[serde(rename = "transfer")]
+    Transfer,
+    #[serde(rename = "utility")]
+    Utility,
+    #[serde(rename = "staking")]
+    Staking,
+    #[serde(rename = "standard")]
+    Standard,
+    #[serde(rename = "locked_transfer")]
+    LockedTransfer,
+    #[serde(rename = "migration")]
+    Migration,
+}
+
+#[cfg(feature = "sql_types")]
+impl From<crate::dao::sea_orm_active_enums::TokenDelegateRole> for TokenDelegateRole {
+    fn from(role: crate::dao::sea_orm_active_enums::TokenDelegateRole) -> Self {
+        use crate::dao::sea_orm_active_enums::TokenDelegateRole as Db;
+        match role {
+            Db::Sale => TokenDelegateRole::Sale,
+            Db::Transfer => TokenDelegateRole::Transfer,
+            Db::Utility => TokenDelegateRole::Utility,
+            Db::Staking => TokenDelegateRole::Staking,
+            Db::Standard => TokenDelegateRole::Standard,
+            Db::LockedTransfer => TokenDelegateRole::LockedTransfer,
+            Db::Migration => TokenDelegateRole::Migration,
+        }
+    }
+}
+
+#[cfg(feature = "sql_types")]
+impl From<TokenDelegateRole> for crate::dao::sea_orm_active_enums::TokenDelegateRole {
+    fn from(role: TokenDelegateRole) -> Self {
+        use crate::dao::sea_orm_active_enums::TokenDelegateRole as Db;
+        match role {
+            TokenDelegateRole::Sale => Db::Sale,
+            TokenDelegateRole::Transfer => Db::Transfer,
+            TokenDelegateRole::Utility => Db::Utility,
+            TokenDelegateRole::Staking => Db::Staking,
+            TokenDelegateRole::Standard => Db::Standard,
+            TokenDelegateRole::LockedTransfer => Db::LockedTransfer,
+            TokenDelegateRole::Migration => Db::Migration,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Ownership {
     pub frozen: bool,
@@ -332,6 +481,10 @@ pub struct Ownership {
     pub non_transferable: Option<bool>,
     pub delegated: bool,
     pub delegate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_state: Option<TokenState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delegate_role: Option<TokenDelegateRole>,
     pub ownership_model: OwnershipModel,
     pub owner: String,
 }
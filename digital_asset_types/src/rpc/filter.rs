@@ -1,3 +1,4 @@
+use super::{Interface, RoyaltyModel};
 use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -28,6 +29,9 @@ pub enum AssetSortBy {
     Updated,
     #[serde(rename = "recent_action")]
     RecentAction,
+    /// Best `name` matches first, only supported by searchAssets with a name filter.
+    #[serde(rename = "name_relevance")]
+    NameRelevance,
     #[serde(rename = "none")]
     None,
 }
@@ -57,3 +61,66 @@ pub enum SearchConditionType {
     #[serde(rename = "any")]
     Any,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct AttributeFilter {
+    pub trait_type: String,
+    /// Matches assets whose trait has any of these values.
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct AttributeFilters {
+    /// How the trait filters are combined, defaults to `all`.
+    pub condition_type: Option<SearchConditionType>,
+    pub traits: Vec<AttributeFilter>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum NameMatch {
+    #[serde(rename = "exact")]
+    Exact,
+    /// Case-insensitive prefix match.
+    #[serde(rename = "prefix")]
+    Prefix,
+    /// Case-insensitive substring match.
+    #[serde(rename = "contains")]
+    #[default]
+    Contains,
+    /// Trigram similarity match, tolerates typos.
+    #[serde(rename = "fuzzy")]
+    Fuzzy,
+}
+
+/// Boolean expression over the searchAssets predicates, e.g.
+/// `{"and": [{"or": [{"ownerAddress": "A"}, {"ownerAddress": "B"}]}, {"not": {"burnt": true}}]}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub enum SearchFilter {
+    And(Vec<SearchFilter>),
+    Or(Vec<SearchFilter>),
+    Not(Box<SearchFilter>),
+    OwnerAddress(String),
+    CreatorAddress(String),
+    Grouping((String, String)),
+    Delegate(String),
+    Frozen(bool),
+    Supply(u64),
+    Burnt(bool),
+    RoyaltyTargetType(RoyaltyModel),
+    RoyaltyTarget(String),
+    RoyaltyAmount(u32),
+    Interface(Interface),
+    TokenType(TokenTypeClass),
+    JsonUri(String),
+}
+
+/// Inclusive bounds on a numeric field, at least one of them must be set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RangeFilter<T> {
+    pub min: Option<T>,
+    pub max: Option<T>,
+}
@@ -14,4 +14,6 @@ pub struct Options {
     pub show_inscription: bool,
     #[serde(default)]
     pub show_fungible: bool,
+    #[serde(default)]
+    pub show_grand_total: bool,
 }
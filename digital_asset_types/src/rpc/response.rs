@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 
 use {
-    crate::rpc::{Asset, TokenAccount},
+    crate::rpc::{Asset, TokenAccount, TreeVersion},
     serde::{Deserialize, Serialize},
 };
 
@@ -18,6 +18,8 @@ pub struct GetGroupingResponse {
     pub group_key: String,
     pub group_name: String,
     pub group_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_max_size: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
@@ -33,11 +35,22 @@ pub struct AssetList {
     pub after: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grand_total: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grand_total_estimated: Option<bool>,
     pub items: Vec<Asset>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub errors: Vec<DasError>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct AssetNotification {
+    pub slot: u64,
+    pub asset: Asset,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
 #[serde(default)]
 pub struct TransactionSignatureList {
@@ -49,7 +62,31 @@ pub struct TransactionSignatureList {
     pub before: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub after: Option<String>,
-    pub items: Vec<(String, String)>,
+    pub items: Vec<TransactionSignatureItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// A signature with the instruction name, or the full row when structured items are requested.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum TransactionSignatureItem {
+    Pair(String, String),
+    Structured(TransactionSignature),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct TransactionSignature {
+    pub signature: String,
+    pub instruction: String,
+    pub slot: Option<u64>,
+    /// Tree sequence number of the change, only set for compressed assets.
+    pub seq: Option<u64>,
+    /// When the indexer recorded the signature, in RFC 3339.
+    pub created_at: Option<String>,
+    pub tree: Option<String>,
+    pub leaf_index: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
@@ -87,6 +124,9 @@ pub struct NftEditions {
     pub max_supply: Option<u64>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub editions: Vec<NftEdition>,
+    /// Edition numbers flagged as minted by the master edition's edition markers.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub minted_editions: Vec<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -96,3 +136,94 @@ pub struct NftEditions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct TreeInfo {
+    pub tree: String,
+    pub max_depth: Option<u32>,
+    pub max_buffer_size: Option<u32>,
+    pub canopy_depth: Option<u32>,
+    pub capacity: Option<u64>,
+    pub creation_slot: Option<u64>,
+    pub tree_creator: Option<String>,
+    pub tree_delegate: Option<String>,
+    pub is_public: Option<bool>,
+    pub version: Option<TreeVersion>,
+    pub num_minted: u64,
+    pub slot_updated: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct TreeInfoList {
+    pub total: u32,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    pub trees: Vec<TreeInfo>,
+    pub cursor: Option<String>,
+}
+
+/// A stretch of sequence numbers during which a compressed asset kept the same owner and
+/// delegate. `to_seq` and `to_slot` are unset for the span that is still current.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct OwnershipSpan {
+    pub owner: String,
+    pub delegate: Option<String>,
+    pub from_seq: u64,
+    pub from_slot: u64,
+    pub to_seq: Option<u64>,
+    pub to_slot: Option<u64>,
+    pub instruction: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct AssetOwnershipHistory {
+    pub id: String,
+    pub items: Vec<OwnershipSpan>,
+}
+
+/// A use authority approved by the owner of an asset, with the number of uses it can still spend.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct DelegatedUseAuthority {
+    pub address: String,
+    pub authority: String,
+    pub allowed_uses: u64,
+}
+
+/// A collection authority approved by the update authority of a collection asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct DelegatedCollectionAuthority {
+    pub address: String,
+    pub authority: String,
+    pub update_authority: Option<String>,
+}
+
+/// A metadata delegate approved by the update authority of an asset. `role` is only set when the
+/// record address matches one of the known delegate roles.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct DelegatedMetadataAuthority {
+    pub address: String,
+    pub delegate: String,
+    pub role: Option<String>,
+    pub update_authority: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(default)]
+pub struct AssetDelegatedAuthorities {
+    pub id: String,
+    pub use_authorities: Vec<DelegatedUseAuthority>,
+    pub collection_authorities: Vec<DelegatedCollectionAuthority>,
+    pub metadata_delegates: Vec<DelegatedMetadataAuthority>,
+}
@@ -89,6 +89,9 @@ pub fn create_asset_data(
             raw_name: Some(metadata.name.into_bytes().to_vec().clone()),
             raw_symbol: Some(metadata.symbol.into_bytes().to_vec().clone()),
             base_info_seq: Some(0),
+            bubblegum_asset_data: None,
+            bubblegum_asset_data_schema: None,
+            bubblegum_asset_data_seq: None,
         },
     )
 }
@@ -177,6 +180,8 @@ pub fn create_asset(
             asset_data_hash: None,
             bubblegum_flags: None,
             non_transferable: None,
+            token_state: None,
+            token_delegate_role: None,
         },
     )
 }
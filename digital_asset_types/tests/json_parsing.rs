@@ -37,6 +37,9 @@ pub async fn parse_onchain_json(json: serde_json::Value) -> Content {
         raw_name: Some(String::from("Handalf").into_bytes().to_vec()),
         raw_symbol: Some(String::from("").into_bytes().to_vec()),
         base_info_seq: Some(0),
+        bubblegum_asset_data: None,
+        bubblegum_asset_data_schema: None,
+        bubblegum_asset_data_seq: None,
     };
 
     v1_content_from_json(&asset_data).unwrap()
@@ -0,0 +1,185 @@
+use std::{collections::HashSet, str::FromStr};
+
+use das_api::asset_changes::{
+    AssetChange, AssetChangeFilter, AssetChangeListener, ASSET_CHANGES_CHANNEL,
+};
+use digital_asset_types::rpc::{
+    response::AssetNotification, Asset, Group, Ownership, OwnershipModel,
+};
+use function_name::named;
+use futures::{
+    future::{ready, FutureExt},
+    StreamExt,
+};
+use itertools::Itertools;
+use program_transformers::ProgramTransformer;
+use sea_orm::{ConnectionTrait, DbBackend, SqlxPostgresConnector, Statement};
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+use sqlx::{
+    postgres::{PgListener, PgPoolOptions},
+    Executor, PgPool,
+};
+use tokio::time::{timeout, Duration};
+
+use super::common::*;
+
+const NFT: &str = "CMVuYDS9nTeujfTPJb8ik7CRhAqZv4DfjfdamFLkJgxE";
+const COLLECTION: &str = "BwwjnxTHeVWdFieDWmoezta19q2NiCkkNUdpXaEZVTbF";
+
+fn asset_change(owner: &str, previous_owner: Option<&str>, collection: &str) -> AssetChange {
+    AssetChange {
+        notification: AssetNotification {
+            slot: 1,
+            asset: Asset {
+                id: NFT.to_string(),
+                ownership: Some(Ownership {
+                    frozen: false,
+                    non_transferable: None,
+                    delegated: false,
+                    delegate: None,
+                    token_state: None,
+                    delegate_role: None,
+                    ownership_model: OwnershipModel::Single,
+                    owner: owner.to_string(),
+                }),
+                grouping: Some(vec![Group {
+                    group_key: "collection".to_string(),
+                    group_value: Some(collection.to_string()),
+                    verified: Some(true),
+                    collection_metadata: None,
+                }]),
+                ..Default::default()
+            },
+        },
+        previous_owner: previous_owner.map(ToString::to_string),
+    }
+}
+
+#[test]
+fn test_asset_change_filter_matches() {
+    let owner = Pubkey::new_unique().to_string();
+    let previous_owner = Pubkey::new_unique().to_string();
+    let other = Pubkey::new_unique().to_string();
+    let transfer = asset_change(&owner, Some(&previous_owner), COLLECTION);
+
+    assert!(AssetChangeFilter::Asset(NFT.to_string()).matches(&transfer));
+    assert!(!AssetChangeFilter::Asset(other.clone()).matches(&transfer));
+
+    // Both sides of a transfer are notified.
+    assert!(AssetChangeFilter::Owner(owner.clone()).matches(&transfer));
+    assert!(AssetChangeFilter::Owner(previous_owner).matches(&transfer));
+    assert!(!AssetChangeFilter::Owner(other.clone()).matches(&transfer));
+    assert!(
+        AssetChangeFilter::Owner(owner.clone()).matches(&asset_change(&owner, None, COLLECTION))
+    );
+
+    assert!(
+        AssetChangeFilter::Group("collection".to_string(), COLLECTION.to_string())
+            .matches(&transfer)
+    );
+    assert!(!AssetChangeFilter::Group("collection".to_string(), other).matches(&transfer));
+    assert!(
+        !AssetChangeFilter::Group("token_group".to_string(), COLLECTION.to_string())
+            .matches(&transfer)
+    );
+
+    let mut ungrouped = asset_change(&owner, None, COLLECTION);
+    ungrouped.notification.asset.grouping = None;
+    ungrouped.notification.asset.ownership = None;
+    assert!(
+        !AssetChangeFilter::Group("collection".to_string(), COLLECTION.to_string())
+            .matches(&ungrouped)
+    );
+    assert!(!AssetChangeFilter::Owner(owner).matches(&ungrouped));
+}
+
+/// A pool whose sessions opt in to the asset change triggers.
+async fn notifying_pool() -> PgPool {
+    PgPoolOptions::new()
+        .after_connect(|conn, _| {
+            Box::pin(async move {
+                conn.execute("SET das.asset_change_notify = 'on'").await?;
+                Ok(())
+            })
+        })
+        .connect(&std::env::var("DATABASE_TEST_URL").unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_change_notifications() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let pool = setup_pg_pool(std::env::var("DATABASE_TEST_URL").unwrap()).await;
+    let mut listener = PgListener::connect_with(&pool).await.unwrap();
+    listener.listen(ASSET_CHANGES_CHANNEL).await.unwrap();
+
+    // Sessions that don't opt in write without notifying.
+    let silent_mint = Pubkey::new_unique();
+    index_account_bytes(&setup, serialized_mint(silent_mint, Pubkey::new_unique())).await;
+
+    let setup = TestSetup {
+        transformer: ProgramTransformer::new(
+            notifying_pool().await,
+            Box::new(|_info| ready(Ok(())).boxed()),
+        ),
+        ..setup
+    };
+    let nft = Pubkey::from_str(NFT).unwrap();
+    index_seed_events(&setup, vec![&seed_nft(NFT)]).await;
+
+    let mut ids = HashSet::new();
+    timeout(Duration::from_secs(10), async {
+        while !ids.contains(&hex::encode(nft)) {
+            let notification = listener.recv().await.unwrap();
+            let payload: serde_json::Value = serde_json::from_str(notification.payload()).unwrap();
+            ids.insert(payload["id"].as_str().unwrap().to_string());
+        }
+    })
+    .await
+    .expect("no notification for the indexed nft");
+    assert!(!ids.contains(&hex::encode(silent_mint)));
+
+    // Subscribers get the loaded asset. The listener starts in the background, so keep
+    // notifying until it picks the change up.
+    let asset_changes = AssetChangeListener::spawn(
+        pool.clone(),
+        SqlxPostgresConnector::from_sqlx_postgres_pool(pool),
+    );
+    let mut notifications = asset_changes.subscribe(AssetChangeFilter::Asset(NFT.to_string()));
+    let payloads = [silent_mint, nft]
+        .iter()
+        .map(|id| serde_json::json!({ "id": hex::encode(id), "slot": 7 }).to_string())
+        .collect_vec();
+    let notification = timeout(Duration::from_secs(10), async {
+        loop {
+            for payload in &payloads {
+                setup
+                    .db
+                    .execute(Statement::from_sql_and_values(
+                        DbBackend::Postgres,
+                        "SELECT pg_notify($1, $2)",
+                        [ASSET_CHANGES_CHANNEL.into(), payload.clone().into()],
+                    ))
+                    .await
+                    .unwrap();
+            }
+            if let Ok(Some(notification)) =
+                timeout(Duration::from_millis(200), notifications.next()).await
+            {
+                return notification;
+            }
+        }
+    })
+    .await
+    .expect("subscriber was not notified");
+    assert_eq!(notification.slot, 7);
+    assert_eq!(notification.asset.id, NFT);
+}
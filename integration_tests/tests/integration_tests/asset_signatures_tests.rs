@@ -0,0 +1,244 @@
+use function_name::named;
+use std::str::FromStr;
+
+use das_api::api::{self, ApiContract};
+use digital_asset_types::{
+    dao::token_accounts,
+    rpc::response::{TransactionSignature, TransactionSignatureItem},
+};
+use itertools::Itertools;
+use mpl_token_metadata::accounts::Metadata;
+use program_transformers::TransactionInfo;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serial_test::serial;
+use solana_sdk::{
+    instruction::{AccountMeta, CompiledInstruction, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+};
+
+use super::common::*;
+
+const MINT: &str = "CMVuYDS9nTeujfTPJb8ik7CRhAqZv4DfjfdamFLkJgxE";
+
+fn transaction(signature: Signature, slot: u64, instructions: Vec<Instruction>) -> TransactionInfo {
+    let account_keys = instructions
+        .iter()
+        .flat_map(|ix| {
+            std::iter::once(ix.program_id).chain(ix.accounts.iter().map(|meta| meta.pubkey))
+        })
+        .unique()
+        .collect::<Vec<_>>();
+    let index_of = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+    let message_instructions = instructions
+        .iter()
+        .map(|ix| CompiledInstruction {
+            program_id_index: index_of(&ix.program_id),
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|meta| index_of(&meta.pubkey))
+                .collect(),
+            data: ix.data.clone(),
+        })
+        .collect();
+    TransactionInfo {
+        slot,
+        signature,
+        account_keys,
+        message_instructions,
+        meta_inner_instructions: vec![],
+    }
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_signatures_for_uncompressed_nft() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    let mint = Pubkey::from_str(MINT).unwrap();
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    let seeds = seed_nfts([MINT]);
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let token_account = token_accounts::Entity::find()
+        .filter(token_accounts::Column::Mint.eq(mint.to_bytes().to_vec()))
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .unwrap();
+    let token_account = Pubkey::try_from(token_account.pubkey.as_slice()).unwrap();
+    let owner = Pubkey::new_unique();
+
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &token_account,
+        &Pubkey::new_unique(),
+        &owner,
+        &[],
+        1,
+    )
+    .unwrap();
+    let update_metadata = Instruction::new_with_bytes(
+        mpl_token_metadata::ID,
+        &[15],
+        vec![
+            AccountMeta::new(Metadata::find_pda(&mint).0, false),
+            AccountMeta::new_readonly(owner, true),
+        ],
+    );
+    let unrelated = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &owner,
+        &[],
+        1,
+    )
+    .unwrap();
+
+    let transfer_signature = Signature::new_unique();
+    let update_signature = Signature::new_unique();
+    for tx in [
+        transaction(transfer_signature, DEFAULT_SLOT, vec![transfer.clone()]),
+        // The metadata account is matched through the mint of the token account.
+        transaction(
+            update_signature,
+            DEFAULT_SLOT + 1,
+            vec![update_metadata, transfer],
+        ),
+    ] {
+        setup.transformer.handle_transaction(&tx).await.unwrap();
+    }
+    let unrelated = transaction(Signature::new_unique(), DEFAULT_SLOT + 2, vec![unrelated]);
+    assert!(setup
+        .transformer
+        .handle_transaction(&unrelated)
+        .await
+        .is_err());
+
+    let request = api::GetAssetSignatures {
+        id: Some(MINT.to_string()),
+        limit: None,
+        page: None,
+        before: None,
+        after: None,
+        tree: None,
+        leaf_index: None,
+        cursor: None,
+        sort_direction: None,
+        instruction: None,
+        structured: None,
+    };
+    let response = setup.das_api.get_asset_signatures(request).await.unwrap();
+    assert_eq!(
+        response.items,
+        vec![
+            TransactionSignatureItem::Pair(
+                update_signature.to_string(),
+                "UpdateMetadataAccountV2".to_string()
+            ),
+            TransactionSignatureItem::Pair(transfer_signature.to_string(), "Transfer".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_signatures_structured() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+    let asset_id = "FLFoCw2RBbxiw9rbEeqPWJ5rasArD9kTCKWEJirTexsU";
+    let mint_signature =
+        "2DP84v6Pi3e4v5i7KSvzmK4Ufbzof3TAiEqDbm9gg8jZpBRF9f1Cy6x54kvZoHPX9k1XfqbsG1FTv2KVP9fvNrN6";
+    let update_signature =
+        "3bsL5zmLKvhN9Je4snTKxjFSpmXEEg2cvMHm2rCNgaEYkNXBqJTA4N7QmvBSWPiNUQPtzJSYzpQYX92NowV3L7vN";
+    let request = |limit, cursor, instruction| api::GetAssetSignatures {
+        id: Some(asset_id.to_string()),
+        limit,
+        page: None,
+        before: None,
+        after: None,
+        tree: None,
+        leaf_index: None,
+        cursor,
+        sort_direction: None,
+        instruction,
+        structured: Some(true),
+    };
+    let structured = |item: &TransactionSignatureItem| match item {
+        TransactionSignatureItem::Structured(signature) => signature.clone(),
+        TransactionSignatureItem::Pair(..) => panic!("Expected a structured item"),
+    };
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    let seeds = seed_txns([mint_signature, update_signature]);
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let response = setup
+        .das_api
+        .get_asset_signatures(request(None, None, None))
+        .await
+        .unwrap();
+    let items = response.items.iter().map(structured).collect_vec();
+    assert_eq!(items.len(), 2);
+    assert_eq!(
+        items[1],
+        TransactionSignature {
+            signature: mint_signature.to_string(),
+            instruction: "MintV1".to_string(),
+            slot: Some(273252291),
+            seq: Some(1),
+            created_at: items[1].created_at.clone(),
+            tree: Some("EzppnpWWMKg4egUkfe86aQQyYabFL9jPPWTrZjniDHxv".to_string()),
+            leaf_index: Some(0),
+        }
+    );
+    assert!(items[1].created_at.is_some());
+    assert_eq!(items[0].signature, update_signature);
+    assert_eq!(items[0].seq, Some(2));
+
+    // Page through one signature at a time using the returned cursor.
+    let first = setup
+        .das_api
+        .get_asset_signatures(request(Some(1), None, None))
+        .await
+        .unwrap();
+    assert_eq!(structured(&first.items[0]).signature, update_signature);
+    let second = setup
+        .das_api
+        .get_asset_signatures(request(Some(1), first.cursor, None))
+        .await
+        .unwrap();
+    assert_eq!(structured(&second.items[0]).signature, mint_signature);
+    let last = setup
+        .das_api
+        .get_asset_signatures(request(Some(1), second.cursor, None))
+        .await
+        .unwrap();
+    assert!(last.items.is_empty());
+
+    let minted = setup
+        .das_api
+        .get_asset_signatures(request(None, None, Some("MintV1".to_string())))
+        .await
+        .unwrap();
+    assert_eq!(minted.items.len(), 1);
+    assert_eq!(structured(&minted.items[0]).signature, mint_signature);
+
+    let burned = setup
+        .das_api
+        .get_asset_signatures(request(None, None, Some("Burn".to_string())))
+        .await
+        .unwrap();
+    assert!(burned.items.is_empty());
+}
@@ -372,3 +372,41 @@ async fn test_mint_transfer_mpl_programs() {
 
     run_get_asset_scenario_test(&setup, asset_id, seeds, Order::AllPermutations).await;
 }
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_ownership_history() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let asset_id = "8vw7tdLGE3FBjaetsJrZAarwsbc8UESsegiLyvWXxs5A";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        "5coWPFty37s7haT3SVyMf6PkTaABEnhCRhfDjXeMNS58czHB5dCFPY6VrsZNwxBnqypmNic1LbLp1j5qjbdnZAc8",
+        "k6jmJcurgBQ6F2bVa86Z1vGb7ievzxwRZ8GAqzFEG8HicDizxceYPUm1KTzWZ3QKtGgy1EuFWUGCRqBeKU9SAoJ",
+        "KHNhLijkAMeKeKm6kpbk3go6q9uMF3zmfCoYSBgERe8qJDW8q5ANpnkyBuyVkychXCeWzRY8i5EtKfeGaDDU23w",
+    ]);
+
+    // The history is keyed by sequence number, so it does not depend on indexing order.
+    for events in seeds.iter().permutations(seeds.len()) {
+        apply_migrations_and_delete_data(setup.db.clone()).await;
+        index_seed_events(&setup, events).await;
+
+        let request = api::GetAssetOwnershipHistory {
+            id: asset_id.to_string(),
+        };
+        let response = setup
+            .das_api
+            .get_asset_ownership_history(request)
+            .await
+            .unwrap();
+        insta::assert_json_snapshot!(name.clone(), response);
+    }
+}
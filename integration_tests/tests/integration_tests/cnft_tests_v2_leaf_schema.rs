@@ -1,10 +1,34 @@
+use borsh::BorshSerialize;
 use function_name::named;
+use std::str::FromStr;
 
 use das_api::api::{self, ApiContract};
+use digital_asset_types::dao::{asset, cl_audits_v2, merkle_tree, sea_orm_active_enums};
 
 use itertools::Itertools;
-
+use mpl_bubblegum::{
+    instructions::{UpdateAssetDataV2, UpdateAssetDataV2InstructionArgs},
+    types::{AssetDataSchema, BubblegumEventType, LeafSchema, Version},
+    LeafSchemaEvent,
+};
+use program_transformers::TransactionInfo;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serial_test::serial;
+use solana_sdk::{
+    bs58,
+    instruction::{CompiledInstruction, Instruction},
+    keccak,
+    pubkey::Pubkey,
+    signature::Signature,
+    system_program,
+};
+use solana_transaction_status::{InnerInstruction, InnerInstructions};
+use spl_account_compression::{
+    events::{
+        AccountCompressionEvent, ApplicationDataEvent, ApplicationDataEventV1, ChangeLogEvent,
+    },
+    state::PathNode,
+};
 
 use super::common::*;
 
@@ -512,3 +536,187 @@ async fn test_update_metadata_v2_new_name_new_uri() {
 
     run_get_asset_scenario_test(&setup, asset_id, seeds, Order::AllPermutations).await;
 }
+
+/// Builds a transaction with a single Bubblegum instruction and the noop events it would log.
+fn bubblegum_transaction(
+    signature: Signature,
+    slot: u64,
+    instruction: Instruction,
+    events: Vec<AccountCompressionEvent>,
+) -> TransactionInfo {
+    let account_keys = std::iter::once(instruction.program_id)
+        .chain(instruction.accounts.iter().map(|meta| meta.pubkey))
+        .chain(std::iter::once(spl_noop::id()))
+        .unique()
+        .collect::<Vec<_>>();
+    let index_of = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+    let message_instructions = vec![CompiledInstruction {
+        program_id_index: index_of(&instruction.program_id),
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|meta| index_of(&meta.pubkey))
+            .collect(),
+        data: instruction.data,
+    }];
+    let instructions = events
+        .into_iter()
+        .map(|event| InnerInstruction {
+            instruction: CompiledInstruction {
+                program_id_index: index_of(&spl_noop::id()),
+                accounts: vec![],
+                data: event.try_to_vec().unwrap(),
+            },
+            stack_height: Some(2),
+        })
+        .collect();
+    TransactionInfo {
+        slot,
+        signature,
+        account_keys,
+        message_instructions,
+        meta_inner_instructions: vec![InnerInstructions {
+            index: 0,
+            instructions,
+        }],
+    }
+}
+
+fn decode_hash(hash: Option<String>) -> [u8; 32] {
+    hash.map(|hash| {
+        bs58::decode(hash.trim())
+            .into_vec()
+            .unwrap()
+            .try_into()
+            .unwrap()
+    })
+    .unwrap_or_default()
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_update_asset_data_v2() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+
+    let asset_id = "H98ZTBg5XdWRxt35XDznbX2Z1m7nCYeYqoBTdi5qAQBg";
+
+    let seeds: Vec<SeedEvent> = seed_txns([
+        // create_tree_v2
+        "tgmTSFiDAGWmWqDg5QkaHfJaX7KRzB6R9catUSpCFbMFSh4RvDVmfJA9idDPhfJTX5XzFEGJ8wtvT8oKktohTon",
+        // mint_v2
+        "2yyNYTb2ojt4CmTMWqV3szZ5muRx5MWfYTpq1ph2t7xDeAmxvnfVDgZLvcYK1oHzj2K8GWJ2eKvdT9tLxcr1yT4P",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let id = Pubkey::from_str(asset_id).unwrap();
+    let minted = asset::Entity::find_by_id(id.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .unwrap();
+    let tree = Pubkey::try_from(minted.tree_id.clone().unwrap().as_slice()).unwrap();
+    let owner = Pubkey::try_from(minted.owner.clone().unwrap().as_slice()).unwrap();
+    let max_depth = merkle_tree::Entity::find_by_id(tree.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .unwrap()
+        .max_depth
+        .unwrap() as u32;
+    let nonce = minted.nonce.unwrap() as u64;
+    let seq = minted.leaf_seq.unwrap() as u64 + 1;
+
+    // The indexer trusts the logged leaf, so the update can be built from the indexed state.
+    let asset_data = br#"{"level":7}"#.to_vec();
+    let data_hash = decode_hash(minted.data_hash.clone());
+    let creator_hash = decode_hash(minted.creator_hash.clone());
+    let flags = minted.bubblegum_flags.map(|flags| flags as u8);
+    let instruction = UpdateAssetDataV2 {
+        tree_config: Pubkey::new_unique(),
+        payer: owner,
+        authority: None,
+        leaf_owner: owner,
+        leaf_delegate: None,
+        merkle_tree: tree,
+        core_collection: None,
+        log_wrapper: spl_noop::id(),
+        compression_program: spl_account_compression::id(),
+        system_program: system_program::id(),
+    }
+    .instruction(UpdateAssetDataV2InstructionArgs {
+        root: [0; 32],
+        data_hash,
+        creator_hash,
+        previous_asset_data_hash: None,
+        flags,
+        nonce,
+        index: nonce as u32,
+        new_asset_data: Some(asset_data.clone()),
+        new_asset_data_schema: Some(AssetDataSchema::Json),
+    });
+    let leaf = LeafSchemaEvent {
+        event_type: BubblegumEventType::LeafSchemaEvent,
+        version: Version::V2,
+        schema: LeafSchema::V2 {
+            id,
+            owner,
+            delegate: owner,
+            nonce,
+            data_hash,
+            creator_hash,
+            collection_hash: decode_hash(minted.collection_hash.clone()),
+            asset_data_hash: keccak::hash(&asset_data).to_bytes(),
+            flags: flags.unwrap_or_default(),
+        },
+        leaf_hash: [1; 32],
+    };
+    let leaf_node = (1u32 << max_depth) + nonce as u32;
+    let path = (0..=max_depth)
+        .map(|level| PathNode::new([level as u8; 32], leaf_node >> level))
+        .collect();
+    let events = vec![
+        AccountCompressionEvent::ApplicationData(ApplicationDataEvent::V1(
+            ApplicationDataEventV1 {
+                application_data: leaf.try_to_vec().unwrap(),
+            },
+        )),
+        AccountCompressionEvent::ChangeLog(ChangeLogEvent::new(tree, path, seq, nonce as u32)),
+    ];
+
+    let signature = Signature::new_unique();
+    let transaction = bubblegum_transaction(signature, DEFAULT_SLOT, instruction, events);
+    setup
+        .transformer
+        .handle_transaction(&transaction)
+        .await
+        .unwrap();
+
+    let audit = cl_audits_v2::Entity::find()
+        .filter(cl_audits_v2::Column::Tree.eq(tree.to_bytes().to_vec()))
+        .filter(cl_audits_v2::Column::Seq.eq(seq as i64))
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        audit.instruction,
+        sea_orm_active_enums::Instruction::UpdateAssetDataV2
+    );
+
+    let request = api::GetAsset {
+        id: asset_id.to_string(),
+        ..api::GetAsset::default()
+    };
+    let response = setup.das_api.get_asset(request).await.unwrap();
+    insta::assert_json_snapshot!(name, response);
+}
@@ -25,8 +25,8 @@ use plerkle_serialization::{
 use program_transformers::ProgramTransformer;
 
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
+use solana_sdk::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -52,6 +52,7 @@ use solana_sdk::{
     commitment_config::{CommitmentConfig, CommitmentLevel},
 };
 use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use spl_token::state::Mint;
 use std::{fmt, time::Duration};
 
 use std::path::PathBuf;
@@ -94,6 +95,7 @@ impl TestSetup {
 
         let das_api_config: Config = das_api::config::Config {
             database_url: database_test_url.to_string(),
+            rpc_url: Some(rpc_url.to_string()),
             ..Default::default()
         };
         let das_api = das_api::api::DasApi::from_config(das_api_config)
@@ -353,6 +355,41 @@ pub async fn get_token_largest_account(client: &RpcClient, mint: Pubkey) -> anyh
     }
 }
 
+/// Packed SPL Token mint with a supply of one and no decimals.
+pub fn mint_data(mint_authority: Pubkey) -> Vec<u8> {
+    let mut data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::Some(mint_authority),
+        supply: 1,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+/// Plerkle serialized account update of a [`mint_data`] mint at [`DEFAULT_SLOT`].
+pub fn serialized_mint(mint: Pubkey, mint_authority: Pubkey) -> Vec<u8> {
+    let data = mint_data(mint_authority);
+    let fbb = serialize_account(
+        flatbuffers::FlatBufferBuilder::new(),
+        &ReplicaAccountInfoV2 {
+            pubkey: &mint.to_bytes(),
+            lamports: 1,
+            owner: &spl_token::id().to_bytes(),
+            executable: false,
+            rent_epoch: 0,
+            data: &data,
+            write_version: 0,
+            txn_signature: None,
+        },
+        DEFAULT_SLOT,
+        false,
+    );
+    fbb.finished_data().to_vec()
+}
+
 pub async fn index_account_bytes(setup: &TestSetup, account_bytes: Vec<u8>) {
     let account = root_as_account_info(&account_bytes).unwrap();
 
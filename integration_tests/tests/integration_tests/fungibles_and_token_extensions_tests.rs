@@ -5,7 +5,21 @@ use das_api::{
 };
 use function_name::named;
 use itertools::Itertools;
+use program_transformers::AccountInfo;
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
 use serial_test::serial;
+use solana_sdk::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use spl_pod::optional_keys::OptionalNonZeroPubkey;
+use spl_token_2022::{
+    extension::{
+        metadata_pointer::MetadataPointer, non_transferable::NonTransferable,
+        permanent_delegate::PermanentDelegate, transfer_fee::TransferFeeConfig,
+        transfer_hook::TransferHook, ExtensionType, StateWithExtensionsMut,
+    },
+    state::{Account, AccountState, Mint},
+};
+use spl_token_group_interface::state::{TokenGroup, TokenGroupMember};
+use spl_token_metadata_interface::state::TokenMetadata;
 
 #[tokio::test]
 #[serial]
@@ -159,3 +173,474 @@ async fn test_token_keg_fungible_with_no_metadata() {
 
     assert!(matches!(response, Err(DasApiError::DatabaseError(_))));
 }
+
+fn token_metadata(mint: &Pubkey) -> TokenMetadata {
+    TokenMetadata {
+        mint: *mint,
+        name: "Group".to_string(),
+        symbol: "GRP".to_string(),
+        ..TokenMetadata::default()
+    }
+}
+
+// A Token-2022 mint carrying its own metadata and, optionally, group or member extensions.
+fn token_2022_mint_data(
+    metadata: &TokenMetadata,
+    group: Option<(u32, u32)>,
+    member: Option<(&Pubkey, u32)>,
+) -> Vec<u8> {
+    token_2022_mint_data_with(metadata, group, member, &[], |_| {})
+}
+
+fn token_2022_mint_data_with(
+    metadata: &TokenMetadata,
+    group: Option<(u32, u32)>,
+    member: Option<(&Pubkey, u32)>,
+    extra_extensions: &[ExtensionType],
+    init_extra: impl FnOnce(&mut StateWithExtensionsMut<Mint>),
+) -> Vec<u8> {
+    let mint = &metadata.mint;
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    extensions.extend_from_slice(extra_extensions);
+    if group.is_some() {
+        extensions.push(ExtensionType::TokenGroup);
+    }
+    if member.is_some() {
+        extensions.push(ExtensionType::TokenGroupMember);
+    }
+    let metadata_len = metadata.tlv_size_of().unwrap();
+    let len = ExtensionType::try_calculate_account_len::<Mint>(&extensions).unwrap() + metadata_len;
+    let mut data = vec![0; len];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    state.base = Mint {
+        mint_authority: COption::None,
+        supply: 1,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    let metadata_pointer = state.init_extension::<MetadataPointer>(true).unwrap();
+    metadata_pointer.metadata_address = OptionalNonZeroPubkey::try_from(Some(*mint)).unwrap();
+    if let Some((size, max_size)) = group {
+        let update_authority = OptionalNonZeroPubkey::try_from(Some(*mint)).unwrap();
+        let token_group = state.init_extension::<TokenGroup>(true).unwrap();
+        *token_group = TokenGroup::new(mint, update_authority, max_size);
+        token_group.size = size.into();
+    }
+    if let Some((group, member_number)) = member {
+        *state.init_extension::<TokenGroupMember>(true).unwrap() =
+            TokenGroupMember::new(mint, group, member_number);
+    }
+    init_extra(&mut state);
+    state.init_variable_len_extension(metadata, false).unwrap();
+    data
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_token_extensions_token_group() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let group = Pubkey::new_from_array([1; 32]);
+    let members = [
+        Pubkey::new_from_array([2; 32]),
+        Pubkey::new_from_array([3; 32]),
+    ];
+    // A mint that claims membership on behalf of another mint is ignored.
+    let spoofed = Pubkey::new_from_array([4; 32]);
+
+    let mut accounts = vec![(
+        group,
+        token_2022_mint_data(&token_metadata(&group), Some((2, 10)), None),
+    )];
+    for (i, member) in members.iter().enumerate() {
+        accounts.push((
+            *member,
+            token_2022_mint_data(&token_metadata(member), None, Some((&group, i as u32 + 1))),
+        ));
+    }
+    accounts.push((
+        spoofed,
+        token_2022_mint_data(&token_metadata(&members[0]), None, Some((&group, 3))),
+    ));
+
+    for (pubkey, data) in accounts {
+        setup
+            .transformer
+            .handle_account_update(&AccountInfo {
+                slot: DEFAULT_SLOT,
+                pubkey,
+                owner: spl_token_2022::id(),
+                data,
+            })
+            .await
+            .unwrap();
+    }
+
+    let request = api::GetGrouping {
+        group_key: "token_group".to_string(),
+        group_value: group.to_string(),
+    };
+    let response = setup.das_api.get_grouping(request).await.unwrap();
+    insta::assert_json_snapshot!(name, response);
+
+    let request: api::GetAssetsByGroup = serde_json::from_value(serde_json::json!({
+        "groupKey": "token_group",
+        "groupValue": group.to_string(),
+    }))
+    .unwrap();
+    let response = setup.das_api.get_assets_by_group(request).await.unwrap();
+    let ids: Vec<String> = response
+        .items
+        .iter()
+        .map(|a| a.id.clone())
+        .sorted()
+        .collect();
+    let expected: Vec<String> = members.iter().map(|m| m.to_string()).sorted().collect();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_token_extensions_metadata_content() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let mint = Pubkey::new_from_array([5; 32]);
+    let update_authority = Pubkey::new_from_array([6; 32]);
+    let mut metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(update_authority)).unwrap(),
+        mint,
+        name: "Extension".to_string(),
+        symbol: "EXT".to_string(),
+        uri: String::new(),
+        additional_metadata: vec![
+            ("rarity".to_string(), "rare".to_string()),
+            ("level".to_string(), "7".to_string()),
+        ],
+    };
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT,
+            pubkey: mint,
+            owner: spl_token_2022::id(),
+            data: token_2022_mint_data(&metadata, None, None),
+        })
+        .await
+        .unwrap();
+
+    let request = api::GetAsset {
+        id: mint.to_string(),
+        ..api::GetAsset::default()
+    };
+    let response = setup.das_api.get_asset(request.clone()).await.unwrap();
+    insta::assert_json_snapshot!(
+        name,
+        (response.content, response.authorities, response.mutable)
+    );
+
+    // Removing the update authority makes the metadata immutable.
+    metadata.update_authority = OptionalNonZeroPubkey::default();
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT + 1,
+            pubkey: mint,
+            owner: spl_token_2022::id(),
+            data: token_2022_mint_data(&metadata, None, None),
+        })
+        .await
+        .unwrap();
+    let response = setup.das_api.get_asset(request.clone()).await.unwrap();
+    let authorities = response.authorities.unwrap_or_default();
+    assert_eq!(authorities.len(), 1);
+    assert_eq!(authorities[0].address, update_authority.to_string());
+    assert!(!response.mutable);
+
+    // Mints with a Metaplex metadata account keep the authority Token Metadata indexed.
+    setup
+        .db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "UPDATE asset SET specification_version = 'v1' WHERE id = $1",
+            [mint.to_bytes().to_vec().into()],
+        ))
+        .await
+        .unwrap();
+    metadata.update_authority =
+        OptionalNonZeroPubkey::try_from(Some(Pubkey::new_from_array([7; 32]))).unwrap();
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT + 2,
+            pubkey: mint,
+            owner: spl_token_2022::id(),
+            data: token_2022_mint_data(&metadata, None, None),
+        })
+        .await
+        .unwrap();
+    let response = setup.das_api.get_asset(request).await.unwrap();
+    let authorities = response.authorities.unwrap_or_default();
+    assert_eq!(authorities.len(), 1);
+    assert_eq!(authorities[0].address, update_authority.to_string());
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_token_extensions_search_filters() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let owner = Pubkey::new_from_array([10; 32]);
+    let hook_program = Pubkey::new_from_array([11; 32]);
+    let plain = Pubkey::new_from_array([12; 32]);
+    let hooked = Pubkey::new_from_array([13; 32]);
+    let delegated = Pubkey::new_from_array([14; 32]);
+    let soulbound = Pubkey::new_from_array([15; 32]);
+    let fee_low = Pubkey::new_from_array([16; 32]);
+    let fee_high = Pubkey::new_from_array([17; 32]);
+
+    let with_fee = |bps: u16| {
+        move |state: &mut StateWithExtensionsMut<Mint>| {
+            let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+            config.newer_transfer_fee.transfer_fee_basis_points = bps.into();
+            config.older_transfer_fee.transfer_fee_basis_points = bps.into();
+        }
+    };
+    let mints = [
+        token_2022_mint_data(&token_metadata(&plain), None, None),
+        token_2022_mint_data_with(
+            &token_metadata(&hooked),
+            None,
+            None,
+            &[ExtensionType::TransferHook],
+            |state| {
+                state
+                    .init_extension::<TransferHook>(true)
+                    .unwrap()
+                    .program_id = OptionalNonZeroPubkey::try_from(Some(hook_program)).unwrap();
+            },
+        ),
+        token_2022_mint_data_with(
+            &token_metadata(&delegated),
+            None,
+            None,
+            &[ExtensionType::PermanentDelegate],
+            |state| {
+                state
+                    .init_extension::<PermanentDelegate>(true)
+                    .unwrap()
+                    .delegate = OptionalNonZeroPubkey::try_from(Some(owner)).unwrap();
+            },
+        ),
+        token_2022_mint_data_with(
+            &token_metadata(&soulbound),
+            None,
+            None,
+            &[ExtensionType::NonTransferable],
+            |state| {
+                state.init_extension::<NonTransferable>(true).unwrap();
+            },
+        ),
+        token_2022_mint_data_with(
+            &token_metadata(&fee_low),
+            None,
+            None,
+            &[ExtensionType::TransferFeeConfig],
+            with_fee(50),
+        ),
+        token_2022_mint_data_with(
+            &token_metadata(&fee_high),
+            None,
+            None,
+            &[ExtensionType::TransferFeeConfig],
+            with_fee(500),
+        ),
+    ];
+    let ids = [plain, hooked, delegated, soulbound, fee_low, fee_high];
+
+    for (i, (mint, data)) in ids.iter().zip(mints).enumerate() {
+        setup
+            .transformer
+            .handle_account_update(&AccountInfo {
+                slot: DEFAULT_SLOT,
+                pubkey: *mint,
+                owner: spl_token_2022::id(),
+                data,
+            })
+            .await
+            .unwrap();
+
+        let mut data = vec![0; Account::LEN];
+        Account {
+            mint: *mint,
+            owner,
+            amount: 1,
+            state: AccountState::Initialized,
+            ..Account::default()
+        }
+        .pack_into_slice(&mut data);
+        setup
+            .transformer
+            .handle_account_update(&AccountInfo {
+                slot: DEFAULT_SLOT,
+                pubkey: Pubkey::new_from_array([20 + i as u8; 32]),
+                owner: spl_token_2022::id(),
+                data,
+            })
+            .await
+            .unwrap();
+    }
+
+    let search = |mut filters: serde_json::Value| {
+        let das_api = &setup.das_api;
+        filters["ownerAddress"] = owner.to_string().into();
+        filters["tokenType"] = "Fungible".into();
+        async move {
+            let request: api::SearchAssets = serde_json::from_value(filters).unwrap();
+            das_api
+                .search_assets(request)
+                .await
+                .unwrap()
+                .items
+                .into_iter()
+                .map(|a| a.id)
+                .sorted()
+                .collect::<Vec<String>>()
+        }
+    };
+    let sorted =
+        |keys: &[Pubkey]| -> Vec<String> { keys.iter().map(|k| k.to_string()).sorted().collect() };
+
+    assert_eq!(
+        search(serde_json::json!({ "hasTransferHook": true })).await,
+        sorted(&[hooked])
+    );
+    assert_eq!(
+        search(serde_json::json!({ "transferHookProgram": hook_program.to_string() })).await,
+        sorted(&[hooked])
+    );
+    assert_eq!(
+        search(serde_json::json!({ "hasPermanentDelegate": true })).await,
+        sorted(&[delegated])
+    );
+    assert_eq!(
+        search(serde_json::json!({ "nonTransferable": true })).await,
+        sorted(&[soulbound])
+    );
+    assert_eq!(
+        search(serde_json::json!({ "transferFeeBpsAbove": 100 })).await,
+        sorted(&[fee_high])
+    );
+    assert_eq!(
+        search(serde_json::json!({
+            "hasTransferHook": false,
+            "hasPermanentDelegate": false,
+            "nonTransferable": false,
+            "transferFeeBpsAbove": 0,
+        }))
+        .await,
+        sorted(&[fee_low, fee_high])
+    );
+    assert_eq!(
+        search(serde_json::json!({
+            "hasTransferHook": false,
+            "hasPermanentDelegate": false,
+            "nonTransferable": false,
+        }))
+        .await,
+        sorted(&[plain, fee_low, fee_high])
+    );
+
+    let request: api::GetAssetsByOwner = serde_json::from_value(serde_json::json!({
+        "ownerAddress": owner.to_string(),
+        "transferFeeBpsAbove": 10,
+    }))
+    .unwrap();
+    let response = setup.das_api.get_assets_by_owner(request).await.unwrap();
+    let owned: Vec<String> = response.items.into_iter().map(|a| a.id).sorted().collect();
+    assert_eq!(owned, sorted(&[fee_low, fee_high]));
+
+    let request: api::GetAssetsByOwner = serde_json::from_value(serde_json::json!({
+        "ownerAddress": owner.to_string(),
+    }))
+    .unwrap();
+    let response = setup.das_api.get_assets_by_owner(request).await.unwrap();
+    assert_eq!(response.total, ids.len() as u32);
+
+    // A fee increase scheduled for a later epoch does not apply yet.
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT + 1,
+            pubkey: fee_low,
+            owner: spl_token_2022::id(),
+            data: token_2022_mint_data_with(
+                &token_metadata(&fee_low),
+                None,
+                None,
+                &[ExtensionType::TransferFeeConfig],
+                |state| {
+                    with_fee(50)(state);
+                    let config = state.get_extension_mut::<TransferFeeConfig>().unwrap();
+                    config.newer_transfer_fee.epoch = 100_000.into();
+                    config.newer_transfer_fee.transfer_fee_basis_points = 1000.into();
+                },
+            ),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        search(serde_json::json!({ "transferFeeBpsAbove": 100 })).await,
+        sorted(&[fee_high])
+    );
+
+    // A newer fee applies once its epoch has started.
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT + 2,
+            pubkey: fee_low,
+            owner: spl_token_2022::id(),
+            data: token_2022_mint_data_with(
+                &token_metadata(&fee_low),
+                None,
+                None,
+                &[ExtensionType::TransferFeeConfig],
+                |state| {
+                    with_fee(50)(state);
+                    let config = state.get_extension_mut::<TransferFeeConfig>().unwrap();
+                    config.newer_transfer_fee.epoch = 0.into();
+                    config.newer_transfer_fee.transfer_fee_basis_points = 1000.into();
+                },
+            ),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        search(serde_json::json!({ "transferFeeBpsAbove": 100 })).await,
+        sorted(&[fee_low, fee_high])
+    );
+
+    let request: api::SearchAssets = serde_json::from_value(serde_json::json!({
+        "transferHookProgram": "not-a-pubkey",
+    }))
+    .unwrap();
+    assert!(matches!(
+        setup.das_api.search_assets(request).await,
+        Err(DasApiError::ValidationError(_))
+    ));
+}
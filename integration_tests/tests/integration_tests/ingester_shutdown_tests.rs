@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use digital_asset_types::dao::{tasks, tokens};
+use function_name::named;
+use nft_ingester::{
+    account_updates::account_worker,
+    ack::ack_worker,
+    source::{MemorySource, MessageSource},
+    tasks::{BgTask, DownloadMetadata, DownloadMetadataTask, IntoTaskData, TaskManager},
+};
+use plerkle_messenger::ConsumptionType;
+use sea_orm::{ConnectionTrait, DbBackend, EntityTrait, Statement, TransactionTrait};
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+use tokio::{
+    sync::mpsc::unbounded_channel,
+    time::{sleep, timeout, Duration},
+};
+use tokio_util::sync::CancellationToken;
+
+use super::common::*;
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_account_worker_drains_and_flushes_acks_on_shutdown() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let pool = setup_pg_pool(std::env::var("DATABASE_TEST_URL").unwrap()).await;
+    let source = MemorySource::new(16);
+    let (bg_task_sender, _bg_task_receiver) = unbounded_channel();
+    // The worker holds the only ack sender, so the ack worker stops once the worker is done.
+    let (ack_task, ack_sender) = ack_worker(Arc::new(source.clone()));
+    let shutdown = CancellationToken::new();
+    let worker = account_worker(
+        pool,
+        Arc::new(source.clone()),
+        bg_task_sender,
+        ack_sender,
+        ConsumptionType::New,
+        "ACC",
+        None,
+        shutdown.clone(),
+    );
+
+    // Writes to `tokens` block until the lock is released, holding the mint update in flight.
+    let lock = setup.db.begin().await.unwrap();
+    lock.execute(Statement::from_string(
+        DbBackend::Postgres,
+        "LOCK TABLE tokens IN EXCLUSIVE MODE".to_string(),
+    ))
+    .await
+    .unwrap();
+
+    let mint = Pubkey::new_unique();
+    let id = source
+        .send("ACC", serialized_mint(mint, Pubkey::new_unique()))
+        .await;
+    let mut consumer = source.consumer().await.unwrap();
+    timeout(Duration::from_secs(10), async {
+        while consumer.stream_size("ACC").await.unwrap() > 0 {
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("mint update was not received");
+
+    shutdown.cancel();
+    sleep(Duration::from_millis(200)).await;
+    assert!(!worker.is_finished());
+    assert!(source.acked("ACC").is_empty());
+
+    // Sent after the cancellation, while the worker is still busy, so it is never received.
+    let unreceived = source
+        .send(
+            "ACC",
+            serialized_mint(Pubkey::new_unique(), Pubkey::new_unique()),
+        )
+        .await;
+
+    lock.rollback().await.unwrap();
+    timeout(Duration::from_secs(10), worker)
+        .await
+        .expect("account worker did not stop")
+        .unwrap();
+    timeout(Duration::from_secs(10), ack_task)
+        .await
+        .expect("ack worker did not stop")
+        .unwrap();
+
+    assert_eq!(source.acked("ACC"), vec![id]);
+    assert!(!source.acked("ACC").contains(&unreceived));
+    assert_eq!(consumer.stream_size("ACC").await.unwrap(), 1);
+    assert!(tokens::Entity::find_by_id(mint.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_task_listener_saves_queued_tasks_on_shutdown() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let pool = setup_pg_pool(std::env::var("DATABASE_TEST_URL").unwrap()).await;
+    let task_definitions: Vec<Box<dyn BgTask>> = vec![Box::new(DownloadMetadataTask {
+        lock_duration: None,
+        max_attempts: None,
+        timeout: None,
+    })];
+    let mut manager = TaskManager::new(name.clone(), pool, task_definitions);
+    let listener = manager.start_listener(false);
+    let sender = manager.get_sender().unwrap();
+
+    for i in 0..10 {
+        let task = DownloadMetadata {
+            asset_data_id: Pubkey::new_unique().to_bytes().to_vec(),
+            uri: format!("https://example.com/{}.json", i),
+            created_at: None,
+        };
+        sender.send(task.into_task_data().unwrap()).unwrap();
+    }
+
+    // Queued tasks are saved before the listener exits, once every sender is dropped.
+    drop(sender);
+    drop(manager);
+    timeout(Duration::from_secs(10), listener)
+        .await
+        .expect("task listener did not stop")
+        .unwrap();
+
+    let saved = tasks::Entity::find().all(setup.db.as_ref()).await.unwrap();
+    assert_eq!(saved.len(), 10);
+}
@@ -0,0 +1,295 @@
+use std::{
+    net::SocketAddr,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use function_name::named;
+use futures::{stream, Stream, StreamExt};
+use nft_ingester::{
+    account_updates::account_worker,
+    ack::ack_worker,
+    config::GrpcSourceConfig,
+    plerkle::{PlerkleAccountInfo, PlerkleTransactionInfo},
+    source::{GrpcSource, MemorySource, MessageSource},
+};
+use plerkle_messenger::ConsumptionType;
+use plerkle_serialization::{root_as_account_info, root_as_transaction_info};
+use program_transformers::{AccountInfo, TransactionInfo};
+use sea_orm::EntityTrait;
+use serial_test::serial;
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    transaction::SanitizedTransaction, transaction::Transaction,
+};
+use solana_transaction_status::TransactionStatusMeta;
+use tokio::{
+    net::TcpListener,
+    sync::mpsc::unbounded_channel,
+    time::{sleep, timeout, Duration},
+};
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_util::sync::CancellationToken;
+use yellowstone_grpc_proto::{
+    convert_to::{create_transaction, create_transaction_meta},
+    prelude::{
+        geyser_server::{Geyser, GeyserServer},
+        subscribe_update::UpdateOneof,
+        GetBlockHeightRequest, GetBlockHeightResponse, GetLatestBlockhashRequest,
+        GetLatestBlockhashResponse, GetSlotRequest, GetSlotResponse, GetVersionRequest,
+        GetVersionResponse, IsBlockhashValidRequest, IsBlockhashValidResponse, PingRequest,
+        PongResponse, SubscribeRequest, SubscribeUpdate, SubscribeUpdateAccount,
+        SubscribeUpdateAccountInfo, SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo,
+    },
+    tonic::{self, transport::Server, Request, Response, Status, Streaming},
+};
+
+use super::common::*;
+use digital_asset_types::dao::tokens;
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_memory_source_drives_account_worker() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let pool = setup_pg_pool(std::env::var("DATABASE_TEST_URL").unwrap()).await;
+    let source = MemorySource::new(16);
+    let (bg_task_sender, _bg_task_receiver) = unbounded_channel();
+    let (ack_task, ack_sender) = ack_worker(Arc::new(source.clone()));
+    let worker = account_worker(
+        pool,
+        Arc::new(source.clone()),
+        bg_task_sender,
+        ack_sender,
+        ConsumptionType::New,
+        "ACC",
+        None,
+        CancellationToken::new(),
+    );
+
+    let mint = Pubkey::new_unique();
+    let id = source
+        .send("ACC", serialized_mint(mint, Pubkey::new_unique()))
+        .await;
+
+    timeout(Duration::from_secs(10), async {
+        while !source.acked("ACC").contains(&id) {
+            sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("account message was not acked");
+    worker.abort();
+    ack_task.abort();
+
+    let token = tokens::Entity::find_by_id(mint.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .expect("mint was not indexed");
+    assert_eq!(token.supply, 1.into());
+    assert_eq!(token.token_program, spl_token::id().to_bytes().to_vec());
+}
+
+type UpdateStream = Pin<Box<dyn Stream<Item = Result<SubscribeUpdate, Status>> + Send>>;
+
+/// Stand-in for a Yellowstone geyser server, it sends its updates on the first subscription
+/// and keeps the stream open.
+struct StandInGeyser {
+    updates: Vec<SubscribeUpdate>,
+    requests: Arc<Mutex<Vec<SubscribeRequest>>>,
+}
+
+#[tonic::async_trait]
+impl Geyser for StandInGeyser {
+    type SubscribeStream = UpdateStream;
+
+    async fn subscribe(
+        &self,
+        request: Request<Streaming<SubscribeRequest>>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        if let Some(request) = request.into_inner().message().await? {
+            self.requests.lock().unwrap().push(request);
+        }
+        let updates =
+            stream::iter(self.updates.clone().into_iter().map(Ok)).chain(stream::pending());
+        Ok(Response::new(Box::pin(updates)))
+    }
+
+    async fn ping(&self, _request: Request<PingRequest>) -> Result<Response<PongResponse>, Status> {
+        Err(Status::unimplemented("ping"))
+    }
+
+    async fn get_latest_blockhash(
+        &self,
+        _request: Request<GetLatestBlockhashRequest>,
+    ) -> Result<Response<GetLatestBlockhashResponse>, Status> {
+        Err(Status::unimplemented("get_latest_blockhash"))
+    }
+
+    async fn get_block_height(
+        &self,
+        _request: Request<GetBlockHeightRequest>,
+    ) -> Result<Response<GetBlockHeightResponse>, Status> {
+        Err(Status::unimplemented("get_block_height"))
+    }
+
+    async fn get_slot(
+        &self,
+        _request: Request<GetSlotRequest>,
+    ) -> Result<Response<GetSlotResponse>, Status> {
+        Err(Status::unimplemented("get_slot"))
+    }
+
+    async fn is_blockhash_valid(
+        &self,
+        _request: Request<IsBlockhashValidRequest>,
+    ) -> Result<Response<IsBlockhashValidResponse>, Status> {
+        Err(Status::unimplemented("is_blockhash_valid"))
+    }
+
+    async fn get_version(
+        &self,
+        _request: Request<GetVersionRequest>,
+    ) -> Result<Response<GetVersionResponse>, Status> {
+        Err(Status::unimplemented("get_version"))
+    }
+}
+
+async fn start_stand_in_geyser(geyser: StandInGeyser) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(GeyserServer::new(geyser))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    addr
+}
+
+#[tokio::test]
+async fn test_grpc_source_serializes_geyser_updates() {
+    let mint = Pubkey::new_unique();
+    let mint_authority = Pubkey::new_unique();
+    let account_update = SubscribeUpdate {
+        filters: vec!["das".to_string()],
+        update_oneof: Some(UpdateOneof::Account(SubscribeUpdateAccount {
+            account: Some(SubscribeUpdateAccountInfo {
+                pubkey: mint.to_bytes().to_vec(),
+                lamports: 1,
+                owner: spl_token::id().to_bytes().to_vec(),
+                executable: false,
+                rent_epoch: 0,
+                data: mint_data(mint_authority),
+                write_version: 1,
+                txn_signature: None,
+            }),
+            slot: 7,
+            is_startup: false,
+        })),
+    };
+
+    let payer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let transaction =
+        SanitizedTransaction::from_transaction_for_tests(Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(&payer.pubkey(), &recipient, 1)],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Default::default(),
+        ));
+    let transaction_update = SubscribeUpdate {
+        filters: vec!["das".to_string()],
+        update_oneof: Some(UpdateOneof::Transaction(SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                signature: transaction.signature().as_ref().to_vec(),
+                is_vote: false,
+                transaction: Some(create_transaction(&transaction)),
+                meta: Some(create_transaction_meta(&TransactionStatusMeta {
+                    inner_instructions: Some(vec![]),
+                    ..Default::default()
+                })),
+                index: 0,
+            }),
+            slot: 8,
+        })),
+    };
+
+    let requests = Arc::new(Mutex::new(vec![]));
+    let addr = start_stand_in_geyser(StandInGeyser {
+        updates: vec![account_update, transaction_update],
+        requests: Arc::clone(&requests),
+    })
+    .await;
+
+    let source = GrpcSource::new(GrpcSourceConfig {
+        endpoint: format!("http://{}", addr),
+        x_token: None,
+        programs: vec![spl_token::id().to_string()],
+        commitment: None,
+        account_stream: None,
+        transaction_stream: None,
+        buffer_size: None,
+    })
+    .unwrap();
+    let mut consumer = source.consumer().await.unwrap();
+
+    let accounts = timeout(
+        Duration::from_secs(10),
+        consumer.recv("ACC", ConsumptionType::New),
+    )
+    .await
+    .expect("no account update received")
+    .unwrap();
+    assert_eq!(accounts.len(), 1);
+    let account: AccountInfo = PlerkleAccountInfo(root_as_account_info(&accounts[0].data).unwrap())
+        .try_into()
+        .unwrap();
+    assert_eq!(
+        account,
+        AccountInfo {
+            slot: 7,
+            pubkey: mint,
+            owner: spl_token::id(),
+            data: mint_data(mint_authority),
+        }
+    );
+
+    let transactions = timeout(
+        Duration::from_secs(10),
+        consumer.recv("TXN", ConsumptionType::New),
+    )
+    .await
+    .expect("no transaction update received")
+    .unwrap();
+    assert_eq!(transactions.len(), 1);
+    let transaction_info: TransactionInfo =
+        PlerkleTransactionInfo(root_as_transaction_info(&transactions[0].data).unwrap())
+            .try_into()
+            .unwrap();
+    assert_eq!(transaction_info.slot, 8);
+    assert_eq!(transaction_info.signature, *transaction.signature());
+    assert_eq!(
+        transaction_info.account_keys,
+        transaction
+            .message()
+            .account_keys()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>()
+    );
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(
+        requests[0].accounts["das"].owner,
+        vec![spl_token::id().to_string()]
+    );
+    assert_eq!(
+        requests[0].transactions["das"].account_include,
+        vec![spl_token::id().to_string()]
+    );
+}
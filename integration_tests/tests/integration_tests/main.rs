@@ -1,13 +1,19 @@
 mod account_close_tests;
 mod account_update_tests;
+mod asset_changes_tests;
+mod asset_signatures_tests;
 mod cnft_tests;
 mod cnft_tests_v2_leaf_schema;
 mod common;
 mod fungibles_and_token_extensions_tests;
 mod general_scenario_tests;
+mod ingester_shutdown_tests;
+mod ingester_source_tests;
 mod mpl_core_tests;
 mod nft_editions_tests;
 mod ops_purge;
+mod ops_quarantine;
+mod ops_replay;
 mod regular_nft_tests;
 mod show_collection_metadata_option_tests;
 mod show_fungible_flag_tests;
@@ -16,3 +22,5 @@ mod test_get_assets_with_multiple_same_ids;
 mod test_show_zero_balance_filter;
 mod token_accounts_tests;
 mod token_type_test;
+mod transaction_partition_tests;
+mod tree_info_tests;
@@ -1,10 +1,15 @@
+use borsh::BorshSerialize;
 use function_name::named;
+use std::str::FromStr;
 
 use das_api::api::{self, ApiContract};
 
 use itertools::Itertools;
 
+use mpl_token_metadata::{accounts::EditionMarker, types::Key};
+use program_transformers::AccountInfo;
 use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
 
 use super::common::*;
 
@@ -48,3 +53,65 @@ async fn test_get_nft_editions() {
 
     insta::assert_json_snapshot!(name, response);
 }
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_get_nft_editions_with_edition_markers() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Mainnet),
+        },
+    )
+    .await;
+
+    let mint = "Ey2Qb8kLctbchQsMnhZs5DjY32To2QtPuXNwWvk4NosL";
+    let seeds: Vec<SeedEvent> = seed_accounts([
+        mint,
+        "9ZmY7qCaq7WbrR7RZdHWCNS9FrFRPwRqU84wzWfmqLDz",
+        "8SHfqzJYABeGfiG1apwiEYt6TvfGQiL1pdwEjvTKsyiZ",
+        "GJvFDcBWf6aDncd1TBzx2ou1rgLFYaMBdbYLBa9oTAEw",
+        "AoxgzXKEsJmUyF5pBb3djn9cJFA26zh2SQHvd9EYijZV",
+        "9yQecKKYSHxez7fFjJkUvkz42TLmkoXzhyZxEf2pw8pz",
+        "4V9QuYLpiMu4ZQmhdEHmgATdgiHkDeJfvZi84BfkYcez",
+        "giWoA4jqHFkodPJgtbRYRcYtiXbsVytnxnEao3QT2gg",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    // Editions 1, 2 and 60 are flagged in the first marker, the master edition has a max supply
+    // of 69 so a single marker covers all of its editions.
+    let mut ledger = [0; 31];
+    ledger[0] = 0b0110_0000;
+    ledger[7] = 0b0000_1000;
+    let marker = EditionMarker {
+        key: Key::EditionMarker,
+        ledger,
+    };
+    let mint = Pubkey::from_str(mint).unwrap();
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT,
+            pubkey: EditionMarker::find_pda(&mint, "0").0,
+            owner: mpl_token_metadata::ID,
+            data: marker.try_to_vec().unwrap(),
+        })
+        .await
+        .unwrap();
+
+    let request = api::GetNftEditions {
+        mint_address: mint.to_string(),
+        page: None,
+        limit: Some(10),
+        before: None,
+        after: None,
+        cursor: None,
+    };
+    let response = setup.das_api.get_nft_editions(request).await.unwrap();
+
+    insta::assert_json_snapshot!(name, response);
+}
@@ -77,6 +77,12 @@ fn mint_model_with_pubkey(pubkey: &Pubkey) -> tokens::Model {
         slot_updated: 0,
         extensions: None,
         extension_data: None,
+        transfer_hook_program: None,
+        permanent_delegate: None,
+        non_transferable: false,
+        older_transfer_fee_basis_points: None,
+        newer_transfer_fee_basis_points: None,
+        newer_transfer_fee_epoch: None,
     }
 }
 
@@ -274,8 +280,9 @@ async fn test_purging_mints() {
 
     let tokens_select_query = r#"SELECT "tokens"."mint", "tokens"."supply", "tokens"."decimals", "tokens"."token_program",
  "tokens"."mint_authority", "tokens"."freeze_authority", "tokens"."close_authority", "tokens"."extension_data",
- "tokens"."slot_updated", "tokens"."extensions", "tokens"."mint"
- FROM "tokens" LIMIT $1 OFFSET $2"#.replace("\n", "");
+ "tokens"."slot_updated", "tokens"."extensions", "tokens"."transfer_hook_program",
+ "tokens"."permanent_delegate", "tokens"."non_transferable", "tokens"."older_transfer_fee_basis_points",
+ "tokens"."newer_transfer_fee_basis_points", "tokens"."newer_transfer_fee_epoch", "tokens"."mint" FROM "tokens" LIMIT $1 OFFSET $2"#.replace("\n", "");
 
     let expected_db_txs = vec![
         Transaction::from_sql_and_values(
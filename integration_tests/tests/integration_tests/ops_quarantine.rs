@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use das_ops::{
+    quarantine::{list_messages, replay_messages, ListArgs, ReplayArgs},
+    replay::{ReplayReport, ReplayStatus},
+};
+use digital_asset_types::dao::{failed_messages, sea_orm_active_enums::FailedMessageType, tokens};
+use function_name::named;
+use nft_ingester::{
+    account_updates::account_worker,
+    ack::ack_worker,
+    dead_letter::{DeadLetterPolicy, FailedMessage},
+    source::MemorySource,
+};
+use plerkle_messenger::ConsumptionType;
+use sea_orm::EntityTrait;
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+use tokio::{
+    sync::mpsc::unbounded_channel,
+    time::{sleep, timeout, Duration},
+};
+use tokio_util::sync::CancellationToken;
+
+use super::common::*;
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_account_worker_quarantines_failing_messages() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let pool = setup_pg_pool(std::env::var("DATABASE_TEST_URL").unwrap()).await;
+    let source = MemorySource::new(16);
+    let (bg_task_sender, _bg_task_receiver) = unbounded_channel();
+    let (ack_task, ack_sender) = ack_worker(Arc::new(source.clone()));
+    let worker = account_worker(
+        pool,
+        Arc::new(source.clone()),
+        bg_task_sender,
+        ack_sender,
+        ConsumptionType::New,
+        "ACC",
+        Some(1),
+        CancellationToken::new(),
+    );
+
+    let poison = b"not a flatbuffer".to_vec();
+    let poison_id = source.send("ACC", poison.clone()).await;
+    let mint = Pubkey::new_unique();
+    let mint_id = source
+        .send("ACC", serialized_mint(mint, Pubkey::new_unique()))
+        .await;
+
+    timeout(Duration::from_secs(10), async {
+        loop {
+            let acked = source.acked("ACC");
+            if acked.contains(&poison_id) && acked.contains(&mint_id) {
+                break;
+            }
+            sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("messages were not acked");
+    worker.abort();
+    ack_task.abort();
+
+    let quarantined = failed_messages::Entity::find()
+        .all(setup.db.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(quarantined.len(), 1);
+    assert_eq!(quarantined[0].stream, "ACC");
+    assert_eq!(quarantined[0].message_id, poison_id);
+    assert_eq!(quarantined[0].message_type, FailedMessageType::Account);
+    assert_eq!(quarantined[0].data, poison);
+    assert_eq!(quarantined[0].tries, 1);
+
+    assert!(tokens::Entity::find_by_id(mint.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_replay_quarantined_messages() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let pool = setup_pg_pool(std::env::var("DATABASE_TEST_URL").unwrap()).await;
+    let dead_letter = DeadLetterPolicy::new(pool, Some(3));
+
+    let mint = Pubkey::new_unique();
+    let mint_data = serialized_mint(mint, Pubkey::new_unique());
+    let failed_mint = |tries| FailedMessage {
+        stream: "ACC",
+        id: "1-0",
+        message_type: FailedMessageType::Account,
+        data: &mint_data,
+        tries,
+        error: "database error".to_string(),
+        slot: Some(DEFAULT_SLOT),
+        signature: None,
+        pubkey: Some(mint.to_bytes().to_vec()),
+    };
+    assert!(!dead_letter.quarantine(failed_mint(1)).await);
+    assert!(dead_letter.quarantine(failed_mint(2)).await);
+    assert!(
+        dead_letter
+            .quarantine(FailedMessage {
+                stream: "TXN",
+                id: "2-0",
+                message_type: FailedMessageType::Transaction,
+                data: b"not a flatbuffer",
+                tries: 2,
+                error: "invalid flatbuffer".to_string(),
+                slot: None,
+                signature: None,
+                pubkey: None,
+            })
+            .await
+    );
+
+    let listed = list_messages(
+        setup.db.as_ref(),
+        &ListArgs {
+            stream: Some("ACC".to_string()),
+            limit: 100,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].tries, 3);
+    assert_eq!(listed[0].slot, Some(DEFAULT_SLOT as i64));
+
+    let mut statuses = vec![];
+    let report = replay_messages(
+        &setup.transformer,
+        setup.db.as_ref(),
+        &ReplayArgs {
+            ids: vec![],
+            all: true,
+            stream: None,
+        },
+        |outcome| statuses.push(outcome.status.clone()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        report,
+        ReplayReport {
+            indexed: 1,
+            skipped: 0,
+            failed: 1,
+        }
+    );
+    assert_eq!(statuses[0], ReplayStatus::Indexed);
+    assert!(matches!(statuses[1], ReplayStatus::Failed(_)));
+
+    assert!(tokens::Entity::find_by_id(mint.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .is_some());
+    let remaining = failed_messages::Entity::find()
+        .all(setup.db.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].stream, "TXN");
+    assert_ne!(remaining[0].error, "invalid flatbuffer");
+
+    let report = replay_messages(
+        &setup.transformer,
+        setup.db.as_ref(),
+        &ReplayArgs {
+            ids: vec![remaining[0].id + 1],
+            all: false,
+            stream: None,
+        },
+        |_| {},
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.failed, 1);
+}
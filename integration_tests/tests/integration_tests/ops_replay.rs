@@ -0,0 +1,202 @@
+use std::{fs, path::PathBuf, str::FromStr};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use das_ops::replay::{replay, ReplayOutcome, ReplayReport, ReplayStatus};
+use digital_asset_types::dao::{asset, tokens};
+use function_name::named;
+use sea_orm::EntityTrait;
+use serial_test::serial;
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, EncodedTransactionWithStatusMeta,
+};
+
+use super::common::*;
+
+const MINT_SIGNATURE: &str =
+    "4ASu45ELoTmvwhNqokGQrh2VH8p5zeUepYLbkcULMeXSCZJGrJa7ojgdVh5JUxBjAMF9Lrp55EgUUFPaPeWKejNQ";
+const MINTED_ASSET: &str = "DFRJ4PwAze1mMQccRmdyc46yQpEVd4FPiwtAVgzGCs7g";
+
+fn mint_account() -> Account {
+    Account {
+        lamports: 1,
+        data: mint_data(Pubkey::new_unique()),
+        owner: spl_token::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn replay_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("das-ops-replay-{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+async fn mint_exists(setup: &TestSetup, mint: Pubkey) -> bool {
+    tokens::Entity::find_by_id(mint.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .is_some()
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_replay_directory() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let dir = replay_dir(&name);
+    fs::copy(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data/transactions/mint_no_json_uri")
+            .join(MINT_SIGNATURE),
+        dir.join("01_mint.txn"),
+    )
+    .unwrap();
+
+    let plerkle_mint = Pubkey::new_unique();
+    fs::write(
+        dir.join("02_mint.acc"),
+        serialized_mint(plerkle_mint, Pubkey::new_unique()),
+    )
+    .unwrap();
+
+    let json_mint = Pubkey::new_unique();
+    let line_mint = Pubkey::new_unique();
+    let no_meta_transaction = EncodedConfirmedTransactionWithStatusMeta {
+        slot: DEFAULT_SLOT,
+        transaction: EncodedTransactionWithStatusMeta {
+            transaction: EncodedTransaction::LegacyBinary(String::new()),
+            meta: None,
+            version: None,
+        },
+        block_time: None,
+    };
+    let lines = [
+        serde_json::json!({
+            "type": "account",
+            "pubkey": json_mint.to_string(),
+            "slot": DEFAULT_SLOT,
+            "account": UiAccount::encode(
+                &json_mint,
+                &mint_account(),
+                UiAccountEncoding::Base64,
+                None,
+                None,
+            ),
+        })
+        .to_string(),
+        serde_json::json!({
+            "type": "plerkle_account",
+            "data": STANDARD.encode(serialized_mint(line_mint, Pubkey::new_unique())),
+        })
+        .to_string(),
+        String::new(),
+        serde_json::to_string(&no_meta_transaction).unwrap(),
+        "not a record".to_string(),
+    ];
+    fs::write(dir.join("03_records.jsonl"), lines.join("\n")).unwrap();
+    fs::write(dir.join(".ignored"), "not a record").unwrap();
+
+    let mut outcomes: Vec<ReplayOutcome> = vec![];
+    let report = replay(&setup.transformer, &dir, false, |outcome| {
+        outcomes.push(outcome.clone())
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+        report,
+        ReplayReport {
+            indexed: 4,
+            skipped: 1,
+            failed: 1,
+        }
+    );
+    assert_eq!(
+        outcomes
+            .iter()
+            .map(|outcome| (outcome.item.clone(), outcome.status.clone()))
+            .take(5)
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                Some(format!("transaction {}", MINT_SIGNATURE)),
+                ReplayStatus::Indexed
+            ),
+            (
+                Some(format!("account {}", plerkle_mint)),
+                ReplayStatus::Indexed
+            ),
+            (
+                Some(format!("account {}", json_mint)),
+                ReplayStatus::Indexed
+            ),
+            (
+                Some(format!("account {}", line_mint)),
+                ReplayStatus::Indexed
+            ),
+            (None, ReplayStatus::Skipped),
+        ]
+    );
+    assert!(outcomes[5].source.ends_with("03_records.jsonl:5"));
+    assert!(matches!(outcomes[5].status, ReplayStatus::Failed(_)));
+
+    let asset_id = Pubkey::from_str(MINTED_ASSET).unwrap();
+    assert!(asset::Entity::find_by_id(asset_id.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .is_some());
+    for mint in [plerkle_mint, json_mint, line_mint] {
+        assert!(mint_exists(&setup, mint).await);
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_replay_stop_on_error() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let dir = replay_dir(&name);
+    let mint = Pubkey::new_unique();
+    let records = dir.join("records.jsonl");
+    let lines = [
+        serde_json::json!({ "type": "plerkle_account", "data": "not base64" }).to_string(),
+        serde_json::json!({
+            "type": "plerkle_account",
+            "data": STANDARD.encode(serialized_mint(mint, Pubkey::new_unique())),
+        })
+        .to_string(),
+    ];
+    fs::write(&records, lines.join("\n")).unwrap();
+
+    let report = replay(&setup.transformer, &records, true, |_| {})
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report,
+        ReplayReport {
+            indexed: 0,
+            skipped: 0,
+            failed: 1,
+        }
+    );
+    assert!(!mint_exists(&setup, mint).await);
+
+    fs::remove_dir_all(dir).unwrap();
+}
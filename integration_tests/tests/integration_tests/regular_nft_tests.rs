@@ -1,10 +1,33 @@
+use borsh::BorshSerialize;
 use function_name::named;
 
 use das_api::api::{self, ApiContract};
+use digital_asset_types::rpc::{
+    TokenDelegateRole as RpcTokenDelegateRole, TokenState as RpcTokenState,
+};
 
 use itertools::Itertools;
 
+use mpl_token_metadata::{
+    accounts::{
+        CollectionAuthorityRecord, Metadata, MetadataDelegateRecord, TokenRecord,
+        UseAuthorityRecord,
+    },
+    instructions::{
+        ApproveCollectionAuthority, ApproveUseAuthority, ApproveUseAuthorityInstructionArgs,
+    },
+    types::{Key, MetadataDelegateRole, TokenDelegateRole, TokenState},
+};
+use program_transformers::{AccountInfo, TransactionInfo};
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
 use serial_test::serial;
+use solana_sdk::{
+    instruction::{CompiledInstruction, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    system_program,
+};
+use std::str::FromStr;
 
 use super::common::*;
 
@@ -144,3 +167,675 @@ async fn test_reg_search_assets() {
     let response = setup.das_api.search_assets(request).await.unwrap();
     insta::assert_json_snapshot!(name, response);
 }
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_search_assets_cursor_sorted_by_recent_action() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2",
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let mut cursor: Option<String> = None;
+    let mut ids = Vec::new();
+    loop {
+        let request = api::SearchAssets {
+            sort_by: Some(serde_json::from_str(r#"{ "sortBy": "recent_action" }"#).unwrap()),
+            limit: Some(1),
+            cursor: cursor.clone(),
+            ..api::SearchAssets::default()
+        };
+        let response = setup.das_api.search_assets(request).await.unwrap();
+        if response.items.is_empty() {
+            break;
+        }
+        ids.extend(response.items.into_iter().map(|asset| asset.id));
+        cursor = response.cursor;
+    }
+
+    insta::assert_json_snapshot!(name, ids);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_search_assets_grand_total() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2",
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let request = r#"
+    {
+        "limit": 1,
+        "page": 1,
+        "options": {
+            "showGrandTotal": true
+        }
+    }
+    "#;
+
+    let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+    let response = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(response.total, 1);
+    assert_eq!(response.grand_total, Some(2));
+    assert_eq!(response.grand_total_estimated, Some(false));
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_search_assets_by_attributes() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2",
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    // Off-chain metadata is not downloaded in tests, so set the attributes directly.
+    for (mint, metadata) in [
+        (
+            "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2",
+            r#"{"attributes": [{"trait_type": "Background", "value": "Blue"}, {"trait_type": "Level", "value": 5}]}"#,
+        ),
+        (
+            "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL",
+            r#"{"attributes": [{"trait_type": "Background", "value": "Red"}, {"trait_type": "Level", "value": "3"}]}"#,
+        ),
+    ] {
+        setup
+            .db
+            .execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "UPDATE asset_data SET metadata = $1::jsonb WHERE id = $2",
+                [
+                    metadata.into(),
+                    Pubkey::from_str(mint).unwrap().to_bytes().to_vec().into(),
+                ],
+            ))
+            .await
+            .unwrap();
+    }
+
+    let request = r#"
+    {
+        "attributes": {
+            "traits": [
+                { "traitType": "Background", "values": ["Blue", "Red"] },
+                { "traitType": "Level", "values": ["5"] }
+            ]
+        }
+    }
+    "#;
+
+    let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+    let response = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(response.total, 1);
+    assert_eq!(
+        response.items[0].id,
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2"
+    );
+
+    let request = r#"
+    {
+        "attributes": {
+            "conditionType": "any",
+            "traits": [
+                { "traitType": "Background", "values": ["Green"] },
+                { "traitType": "Level", "values": ["3"] }
+            ]
+        }
+    }
+    "#;
+
+    let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+    let response = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(response.total, 1);
+    assert_eq!(
+        response.items[0].id,
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL"
+    );
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_search_assets_by_name() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2",
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    for request in [
+        r#"{ "name": "Claynosaurz: Call of Saga #1887", "nameMatch": "exact" }"#,
+        r#"{ "name": "claynosaurz: call", "nameMatch": "prefix" }"#,
+        r#"{ "name": "saga #1887" }"#,
+        r#"{
+            "name": "Claynosaurs Cal of Saga 1887",
+            "nameMatch": "fuzzy",
+            "sortBy": { "sortBy": "name_relevance" },
+            "page": 1
+        }"#,
+    ] {
+        let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+        let response = setup.das_api.search_assets(request).await.unwrap();
+        assert_eq!(
+            response.items[0].id,
+            "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL"
+        );
+    }
+
+    // LIKE wildcards in the name are matched literally.
+    let request = r#"{ "name": "___" }"#;
+    let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+    let response = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(response.total, 0);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_search_assets_with_filter() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2",
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let request = r#"
+    {
+        "filter": {
+            "and": [
+                {
+                    "or": [
+                        { "ownerAddress": "6Cr66AabRYymhZgYQSfTCo6FVpH18wXrMZswAbcErpyX" },
+                        { "ownerAddress": "11111111111111111111111111111111" }
+                    ]
+                },
+                { "grouping": ["collection", "1yPMtWU5aqcF72RdyRD5yipmcMRC8NGNK59NvYubLkZ"] },
+                { "not": { "burnt": true } }
+            ]
+        }
+    }
+    "#;
+
+    let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+    let response = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(response.total, 1);
+    assert_eq!(
+        response.items[0].id,
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL"
+    );
+
+    let request = r#"
+    {
+        "filter": {
+            "not": { "ownerAddress": "6Cr66AabRYymhZgYQSfTCo6FVpH18wXrMZswAbcErpyX" }
+        }
+    }
+    "#;
+
+    let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+    let response = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(response.total, 1);
+    assert_eq!(
+        response.items[0].id,
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2"
+    );
+
+    // Neither asset has a delegate, so both are "not delegated to" any address.
+    let request = r#"
+    {
+        "filter": {
+            "not": { "delegate": "6Cr66AabRYymhZgYQSfTCo6FVpH18wXrMZswAbcErpyX" }
+        }
+    }
+    "#;
+
+    let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+    let response = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(response.total, 2);
+
+    // An asset without a specification class is neither of the negated interface or token type.
+    setup
+        .db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "UPDATE asset SET specification_asset_class = NULL WHERE id = $1",
+            [
+                Pubkey::from_str("Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL")
+                    .unwrap()
+                    .to_bytes()
+                    .to_vec()
+                    .into(),
+            ],
+        ))
+        .await
+        .unwrap();
+    for filter in [
+        r#"{ "not": { "interface": "V1_NFT" } }"#,
+        r#"{ "not": { "tokenType": "Nft" } }"#,
+        r#"{ "not": { "tokenType": "Fungible" } }"#,
+    ] {
+        let request = format!(r#"{{ "filter": {} }}"#, filter);
+        let request: api::SearchAssets = serde_json::from_str(&request).unwrap();
+        let response = setup.das_api.search_assets(request).await.unwrap();
+        // Without a class the asset has no interface to render, so it is listed in `errors`.
+        assert!(response
+            .errors
+            .iter()
+            .any(|error| error.id == "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL"));
+    }
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_reg_search_assets_with_lists_and_ranges() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let seeds: Vec<SeedEvent> = seed_nfts([
+        "2PfAwPb2hdgsf7xCKyU2kAWUGKnkxYZLfg5SMf4YP1h2",
+        "Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    for (request, total) in [
+        (
+            r#"
+            {
+                "ownerAddresses": [
+                    "C2ch7QUCrYZRkhVVzTXojkdjhdJhaY77i4VQdoPS64HX",
+                    "6Cr66AabRYymhZgYQSfTCo6FVpH18wXrMZswAbcErpyX"
+                ],
+                "groupings": [
+                    ["collection", "1yPMtWU5aqcF72RdyRD5yipmcMRC8NGNK59NvYubLkZ"],
+                    ["collection", "11111111111111111111111111111111"]
+                ],
+                "royaltyAmountRange": { "min": 400, "max": 600 },
+                "slotUpdatedRange": { "min": 1 },
+                "createdAtRange": { "min": 0 }
+            }
+            "#,
+            2,
+        ),
+        (r#"{ "royaltyAmountRange": { "max": 499 } }"#, 0),
+        (
+            r#"{ "ownerAddresses": ["6Cr66AabRYymhZgYQSfTCo6FVpH18wXrMZswAbcErpyX"] }"#,
+            1,
+        ),
+    ] {
+        let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+        let response = setup.das_api.search_assets(request).await.unwrap();
+        assert_eq!(response.total, total);
+    }
+
+    // An asset in more than one of the listed groups is returned once.
+    setup
+        .db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO asset_grouping (asset_id, group_key, group_value, verified) \
+             VALUES ($1, 'token_group', $2, true)",
+            [
+                Pubkey::from_str("Dt3XDSAdXAJbHqvuycgCTHykKCC7tntMFGMmSvfBbpTL")
+                    .unwrap()
+                    .to_bytes()
+                    .to_vec()
+                    .into(),
+                "11111111111111111111111111111111".into(),
+            ],
+        ))
+        .await
+        .unwrap();
+    let request = r#"
+    {
+        "groupings": [
+            ["collection", "1yPMtWU5aqcF72RdyRD5yipmcMRC8NGNK59NvYubLkZ"],
+            ["token_group", "11111111111111111111111111111111"]
+        ]
+    }
+    "#;
+    let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+    let response = setup.das_api.search_assets(request).await.unwrap();
+    assert_eq!(response.total, 2);
+    assert_eq!(
+        response
+            .items
+            .iter()
+            .map(|asset| &asset.id)
+            .unique()
+            .count(),
+        2
+    );
+
+    let request = r#"
+    {
+        "ownerAddress": "6Cr66AabRYymhZgYQSfTCo6FVpH18wXrMZswAbcErpyX",
+        "ownerAddresses": ["C2ch7QUCrYZRkhVVzTXojkdjhdJhaY77i4VQdoPS64HX"]
+    }
+    "#;
+    let request: api::SearchAssets = serde_json::from_str(request).unwrap();
+    assert!(setup.das_api.search_assets(request).await.is_err());
+}
+
+/// Builds a transaction with a single Token Metadata instruction.
+fn token_metadata_transaction(slot: u64, instruction: Instruction) -> TransactionInfo {
+    let account_keys = std::iter::once(instruction.program_id)
+        .chain(instruction.accounts.iter().map(|meta| meta.pubkey))
+        .unique()
+        .collect::<Vec<_>>();
+    let index_of = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+    TransactionInfo {
+        slot,
+        signature: Signature::new_unique(),
+        message_instructions: vec![CompiledInstruction {
+            program_id_index: index_of(&instruction.program_id),
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| index_of(&meta.pubkey))
+                .collect(),
+            data: instruction.data,
+        }],
+        account_keys,
+        meta_inner_instructions: vec![],
+    }
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_delegated_authorities() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let mint = Pubkey::from_str("8gx4sNTSkj9yUJ5QvF7njFa8LG3ZBzsmfH5qtF3xSt3x").unwrap();
+    let metadata = Metadata::find_pda(&mint).0;
+    let user = Pubkey::from_str("EzGoJSRNGN4H4y2Tn3gJqMeCjXqg8j7bzJ1NeBM2Nb1X").unwrap();
+    let collection_authority =
+        Pubkey::from_str("4WgCmt6PtM1u3KsVaaN9iyKLMfSJfQBPp6nqfYBq5Yh9").unwrap();
+    let (use_authority_record, use_bump) = UseAuthorityRecord::find_pda(&mint, &user);
+    let (collection_authority_record, collection_bump) =
+        CollectionAuthorityRecord::find_pda(&mint, &collection_authority);
+
+    // The use authority is approved before its record account is indexed.
+    let approve_use = ApproveUseAuthority {
+        use_authority_record,
+        owner: Pubkey::new_unique(),
+        payer: Pubkey::new_unique(),
+        user,
+        owner_token_account: Pubkey::new_unique(),
+        metadata,
+        mint,
+        burner: Pubkey::new_unique(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: None,
+    }
+    .instruction(ApproveUseAuthorityInstructionArgs { number_of_uses: 5 });
+    setup
+        .transformer
+        .handle_transaction(&token_metadata_transaction(DEFAULT_SLOT, approve_use))
+        .await
+        .unwrap();
+    let use_record = UseAuthorityRecord {
+        key: Key::UseAuthorityRecord,
+        allowed_uses: 5,
+        bump: use_bump,
+    };
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT,
+            pubkey: use_authority_record,
+            owner: mpl_token_metadata::ID,
+            data: use_record.try_to_vec().unwrap(),
+        })
+        .await
+        .unwrap();
+
+    // The collection authority record account is indexed before its approval.
+    let collection_record = CollectionAuthorityRecord {
+        key: Key::CollectionAuthorityRecord,
+        bump: collection_bump,
+        update_authority: Some(Pubkey::new_from_array([7; 32])),
+    };
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT,
+            pubkey: collection_authority_record,
+            owner: mpl_token_metadata::ID,
+            data: collection_record.try_to_vec().unwrap(),
+        })
+        .await
+        .unwrap();
+    let approve_collection = ApproveCollectionAuthority {
+        collection_authority_record,
+        new_collection_authority: collection_authority,
+        update_authority: Pubkey::new_unique(),
+        payer: Pubkey::new_unique(),
+        metadata,
+        mint,
+        system_program: system_program::id(),
+        rent: None,
+    }
+    .instruction();
+    setup
+        .transformer
+        .handle_transaction(&token_metadata_transaction(
+            DEFAULT_SLOT,
+            approve_collection,
+        ))
+        .await
+        .unwrap();
+
+    // Metadata delegate records store their mint and delegate.
+    let update_authority = Pubkey::new_from_array([7; 32]);
+    let (metadata_delegate_record, metadata_delegate_bump) = MetadataDelegateRecord::find_pda(
+        &mint,
+        MetadataDelegateRole::Collection,
+        &update_authority,
+        &collection_authority,
+    );
+    let metadata_delegate = MetadataDelegateRecord {
+        key: Key::MetadataDelegate,
+        bump: metadata_delegate_bump,
+        mint,
+        delegate: collection_authority,
+        update_authority,
+    };
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT,
+            pubkey: metadata_delegate_record,
+            owner: mpl_token_metadata::ID,
+            data: metadata_delegate.try_to_vec().unwrap(),
+        })
+        .await
+        .unwrap();
+
+    let request = api::GetAssetDelegatedAuthorities {
+        id: mint.to_string(),
+    };
+    let response = setup
+        .das_api
+        .get_asset_delegated_authorities(request.clone())
+        .await
+        .unwrap();
+    insta::assert_json_snapshot!(name, response);
+
+    // Revoking the use authority closes its record.
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT + 1,
+            pubkey: use_authority_record,
+            owner: mpl_token_metadata::ID,
+            data: vec![],
+        })
+        .await
+        .unwrap();
+    let response = setup
+        .das_api
+        .get_asset_delegated_authorities(request)
+        .await
+        .unwrap();
+    assert!(response.use_authorities.is_empty());
+    assert_eq!(response.collection_authorities.len(), 1);
+    assert_eq!(response.metadata_delegates.len(), 1);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_programmable_nft_token_record() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    let mint = "CMVuYDS9nTeujfTPJb8ik7CRhAqZv4DfjfdamFLkJgxE";
+    let seeds: Vec<SeedEvent> = seed_nfts([mint]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    let token_accounts = setup
+        .das_api
+        .get_token_accounts(api::GetTokenAccounts {
+            owner_address: None,
+            mint_address: Some(mint.to_string()),
+            limit: None,
+            page: None,
+            before: None,
+            after: None,
+            options: None,
+            cursor: None,
+        })
+        .await
+        .unwrap();
+    let token_account = Pubkey::from_str(&token_accounts.token_accounts[0].address).unwrap();
+    let mint = Pubkey::from_str(mint).unwrap();
+    let (token_record, bump) = TokenRecord::find_pda(&mint, &token_account);
+
+    let mut record = TokenRecord {
+        key: Key::TokenRecord,
+        bump,
+        state: TokenState::Locked,
+        rule_set_revision: None,
+        delegate: Some(Pubkey::new_from_array([3; 32])),
+        delegate_role: Some(TokenDelegateRole::Staking),
+        locked_transfer: None,
+    };
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT,
+            pubkey: token_record,
+            owner: mpl_token_metadata::ID,
+            data: record.try_to_vec().unwrap(),
+        })
+        .await
+        .unwrap();
+
+    let request = api::GetAsset {
+        id: mint.to_string(),
+        ..api::GetAsset::default()
+    };
+    let response = setup.das_api.get_asset(request.clone()).await.unwrap();
+    insta::assert_json_snapshot!(name.clone(), response.ownership);
+
+    let search = api::SearchAssets {
+        token_state: Some(RpcTokenState::Locked),
+        token_delegate_role: Some(RpcTokenDelegateRole::Staking),
+        ..api::SearchAssets::default()
+    };
+    let found = setup.das_api.search_assets(search).await.unwrap();
+    assert_eq!(found.items.len(), 1);
+
+    // Unlocking the token record unfreezes the asset even though its token account stays frozen.
+    record.state = TokenState::Unlocked;
+    record.delegate = None;
+    record.delegate_role = None;
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT + 1,
+            pubkey: token_record,
+            owner: mpl_token_metadata::ID,
+            data: record.try_to_vec().unwrap(),
+        })
+        .await
+        .unwrap();
+    let response = setup.das_api.get_asset(request.clone()).await.unwrap();
+    let ownership = response.ownership.unwrap();
+    assert!(!ownership.frozen);
+    assert_eq!(ownership.token_state, Some(RpcTokenState::Unlocked));
+    assert_eq!(ownership.delegate_role, None);
+
+    // The frozen filter agrees with the response.
+    let search = api::SearchAssets {
+        frozen: Some(false),
+        ..api::SearchAssets::default()
+    };
+    let found = setup.das_api.search_assets(search).await.unwrap();
+    assert_eq!(found.items.len(), 1);
+    assert_eq!(found.items[0].id, mint.to_string());
+    let search = api::SearchAssets {
+        frozen: Some(true),
+        ..api::SearchAssets::default()
+    };
+    let found = setup.das_api.search_assets(search).await.unwrap();
+    assert!(found.items.is_empty());
+
+    // Closing the token record clears its state from the asset.
+    setup
+        .transformer
+        .handle_account_update(&AccountInfo {
+            slot: DEFAULT_SLOT + 2,
+            pubkey: token_record,
+            owner: mpl_token_metadata::ID,
+            data: vec![],
+        })
+        .await
+        .unwrap();
+    let response = setup.das_api.get_asset(request).await.unwrap();
+    let ownership = response.ownership.unwrap();
+    assert_eq!(ownership.token_state, None);
+    assert_eq!(ownership.delegate_role, None);
+}
@@ -218,3 +218,54 @@ async fn test_search_asset_with_token_type_fungible() {
     let response = setup.das_api.search_assets(request).await.unwrap();
     insta::assert_json_snapshot!(name, response);
 }
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_search_asset_filter_owner_of_fungible() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Mainnet),
+        },
+    )
+    .await;
+
+    let seeds: Vec<SeedEvent> = seed_accounts([
+        "7EYnhQoR9YM3N7UoaKRoA44Uy8JeaZV3qyouov87awMs",
+        "7BajpcYgnxmWK91RhrfsdB3Tm83PcDwPvMC8ZinvtTY6",
+        "6BRNfDfdq1nKyU1TQiCEQLWyPtD8EwUH9Kt2ahsbidUx",
+    ]);
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    index_seed_events(&setup, seeds.iter().collect_vec()).await;
+
+    // The owner of a fungible is the owner of its token account, in a filter tree as well.
+    let flat = r#"
+    {
+    "ownerAddress": "2oerfxddTpK5hWAmCMYB6fr9WvNrjEH54CHCWK8sAq7g",
+    "tokenType": "Fungible"
+    }
+    "#;
+    let tree = r#"
+    {
+    "filter": {
+        "and": [
+            { "ownerAddress": "2oerfxddTpK5hWAmCMYB6fr9WvNrjEH54CHCWK8sAq7g" },
+            { "tokenType": "Fungible" }
+        ]
+    }
+    }
+    "#;
+
+    let flat: api::SearchAssets = serde_json::from_str(flat).unwrap();
+    let flat = setup.das_api.search_assets(flat).await.unwrap();
+    let tree: api::SearchAssets = serde_json::from_str(tree).unwrap();
+    let tree = setup.das_api.search_assets(tree).await.unwrap();
+    assert!(flat.total > 0);
+    assert_eq!(
+        tree.items.iter().map(|asset| &asset.id).collect_vec(),
+        flat.items.iter().map(|asset| &asset.id).collect_vec()
+    );
+}
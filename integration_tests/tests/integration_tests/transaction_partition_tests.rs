@@ -0,0 +1,169 @@
+use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+use digital_asset_types::dao::asset;
+use function_name::named;
+use nft_ingester::{
+    ack::ack_worker,
+    config::{WorkerConfig, WorkerType},
+    source::MemorySource,
+    transaction_notifications::{partition_messages, transaction_worker},
+};
+use plerkle_messenger::{ConsumptionType, RecvData};
+use sea_orm::EntityTrait;
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+use tokio::{
+    sync::mpsc::unbounded_channel,
+    time::{sleep, timeout, Duration},
+};
+use tokio_util::sync::CancellationToken;
+
+use super::common::*;
+
+const MINT_DELEGATE_TRANSFER: [&str; 3] = [
+    "KNWsAYPo3mm1HuFxRyEwBBMUZ2hqTnFXjoPVFo7WxGTfmfRwz6K8eERc4dnJpHyuoDkAZu1czK55iB1SbtCsdW2",
+    "3B1sASkuToCWuGFRG47axQDm1SpgLi8qDDGnRFeR7LB6oa5C3ZmkEuX98373gdMTBXED44FkwT227kBBAGSw7e8M",
+    "5Q8TAMMkMTHEM2BHyD2fp2sVdYKByFeATzM2mHF6Xbbar33WaeuygPKGYCWiDEt3MZU1mUrq1ePnT9o4Pa318p8w",
+];
+
+const MINT_TRANSFER_BURN: [&str; 3] = [
+    "5coWPFty37s7haT3SVyMf6PkTaABEnhCRhfDjXeMNS58czHB5dCFPY6VrsZNwxBnqypmNic1LbLp1j5qjbdnZAc8",
+    "k6jmJcurgBQ6F2bVa86Z1vGb7ievzxwRZ8GAqzFEG8HicDizxceYPUm1KTzWZ3QKtGgy1EuFWUGCRqBeKU9SAoJ",
+    "KHNhLijkAMeKeKm6kpbk3go6q9uMF3zmfCoYSBgERe8qJDW8q5ANpnkyBuyVkychXCeWzRY8i5EtKfeGaDDU23w",
+];
+
+fn cached_transaction(test: &str, signature: &str) -> Vec<u8> {
+    std::fs::read(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data/transactions")
+            .join(test)
+            .join(signature),
+    )
+    .unwrap()
+}
+
+fn ids(group: &[RecvData]) -> Vec<&str> {
+    group.iter().map(|item| item.id.as_str()).collect()
+}
+
+#[test]
+fn test_partition_messages_by_tree() {
+    // Interleave the transactions of two trees with messages that touch no tree.
+    let mut items = vec![];
+    for i in 0..3 {
+        items.push(RecvData::new(
+            format!("a-{}", i),
+            cached_transaction("mint_delegate_transfer", MINT_DELEGATE_TRANSFER[i]),
+        ));
+        items.push(RecvData::new(
+            format!("b-{}", i),
+            cached_transaction("mint_transfer_burn", MINT_TRANSFER_BURN[i]),
+        ));
+        items.push(RecvData::new(
+            format!("c-{}", i),
+            b"not a transaction".to_vec(),
+        ));
+    }
+
+    let unpartitioned = partition_messages(items.clone(), None);
+    assert_eq!(unpartitioned.len(), 9);
+    assert!(unpartitioned.iter().all(|group| group.len() == 1));
+
+    let groups = partition_messages(items, Some(4));
+    assert!(groups.len() <= 4);
+    assert_eq!(groups.iter().map(Vec::len).sum::<usize>(), 9);
+    for tree in ["a-", "b-"] {
+        let tree_groups = groups
+            .iter()
+            .map(|group| {
+                ids(group)
+                    .into_iter()
+                    .filter(|id| id.starts_with(tree))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|ids| !ids.is_empty())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tree_groups,
+            vec![vec![
+                format!("{}0", tree),
+                format!("{}1", tree),
+                format!("{}2", tree)
+            ]]
+        );
+    }
+}
+
+#[test]
+fn test_partitioned_stream_consumption_type() {
+    // A partitioned stream has a single consumer, which must claim pending and read new messages.
+    let partitioned = WorkerConfig {
+        stream_name: "TXN".to_string(),
+        worker_type: WorkerType::Transaction,
+        worker_count: 3,
+        partitions: Some(4),
+    };
+    assert_eq!(partitioned.consumer_count(), 1);
+    assert_eq!(partitioned.consumption_type(0), ConsumptionType::All);
+
+    let unpartitioned = WorkerConfig {
+        partitions: None,
+        ..partitioned
+    };
+    assert_eq!(unpartitioned.consumer_count(), 3);
+    assert_eq!(
+        unpartitioned.consumption_type(0),
+        ConsumptionType::Redeliver
+    );
+    assert_eq!(unpartitioned.consumption_type(1), ConsumptionType::New);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_partitioned_transaction_worker() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+
+    let pool = setup_pg_pool(std::env::var("DATABASE_TEST_URL").unwrap()).await;
+    let source = MemorySource::new(16);
+    let (bg_task_sender, _bg_task_receiver) = unbounded_channel();
+    let (ack_task, ack_sender) = ack_worker(Arc::new(source.clone()));
+    let worker = transaction_worker(
+        pool,
+        Arc::new(source.clone()),
+        bg_task_sender,
+        ack_sender,
+        ConsumptionType::New,
+        "TXN",
+        None,
+        Some(2),
+        None,
+        CancellationToken::new(),
+    );
+
+    let mut ids = vec![];
+    for signature in MINT_DELEGATE_TRANSFER {
+        let data = cached_transaction("mint_delegate_transfer", signature);
+        ids.push(source.send("TXN", data).await);
+    }
+
+    timeout(Duration::from_secs(10), async {
+        while !ids.iter().all(|id| source.acked("TXN").contains(id)) {
+            sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("transactions were not acked");
+    worker.abort();
+    ack_task.abort();
+
+    let asset_id = Pubkey::from_str("77wWrvhgEkkQZQVA2hoka1JTsjG3w7BVzvcmqxDrVPWE").unwrap();
+    assert!(asset::Entity::find_by_id(asset_id.to_bytes().to_vec())
+        .one(setup.db.as_ref())
+        .await
+        .unwrap()
+        .is_some());
+}
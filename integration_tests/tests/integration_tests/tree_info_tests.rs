@@ -0,0 +1,484 @@
+use borsh::BorshDeserialize;
+use function_name::named;
+use std::str::FromStr;
+
+use das_api::api::{self, ApiContract};
+use digital_asset_types::dao::{backfill_items, cl_items, cl_items_history};
+use futures::future::{ready, FutureExt};
+use mpl_bubblegum::instructions::{
+    CreateTreeConfig, CreateTreeConfigInstructionArgs, SetTreeDelegate,
+};
+use program_transformers::{AccountInfo, ProgramTransformer, TransactionInfo};
+use sea_orm::{sea_query::Expr, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use serial_test::serial;
+use solana_sdk::{
+    instruction::{CompiledInstruction, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+};
+use spl_account_compression::state::{
+    merkle_tree_get_size, ConcurrentMerkleTreeHeader, CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
+
+use super::common::*;
+
+const TREE: &str = "67B3xvadpVeBJ6MT5tQqBqntrBeLNjrWbcF6uEAH1EEi";
+const TREE_CREATOR: &str = "C2ch7QUCrYZRkhVVzTXojkdjhdJhaY77i4VQdoPS64HX";
+
+fn create_tree_transaction(tree: Pubkey, tree_creator: Pubkey) -> TransactionInfo {
+    let payer = Pubkey::new_unique();
+    let ix = CreateTreeConfig {
+        tree_config: Pubkey::find_program_address(&[tree.as_ref()], &mpl_bubblegum::ID).0,
+        merkle_tree: tree,
+        payer,
+        tree_creator,
+        log_wrapper: Pubkey::new_unique(),
+        compression_program: spl_account_compression::id(),
+        system_program: solana_sdk::system_program::id(),
+    }
+    .instruction(CreateTreeConfigInstructionArgs {
+        max_depth: 14,
+        max_buffer_size: 64,
+        public: Some(false),
+    });
+
+    bubblegum_transaction(ix, DEFAULT_SLOT)
+}
+
+fn set_tree_delegate_transaction(
+    tree: Pubkey,
+    tree_creator: Pubkey,
+    new_tree_delegate: Pubkey,
+    slot: u64,
+) -> TransactionInfo {
+    let ix = SetTreeDelegate {
+        tree_config: Pubkey::find_program_address(&[tree.as_ref()], &mpl_bubblegum::ID).0,
+        tree_creator,
+        new_tree_delegate,
+        merkle_tree: tree,
+        system_program: solana_sdk::system_program::id(),
+    }
+    .instruction();
+
+    bubblegum_transaction(ix, slot)
+}
+
+fn bubblegum_transaction(ix: Instruction, slot: u64) -> TransactionInfo {
+    let mut account_keys = vec![ix.program_id];
+    account_keys.extend(ix.accounts.iter().map(|meta| meta.pubkey));
+    TransactionInfo {
+        slot,
+        signature: Signature::default(),
+        message_instructions: vec![CompiledInstruction {
+            program_id_index: 0,
+            accounts: (1..account_keys.len() as u8).collect(),
+            data: ix.data,
+        }],
+        account_keys,
+        meta_inner_instructions: vec![],
+    }
+}
+
+async fn history_transformer(seq_window: u64) -> ProgramTransformer {
+    let pool = setup_pg_pool(std::env::var("DATABASE_TEST_URL").unwrap()).await;
+    ProgramTransformer::new(pool, Box::new(|_info| ready(Ok(())).boxed()))
+        .with_cl_items_history(Some(seq_window))
+}
+
+fn tree_account(tree: Pubkey) -> AccountInfo {
+    let mut data = vec![1, 0];
+    data.extend_from_slice(&64u32.to_le_bytes());
+    data.extend_from_slice(&14u32.to_le_bytes());
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(&100u64.to_le_bytes());
+    data.resize(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1, 0);
+
+    let header = ConcurrentMerkleTreeHeader::try_from_slice(&data).unwrap();
+    let tree_size = merkle_tree_get_size(&header).unwrap();
+    // Four leaves appended, the count is the index of the rightmost proof at the end of the tree.
+    let index_offset = data.len() + tree_size - 8;
+    // Tree followed by a canopy of depth 2.
+    data.resize(data.len() + tree_size + 6 * 32, 0);
+    data[index_offset..index_offset + 4].copy_from_slice(&4u32.to_le_bytes());
+
+    AccountInfo {
+        slot: DEFAULT_SLOT,
+        pubkey: tree,
+        owner: spl_account_compression::id(),
+        data,
+    }
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_tree_info_from_create_tree_mint_and_account_update() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    let tree = Pubkey::from_str(TREE).unwrap();
+    let tree_creator = Pubkey::from_str(TREE_CREATOR).unwrap();
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    // Mints leaf 3 of the tree.
+    let seed = seed_txn(
+        "4ASu45ELoTmvwhNqokGQrh2VH8p5zeUepYLbkcULMeXSCZJGrJa7ojgdVh5JUxBjAMF9Lrp55EgUUFPaPeWKejNQ",
+    );
+    index_seed_events(&setup, vec![&seed]).await;
+    setup
+        .transformer
+        .handle_transaction(&create_tree_transaction(tree, tree_creator))
+        .await
+        .unwrap();
+    setup
+        .transformer
+        .handle_account_update(&tree_account(tree))
+        .await
+        .unwrap();
+
+    let request = api::GetTreeInfo {
+        tree: TREE.to_string(),
+    };
+    let response = setup.das_api.get_tree_info(request).await.unwrap();
+    assert_eq!(response.max_depth, Some(14));
+    assert_eq!(response.max_buffer_size, Some(64));
+    assert_eq!(response.canopy_depth, Some(2));
+    assert_eq!(response.creation_slot, Some(100));
+    assert_eq!(response.tree_creator.as_deref(), Some(TREE_CREATOR));
+    assert_eq!(response.tree_delegate.as_deref(), Some(TREE_CREATOR));
+    assert_eq!(response.is_public, Some(false));
+    assert_eq!(response.num_minted, 4);
+    insta::assert_json_snapshot!(name.clone(), response);
+
+    let request = api::GetTreesByAuthority {
+        authority: TREE_CREATOR.to_string(),
+        limit: None,
+        page: Some(1),
+        before: None,
+        after: None,
+        cursor: None,
+    };
+    let response = setup.das_api.get_trees_by_authority(request).await.unwrap();
+    assert_eq!(response.total, 1);
+    assert_eq!(response.trees[0].tree, TREE);
+
+    let request = api::GetTreesByAuthority {
+        authority: Pubkey::new_unique().to_string(),
+        limit: None,
+        page: Some(1),
+        before: None,
+        after: None,
+        cursor: None,
+    };
+    let response = setup.das_api.get_trees_by_authority(request).await.unwrap();
+    assert_eq!(response.total, 0);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_tree_delegate_from_set_tree_delegate() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    let tree = Pubkey::from_str(TREE).unwrap();
+    let tree_creator = Pubkey::from_str(TREE_CREATOR).unwrap();
+    let new_tree_delegate = Pubkey::new_unique();
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    setup
+        .transformer
+        .handle_transaction(&create_tree_transaction(tree, tree_creator))
+        .await
+        .unwrap();
+    setup
+        .transformer
+        .handle_transaction(&set_tree_delegate_transaction(
+            tree,
+            tree_creator,
+            new_tree_delegate,
+            DEFAULT_SLOT + 2,
+        ))
+        .await
+        .unwrap();
+    // An older delegate change and a replayed `CreateTree` must not revert the delegate.
+    setup
+        .transformer
+        .handle_transaction(&set_tree_delegate_transaction(
+            tree,
+            tree_creator,
+            Pubkey::new_unique(),
+            DEFAULT_SLOT + 1,
+        ))
+        .await
+        .unwrap();
+    setup
+        .transformer
+        .handle_transaction(&create_tree_transaction(tree, tree_creator))
+        .await
+        .unwrap();
+
+    let request = api::GetTreeInfo {
+        tree: TREE.to_string(),
+    };
+    let response = setup.das_api.get_tree_info(request).await.unwrap();
+    assert_eq!(response.tree_creator.as_deref(), Some(TREE_CREATOR));
+    assert_eq!(response.tree_delegate, Some(new_tree_delegate.to_string()));
+
+    let request = api::GetTreesByAuthority {
+        authority: new_tree_delegate.to_string(),
+        limit: None,
+        page: Some(1),
+        before: None,
+        after: None,
+        cursor: None,
+    };
+    let response = setup.das_api.get_trees_by_authority(request).await.unwrap();
+    assert_eq!(response.total, 1);
+    assert_eq!(response.trees[0].tree, TREE);
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_proof_canopy_truncation() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new(name.clone()).await;
+    let asset_id = "DFRJ4PwAze1mMQccRmdyc46yQpEVd4FPiwtAVgzGCs7g";
+    let proof_request = |truncate_canopy, canopy_depth| api::GetAssetProof {
+        id: asset_id.to_string(),
+        truncate_canopy,
+        canopy_depth,
+        at_seq: None,
+    };
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    let seed = seed_txn(
+        "4ASu45ELoTmvwhNqokGQrh2VH8p5zeUepYLbkcULMeXSCZJGrJa7ojgdVh5JUxBjAMF9Lrp55EgUUFPaPeWKejNQ",
+    );
+    index_seed_events(&setup, vec![&seed]).await;
+
+    let full = setup
+        .das_api
+        .get_asset_proof(proof_request(None, None))
+        .await
+        .unwrap();
+
+    let truncated = setup
+        .das_api
+        .get_asset_proof(proof_request(None, Some(3)))
+        .await
+        .unwrap();
+    assert_eq!(truncated.root, full.root);
+    assert_eq!(truncated.proof, full.proof[..full.proof.len() - 3]);
+
+    // A canopy covering the whole tree is valid and leaves an empty proof.
+    let truncated = setup
+        .das_api
+        .get_asset_proof(proof_request(None, Some(full.proof.len() as u32)))
+        .await
+        .unwrap();
+    assert_eq!(truncated.root, full.root);
+    assert!(truncated.proof.is_empty());
+
+    // Without indexed tree metadata the full proof is returned.
+    let response = setup
+        .das_api
+        .get_asset_proof(proof_request(Some(true), None))
+        .await
+        .unwrap();
+    assert_eq!(response.proof, full.proof);
+
+    setup
+        .transformer
+        .handle_account_update(&tree_account(Pubkey::from_str(TREE).unwrap()))
+        .await
+        .unwrap();
+    let response = setup
+        .das_api
+        .get_asset_proof(proof_request(Some(true), None))
+        .await
+        .unwrap();
+    assert_eq!(response.proof, full.proof[..full.proof.len() - 2]);
+
+    let proofs = setup
+        .das_api
+        .get_asset_proofs(api::GetAssetProofs {
+            ids: vec![asset_id.to_string()],
+            truncate_canopy: Some(true),
+            canopy_depth: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(proofs[asset_id].as_ref().unwrap().proof, response.proof);
+
+    assert!(setup
+        .das_api
+        .get_asset_proof(proof_request(Some(true), Some(2)))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_proof_verification() {
+    let name = trim_test_name(function_name!());
+    let setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+    let asset_id = "FLFoCw2RBbxiw9rbEeqPWJ5rasArD9kTCKWEJirTexsU";
+    let proof_request = || api::GetAssetProof {
+        id: asset_id.to_string(),
+        truncate_canopy: None,
+        canopy_depth: None,
+        at_seq: None,
+    };
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    let seed = seed_txn(
+        "2DP84v6Pi3e4v5i7KSvzmK4Ufbzof3TAiEqDbm9gg8jZpBRF9f1Cy6x54kvZoHPX9k1XfqbsG1FTv2KVP9fvNrN6",
+    );
+    index_seed_events(&setup, vec![&seed]).await;
+
+    let proof = setup
+        .das_api
+        .get_asset_proof(proof_request())
+        .await
+        .unwrap();
+    assert!(proof.verified);
+    let queued = backfill_items::Entity::find()
+        .filter(backfill_items::Column::ForceChk.eq(true))
+        .count(setup.db.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(queued, 0);
+
+    // Simulate a missed sequence by leaving the root behind the rest of the path.
+    cl_items::Entity::update_many()
+        .col_expr(cl_items::Column::Hash, Expr::value(vec![0u8; 32]))
+        .filter(cl_items::Column::NodeIdx.eq(1))
+        .exec(setup.db.as_ref())
+        .await
+        .unwrap();
+
+    let proof = setup
+        .das_api
+        .get_asset_proof(proof_request())
+        .await
+        .unwrap();
+    assert!(!proof.verified);
+
+    let proofs = setup
+        .das_api
+        .get_asset_proofs(api::GetAssetProofs {
+            ids: vec![asset_id.to_string()],
+            truncate_canopy: None,
+            canopy_depth: None,
+        })
+        .await
+        .unwrap();
+    assert!(!proofs[asset_id].as_ref().unwrap().verified);
+
+    let queued = backfill_items::Entity::find()
+        .filter(backfill_items::Column::ForceChk.eq(true))
+        .all(setup.db.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(queued.len(), 1);
+    assert_eq!(
+        Pubkey::try_from(queued[0].tree.as_slice())
+            .unwrap()
+            .to_string(),
+        proof.tree_id
+    );
+}
+
+#[tokio::test]
+#[serial]
+#[named]
+async fn test_asset_proof_at_seq() {
+    let name = trim_test_name(function_name!());
+    let mut setup = TestSetup::new_with_options(
+        name.clone(),
+        TestSetupOptions {
+            network: Some(Network::Devnet),
+        },
+    )
+    .await;
+    let asset_id = "FLFoCw2RBbxiw9rbEeqPWJ5rasArD9kTCKWEJirTexsU";
+    let proof_request = |at_seq| api::GetAssetProof {
+        id: asset_id.to_string(),
+        truncate_canopy: None,
+        canopy_depth: None,
+        at_seq,
+    };
+
+    apply_migrations_and_delete_data(setup.db.clone()).await;
+    let seeds = seed_txns([
+        // mint cNFT
+        "2DP84v6Pi3e4v5i7KSvzmK4Ufbzof3TAiEqDbm9gg8jZpBRF9f1Cy6x54kvZoHPX9k1XfqbsG1FTv2KVP9fvNrN6",
+        // update metadata
+        "3bsL5zmLKvhN9Je4snTKxjFSpmXEEg2cvMHm2rCNgaEYkNXBqJTA4N7QmvBSWPiNUQPtzJSYzpQYX92NowV3L7vN",
+    ]);
+    // The history is only saved when enabled.
+    index_seed_events(&setup, seeds.iter().collect()).await;
+    assert_eq!(
+        cl_items_history::Entity::find()
+            .count(setup.db.as_ref())
+            .await
+            .unwrap(),
+        0
+    );
+    setup.transformer = history_transformer(10).await;
+    index_seed_events(&setup, seeds.iter().collect()).await;
+
+    let latest = setup
+        .das_api
+        .get_asset_proof(proof_request(None))
+        .await
+        .unwrap();
+    let at_latest = setup
+        .das_api
+        .get_asset_proof(proof_request(Some(2)))
+        .await
+        .unwrap();
+    assert_eq!(at_latest, latest);
+
+    let at_mint = setup
+        .das_api
+        .get_asset_proof(proof_request(Some(1)))
+        .await
+        .unwrap();
+    assert!(at_mint.verified);
+    assert_eq!(at_mint.node_index, latest.node_index);
+    assert_ne!(at_mint.root, latest.root);
+    assert_ne!(at_mint.leaf, latest.leaf);
+
+    // Sequences past the indexed changelog cannot be proven.
+    assert!(setup
+        .das_api
+        .get_asset_proof(proof_request(Some(3)))
+        .await
+        .is_err());
+
+    // Once the mint falls out of the kept window its nodes are pruned, so its proof can no longer
+    // be rebuilt.
+    setup.transformer = history_transformer(0).await;
+    index_seed_events(&setup, seeds.iter().collect()).await;
+    assert_eq!(
+        setup
+            .das_api
+            .get_asset_proof(proof_request(Some(2)))
+            .await
+            .unwrap(),
+        latest
+    );
+    assert!(setup
+        .das_api
+        .get_asset_proof(proof_request(Some(1)))
+        .await
+        .is_err());
+}
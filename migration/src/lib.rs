@@ -50,6 +50,21 @@ mod m20250313_105132_drop_idx_token_accounts_owner_and_idx_ta_mint;
 mod m20250313_105206_add_idx_ta_owner_amount_and_idx_ta_mint_amount;
 mod m20250321_120101_add_bgum_leaf_schema_v2_items;
 mod m20250327_120101_add_bubblegum_v2_ixs_to_enum;
+mod m20250401_120101_add_asset_change_notify_triggers;
+mod m20250402_120101_add_asset_data_attributes_idx;
+mod m20250403_120101_add_asset_data_name_trgm_idx;
+mod m20250404_120101_add_merkle_tree_table;
+mod m20250404_130101_add_backfill_items_pending_force_chk_idx;
+mod m20250405_120101_add_cl_items_history_table;
+mod m20250406_120101_add_asset_signatures_table;
+mod m20250407_120101_add_signature_slot_and_created_at;
+mod m20250408_120101_add_asset_leaf_history_table;
+mod m20250409_120101_add_bubblegum_asset_data;
+mod m20250410_120101_add_authority_record_attachments;
+mod m20250411_120101_add_token_records_table;
+mod m20250412_120101_add_token_groups_table;
+mod m20250413_120101_add_tokens_extension_columns;
+mod m20250414_120101_add_failed_messages_table;
 
 pub mod model;
 
@@ -109,6 +124,21 @@ impl MigratorTrait for Migrator {
             Box::new(m20250313_105206_add_idx_ta_owner_amount_and_idx_ta_mint_amount::Migration),
             Box::new(m20250321_120101_add_bgum_leaf_schema_v2_items::Migration),
             Box::new(m20250327_120101_add_bubblegum_v2_ixs_to_enum::Migration),
+            Box::new(m20250401_120101_add_asset_change_notify_triggers::Migration),
+            Box::new(m20250402_120101_add_asset_data_attributes_idx::Migration),
+            Box::new(m20250403_120101_add_asset_data_name_trgm_idx::Migration),
+            Box::new(m20250404_120101_add_merkle_tree_table::Migration),
+            Box::new(m20250404_130101_add_backfill_items_pending_force_chk_idx::Migration),
+            Box::new(m20250405_120101_add_cl_items_history_table::Migration),
+            Box::new(m20250406_120101_add_asset_signatures_table::Migration),
+            Box::new(m20250407_120101_add_signature_slot_and_created_at::Migration),
+            Box::new(m20250408_120101_add_asset_leaf_history_table::Migration),
+            Box::new(m20250409_120101_add_bubblegum_asset_data::Migration),
+            Box::new(m20250410_120101_add_authority_record_attachments::Migration),
+            Box::new(m20250411_120101_add_token_records_table::Migration),
+            Box::new(m20250412_120101_add_token_groups_table::Migration),
+            Box::new(m20250413_120101_add_tokens_extension_columns::Migration),
+            Box::new(m20250414_120101_add_failed_messages_table::Migration),
         ]
     }
 }
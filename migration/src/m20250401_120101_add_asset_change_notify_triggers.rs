@@ -0,0 +1,131 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        // Publishes `{ id, slot, previous_owner }` on the `asset_changes` channel so API
+        // subscribers are notified without polling. The payload is kept small on purpose,
+        // listeners load the full asset themselves.
+        //
+        // The triggers are opt-in, they only fire for sessions where `das.asset_change_notify`
+        // is `on`, e.g. after `ALTER ROLE <ingester role> SET das.asset_change_notify = 'on'`.
+        // Otherwise the `WHEN` condition skips them before the function is even called.
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "
+            CREATE OR REPLACE FUNCTION notify_asset_change() RETURNS trigger AS $$
+            DECLARE
+                previous_owner bytea;
+            BEGIN
+                IF TG_OP = 'UPDATE' THEN
+                    previous_owner := OLD.owner;
+                END IF;
+                PERFORM pg_notify(
+                    'asset_changes',
+                    json_build_object(
+                        'id', encode(NEW.id, 'hex'),
+                        'slot', GREATEST(
+                            NEW.slot_updated,
+                            NEW.slot_updated_metadata_account,
+                            NEW.slot_updated_mint_account,
+                            NEW.slot_updated_token_account,
+                            NEW.slot_updated_cnft_transaction
+                        ),
+                        'previous_owner', encode(previous_owner, 'hex')
+                    )::text
+                );
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql;
+            "
+            .to_string(),
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "
+            CREATE OR REPLACE FUNCTION notify_asset_grouping_change() RETURNS trigger AS $$
+            BEGIN
+                PERFORM pg_notify(
+                    'asset_changes',
+                    json_build_object(
+                        'id', encode(NEW.asset_id, 'hex'),
+                        'slot', NEW.slot_updated
+                    )::text
+                );
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql;
+            "
+            .to_string(),
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "
+            CREATE TRIGGER asset_change_notify
+                AFTER INSERT OR UPDATE ON asset
+                FOR EACH ROW
+                WHEN (current_setting('das.asset_change_notify', true) = 'on')
+                EXECUTE FUNCTION notify_asset_change();
+            "
+            .to_string(),
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "
+            CREATE TRIGGER asset_grouping_change_notify
+                AFTER INSERT OR UPDATE ON asset_grouping
+                FOR EACH ROW
+                WHEN (current_setting('das.asset_change_notify', true) = 'on')
+                EXECUTE FUNCTION notify_asset_grouping_change();
+            "
+            .to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "DROP TRIGGER IF EXISTS asset_grouping_change_notify ON asset_grouping;".to_string(),
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "DROP TRIGGER IF EXISTS asset_change_notify ON asset;".to_string(),
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "DROP FUNCTION IF EXISTS notify_asset_grouping_change();".to_string(),
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "DROP FUNCTION IF EXISTS notify_asset_change();".to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
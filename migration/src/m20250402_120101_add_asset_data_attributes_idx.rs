@@ -0,0 +1,34 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        // Backs the `attributes` filter of `searchAssets`, which queries with `@>` containment.
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "CREATE INDEX CONCURRENTLY IF NOT EXISTS asset_data_attributes_idx ON asset_data USING GIN ((metadata -> 'attributes') jsonb_path_ops);"
+                .to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "DROP INDEX CONCURRENTLY IF EXISTS asset_data_attributes_idx;".to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
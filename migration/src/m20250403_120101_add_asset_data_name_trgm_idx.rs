@@ -0,0 +1,62 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        // Kept out of `public` so resetting the schema, which drops every type in it, does not
+        // trip over the extension's types. Databases that already have pg_trgm keep it where it
+        // is, so its schema is looked up rather than assumed, the same way the API qualifies its
+        // functions and operators.
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "CREATE SCHEMA IF NOT EXISTS extensions;".to_string(),
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "CREATE EXTENSION IF NOT EXISTS pg_trgm WITH SCHEMA extensions;".to_string(),
+        ))
+        .await?;
+
+        let schema: String = conn
+            .query_one(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "SELECT quote_ident(n.nspname) AS schema FROM pg_extension e JOIN pg_namespace n ON n.oid = e.extnamespace WHERE e.extname = 'pg_trgm';"
+                    .to_string(),
+            ))
+            .await?
+            .ok_or_else(|| DbErr::Migration("pg_trgm is not installed".to_string()))?
+            .try_get("", "schema")?;
+
+        // Backs the exact, prefix, contains and fuzzy `name` searches of `searchAssets`.
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            format!(
+                "CREATE INDEX CONCURRENTLY IF NOT EXISTS asset_data_name_trgm_idx ON asset_data USING GIN ((chain_data ->> 'name') {}.gin_trgm_ops);",
+                schema
+            ),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "DROP INDEX CONCURRENTLY IF EXISTS asset_data_name_trgm_idx;".to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use enum_iterator::all;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+use crate::model::r#enum::TreeVersion;
+use crate::model::table::MerkleTree;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("tree_version"))
+                    .values(all::<TreeVersion>().collect::<Vec<_>>())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Rows are filled from both `CreateTree` instructions and tree account updates, which can
+        // arrive in any order, so everything except the key is nullable.
+        manager
+            .create_table(
+                Table::create()
+                    .table(MerkleTree::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MerkleTree::Id)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MerkleTree::MaxDepth).integer())
+                    .col(ColumnDef::new(MerkleTree::MaxBufferSize).integer())
+                    .col(ColumnDef::new(MerkleTree::CanopyDepth).integer())
+                    .col(ColumnDef::new(MerkleTree::CreationSlot).big_integer())
+                    .col(ColumnDef::new(MerkleTree::TreeCreator).binary())
+                    .col(ColumnDef::new(MerkleTree::TreeDelegate).binary())
+                    .col(ColumnDef::new(MerkleTree::TreeDelegateSlotUpdated).big_integer())
+                    .col(ColumnDef::new(MerkleTree::IsPublic).boolean())
+                    .col(ColumnDef::new(MerkleTree::Version).enumeration(
+                        Alias::new("tree_version"),
+                        all::<TreeVersion>().collect::<Vec<_>>(),
+                    ))
+                    .col(
+                        ColumnDef::new(MerkleTree::NumMinted)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(MerkleTree::SlotUpdated)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("merkle_tree_tree_creator_idx")
+                    .table(MerkleTree::Table)
+                    .col(MerkleTree::TreeCreator)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("merkle_tree_tree_delegate_idx")
+                    .table(MerkleTree::Table)
+                    .col(MerkleTree::TreeDelegate)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MerkleTree::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .if_exists()
+                    .name(Alias::new("tree_version"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        // Keep a single pending forced check per tree before the unique index goes on. The
+        // duplicates lose their `force_chk` flag and stay behind as regular backfill items, the
+        // kept one still checks the tree from sequence 1.
+        let demoted = conn
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "UPDATE backfill_items SET force_chk = false
+                WHERE force_chk AND NOT failed AND NOT backfilled
+                AND id NOT IN (
+                    SELECT MIN(id) FROM backfill_items
+                    WHERE force_chk AND NOT failed AND NOT backfilled
+                    GROUP BY tree
+                );"
+                .to_string(),
+            ))
+            .await?
+            .rows_affected();
+        if demoted > 0 {
+            println!(
+                "Cleared force_chk on {} duplicate pending backfill_items rows",
+                demoted
+            );
+        }
+
+        // Lets stale proofs queue a forced check with `ON CONFLICT DO NOTHING`, so concurrent
+        // requests for the same tree queue it once.
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "CREATE UNIQUE INDEX IF NOT EXISTS backfill_items_pending_force_chk_idx ON backfill_items (tree) WHERE force_chk AND NOT failed AND NOT backfilled;"
+                .to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            "DROP INDEX IF EXISTS backfill_items_pending_force_chk_idx;".to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
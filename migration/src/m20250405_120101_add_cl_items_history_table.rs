@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::ClItemsHistory;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `cl_items` only keeps the latest version of each node, every version is kept here so
+        // proofs can be rebuilt as of an older sequence number.
+        manager
+            .create_table(
+                Table::create()
+                    .table(ClItemsHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ClItemsHistory::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ClItemsHistory::Tree).binary().not_null())
+                    .col(
+                        ColumnDef::new(ClItemsHistory::NodeIdx)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ClItemsHistory::LeafIdx).big_integer())
+                    .col(ColumnDef::new(ClItemsHistory::Seq).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(ClItemsHistory::Level)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ClItemsHistory::Hash).binary().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .unique()
+                    .name("cl_items_history_tree_node_idx_seq_idx")
+                    .table(ClItemsHistory::Table)
+                    .col(ClItemsHistory::Tree)
+                    .col(ClItemsHistory::NodeIdx)
+                    .col(ClItemsHistory::Seq)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ClItemsHistory::Table).to_owned())
+            .await
+    }
+}
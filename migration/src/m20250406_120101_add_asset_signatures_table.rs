@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::AssetSignatures;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Compressed assets keep their history in `cl_audits_v2`, this covers every other asset.
+        manager
+            .create_table(
+                Table::create()
+                    .table(AssetSignatures::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AssetSignatures::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AssetSignatures::AssetId).binary().not_null())
+                    .col(
+                        ColumnDef::new(AssetSignatures::Signature)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AssetSignatures::Instruction).string())
+                    .col(
+                        ColumnDef::new(AssetSignatures::Slot)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .unique()
+                    .name("asset_signatures_asset_id_signature_idx")
+                    .table(AssetSignatures::Table)
+                    .col(AssetSignatures::AssetId)
+                    .col(AssetSignatures::Signature)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("asset_signatures_asset_id_slot_idx")
+                    .table(AssetSignatures::Table)
+                    .col(AssetSignatures::AssetId)
+                    .col(AssetSignatures::Slot)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AssetSignatures::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::{AssetSignatures, ClAuditsV2};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Rows written before this migration have no slot.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClAuditsV2::Table)
+                    .add_column(ColumnDef::new(ClAuditsV2::Slot).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AssetSignatures::Table)
+                    .add_column(
+                        ColumnDef::new(AssetSignatures::CreatedAt)
+                            .date_time()
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp))
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ClAuditsV2::Table)
+                    .drop_column(ClAuditsV2::Slot)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AssetSignatures::Table)
+                    .drop_column(AssetSignatures::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
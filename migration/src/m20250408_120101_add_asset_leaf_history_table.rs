@@ -0,0 +1,83 @@
+use enum_iterator::all;
+use sea_orm_migration::prelude::*;
+
+use crate::model::r#enum::BubblegumInstruction;
+use crate::model::table::{AssetLeafHistory, ClAuditsV2};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Append-only record of every leaf schema seen for a compressed asset, `asset` only keeps
+        // the latest one.
+        manager
+            .create_table(
+                Table::create()
+                    .table(AssetLeafHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AssetLeafHistory::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AssetLeafHistory::AssetId)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AssetLeafHistory::Tree).binary().not_null())
+                    .col(
+                        ColumnDef::new(AssetLeafHistory::LeafIdx)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AssetLeafHistory::Seq)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AssetLeafHistory::Slot)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AssetLeafHistory::Owner).binary().not_null())
+                    .col(ColumnDef::new(AssetLeafHistory::Delegate).binary())
+                    .col(ColumnDef::new(AssetLeafHistory::Leaf).binary().not_null())
+                    .col(
+                        ColumnDef::new(AssetLeafHistory::Instruction)
+                            .enumeration(
+                                ClAuditsV2::Instruction,
+                                all::<BubblegumInstruction>().collect::<Vec<_>>(),
+                            )
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .unique()
+                    .name("asset_leaf_history_asset_id_seq_idx")
+                    .table(AssetLeafHistory::Table)
+                    .col(AssetLeafHistory::AssetId)
+                    .col(AssetLeafHistory::Seq)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AssetLeafHistory::Table).to_owned())
+            .await
+    }
+}
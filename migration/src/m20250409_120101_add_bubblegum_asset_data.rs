@@ -0,0 +1,77 @@
+use enum_iterator::all;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+use crate::model::r#enum::AssetDataSchema;
+use crate::model::table::AssetData;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("asset_data_schema"))
+                    .values(all::<AssetDataSchema>().collect::<Vec<_>>())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Asset data attached to Bubblegum V2 leaves by `UpdateAssetDataV2`, with the sequence
+        // number of the change that last wrote it.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AssetData::Table)
+                    .add_column(
+                        ColumnDef::new(AssetData::BubblegumAssetData)
+                            .binary()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(AssetData::BubblegumAssetDataSchema)
+                            .enumeration(
+                                Alias::new("asset_data_schema"),
+                                all::<AssetDataSchema>().collect::<Vec<_>>(),
+                            )
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(AssetData::BubblegumAssetDataSeq)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AssetData::Table)
+                    .drop_column(AssetData::BubblegumAssetData)
+                    .drop_column(AssetData::BubblegumAssetDataSchema)
+                    .drop_column(AssetData::BubblegumAssetDataSeq)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .if_exists()
+                    .name(Alias::new("asset_data_schema"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
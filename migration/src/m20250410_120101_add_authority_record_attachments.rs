@@ -0,0 +1,70 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::AssetV1AccountAttachments;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for value in ["use_authority_record", "collection_authority_record"] {
+            manager
+                .get_connection()
+                .execute(Statement::from_string(
+                    DatabaseBackend::Postgres,
+                    format!("ALTER TYPE v1_account_attachments ADD VALUE IF NOT EXISTS '{value}';"),
+                ))
+                .await?;
+        }
+
+        // Authority delegated by a use or collection authority record. The record accounts do not
+        // store it, so it is taken from the instruction that approved the record.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AssetV1AccountAttachments::Table)
+                    .add_column(
+                        ColumnDef::new(AssetV1AccountAttachments::Authority)
+                            .binary()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "CREATE INDEX CONCURRENTLY IF NOT EXISTS asset_v1_account_attachments_asset_id_idx ON asset_v1_account_attachments (asset_id);"
+                    .to_string(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("asset_v1_account_attachments_asset_id_idx")
+                    .table(AssetV1AccountAttachments::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AssetV1AccountAttachments::Table)
+                    .drop_column(AssetV1AccountAttachments::Authority)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use enum_iterator::all;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+use crate::model::r#enum::{TokenDelegateRole, TokenState};
+use crate::model::table::{Asset, TokenRecords};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "ALTER TYPE v1_account_attachments ADD VALUE IF NOT EXISTS 'metadata_delegate_record';"
+                    .to_string(),
+            ))
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("token_state"))
+                    .values(all::<TokenState>().collect::<Vec<_>>())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("token_delegate_role"))
+                    .values(all::<TokenDelegateRole>().collect::<Vec<_>>())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Programmable NFT token records. The accounts do not store the mint and token account
+        // they belong to, those are filled in from the token account that derives the record.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TokenRecords::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TokenRecords::Pubkey)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TokenRecords::Mint).binary())
+                    .col(ColumnDef::new(TokenRecords::TokenAccount).binary())
+                    .col(ColumnDef::new(TokenRecords::State).enumeration(
+                        Alias::new("token_state"),
+                        all::<TokenState>().collect::<Vec<_>>(),
+                    ))
+                    .col(ColumnDef::new(TokenRecords::Delegate).binary())
+                    .col(ColumnDef::new(TokenRecords::DelegateRole).enumeration(
+                        Alias::new("token_delegate_role"),
+                        all::<TokenDelegateRole>().collect::<Vec<_>>(),
+                    ))
+                    .col(ColumnDef::new(TokenRecords::LockedTransfer).binary())
+                    .col(ColumnDef::new(TokenRecords::RuleSetRevision).big_integer())
+                    .col(
+                        ColumnDef::new(TokenRecords::SlotUpdated)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Lock state and delegate role of the token record of the current holder.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Asset::Table)
+                    .add_column(
+                        ColumnDef::new(Asset::TokenState)
+                            .enumeration(
+                                Alias::new("token_state"),
+                                all::<TokenState>().collect::<Vec<_>>(),
+                            )
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(Asset::TokenDelegateRole)
+                            .enumeration(
+                                Alias::new("token_delegate_role"),
+                                all::<TokenDelegateRole>().collect::<Vec<_>>(),
+                            )
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Asset::Table)
+                    .drop_column(Asset::TokenState)
+                    .drop_column(Asset::TokenDelegateRole)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TokenRecords::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .if_exists()
+                    .name(Alias::new("token_delegate_role"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .if_exists()
+                    .name(Alias::new("token_state"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
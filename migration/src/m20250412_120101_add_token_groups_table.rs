@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::TokenGroups;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Token-2022 groups, keyed by the group address. Members are stored in `asset_grouping`.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TokenGroups::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TokenGroups::Pubkey)
+                            .binary()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TokenGroups::Mint).binary().not_null())
+                    .col(ColumnDef::new(TokenGroups::UpdateAuthority).binary())
+                    .col(ColumnDef::new(TokenGroups::Size).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(TokenGroups::MaxSize)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TokenGroups::SlotUpdated)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TokenGroups::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use sea_orm_migration::prelude::*;
+
+use crate::model::table::Tokens;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Token-2022 extensions that wallets filter on, promoted out of `tokens.extensions`. Mints
+        // indexed before this migration fill them when they are reindexed, see the README.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tokens::Table)
+                    .add_column(ColumnDef::new(Tokens::TransferHookProgram).binary().null())
+                    .add_column(ColumnDef::new(Tokens::PermanentDelegate).binary().null())
+                    .add_column(
+                        ColumnDef::new(Tokens::NonTransferable)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column(
+                        ColumnDef::new(Tokens::OlderTransferFeeBasisPoints)
+                            .integer()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(Tokens::NewerTransferFeeBasisPoints)
+                            .integer()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(Tokens::NewerTransferFeeEpoch)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let conn = manager.get_connection();
+
+        // Only a small share of mints use these extensions, so the indexes are partial.
+        for sql in [
+            "CREATE INDEX CONCURRENTLY IF NOT EXISTS tokens_transfer_hook_program_idx ON tokens (transfer_hook_program) WHERE transfer_hook_program IS NOT NULL;",
+            "CREATE INDEX CONCURRENTLY IF NOT EXISTS tokens_permanent_delegate_idx ON tokens (permanent_delegate) WHERE permanent_delegate IS NOT NULL;",
+            "CREATE INDEX CONCURRENTLY IF NOT EXISTS tokens_non_transferable_idx ON tokens (mint) WHERE non_transferable;",
+            "CREATE INDEX CONCURRENTLY IF NOT EXISTS tokens_transfer_fee_idx ON tokens (newer_transfer_fee_basis_points, older_transfer_fee_basis_points) WHERE newer_transfer_fee_epoch IS NOT NULL;",
+        ] {
+            conn.execute(Statement::from_string(
+                DatabaseBackend::Postgres,
+                sql.to_string(),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for index in [
+            "tokens_transfer_hook_program_idx",
+            "tokens_permanent_delegate_idx",
+            "tokens_non_transferable_idx",
+            "tokens_transfer_fee_idx",
+        ] {
+            manager
+                .drop_index(Index::drop().name(index).table(Tokens::Table).to_owned())
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tokens::Table)
+                    .drop_column(Tokens::TransferHookProgram)
+                    .drop_column(Tokens::PermanentDelegate)
+                    .drop_column(Tokens::NonTransferable)
+                    .drop_column(Tokens::OlderTransferFeeBasisPoints)
+                    .drop_column(Tokens::NewerTransferFeeBasisPoints)
+                    .drop_column(Tokens::NewerTransferFeeEpoch)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
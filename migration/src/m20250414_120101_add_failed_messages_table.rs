@@ -0,0 +1,92 @@
+use enum_iterator::all;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+use crate::model::r#enum::FailedMessageType;
+use crate::model::table::FailedMessages;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(Alias::new("failed_message_type"))
+                    .values(all::<FailedMessageType>().collect::<Vec<_>>())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Stream messages quarantined by the ingester after running out of tries, along with
+        // the plerkle payload so they can be inspected and replayed.
+        manager
+            .create_table(
+                Table::create()
+                    .table(FailedMessages::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FailedMessages::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FailedMessages::Stream).text().not_null())
+                    .col(ColumnDef::new(FailedMessages::MessageId).text().not_null())
+                    .col(
+                        ColumnDef::new(FailedMessages::MessageType)
+                            .enumeration(
+                                Alias::new("failed_message_type"),
+                                all::<FailedMessageType>().collect::<Vec<_>>(),
+                            )
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(FailedMessages::Data).binary().not_null())
+                    .col(ColumnDef::new(FailedMessages::Error).text().not_null())
+                    .col(ColumnDef::new(FailedMessages::Tries).integer().not_null())
+                    .col(ColumnDef::new(FailedMessages::Slot).big_integer())
+                    .col(ColumnDef::new(FailedMessages::Signature).binary())
+                    .col(ColumnDef::new(FailedMessages::Pubkey).binary())
+                    .col(
+                        ColumnDef::new(FailedMessages::CreatedAt)
+                            .date_time()
+                            .default(SimpleExpr::Keyword(Keyword::CurrentTimestamp))
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("failed_messages_stream_idx")
+                    .table(FailedMessages::Table)
+                    .col(FailedMessages::Stream)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FailedMessages::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .if_exists()
+                    .name(Alias::new("failed_message_type"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -29,3 +29,40 @@ pub enum BubblegumInstruction {
     // We need to use an alias instead.
     // UpdateMetadata,
 }
+
+#[derive(Iden, Debug, PartialEq, Sequence)]
+pub enum TreeVersion {
+    V1,
+    V2,
+}
+
+#[derive(Iden, Debug, PartialEq, Sequence)]
+pub enum AssetDataSchema {
+    Binary,
+    Json,
+    MsgPack,
+}
+
+#[derive(Iden, Debug, PartialEq, Sequence)]
+pub enum TokenState {
+    Unlocked,
+    Locked,
+    Listed,
+}
+
+#[derive(Iden, Debug, PartialEq, Sequence)]
+pub enum TokenDelegateRole {
+    Sale,
+    Transfer,
+    Utility,
+    Staking,
+    Standard,
+    LockedTransfer,
+    Migration,
+}
+
+#[derive(Iden, Debug, PartialEq, Sequence)]
+pub enum FailedMessageType {
+    Transaction,
+    Account,
+}
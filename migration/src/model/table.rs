@@ -95,6 +95,8 @@ pub enum Asset {
     AssetDataHash,
     BubblegumFlags,
     NonTransferable,
+    TokenState,
+    TokenDelegateRole,
 }
 
 #[derive(Copy, Clone, Iden)]
@@ -111,6 +113,9 @@ pub enum AssetData {
     RawName,
     RawSymbol,
     BaseInfoSeq,
+    BubblegumAssetData,
+    BubblegumAssetDataSchema,
+    BubblegumAssetDataSeq,
 }
 
 #[derive(Copy, Clone, Iden)]
@@ -154,6 +159,12 @@ pub enum Tokens {
     FreezeAuthority,
     CloseAuthority,
     SlotUpdated,
+    TransferHookProgram,
+    PermanentDelegate,
+    NonTransferable,
+    OlderTransferFeeBasisPoints,
+    NewerTransferFeeBasisPoints,
+    NewerTransferFeeEpoch,
 }
 
 #[derive(Copy, Clone, Iden)]
@@ -180,4 +191,108 @@ pub enum ClAuditsV2 {
     CreatedAt,
     Tx,
     Instruction,
+    Slot,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum MerkleTree {
+    Table,
+    Id,
+    MaxDepth,
+    MaxBufferSize,
+    CanopyDepth,
+    CreationSlot,
+    TreeCreator,
+    TreeDelegate,
+    TreeDelegateSlotUpdated,
+    IsPublic,
+    Version,
+    NumMinted,
+    SlotUpdated,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum ClItemsHistory {
+    Table,
+    Id,
+    Tree,
+    NodeIdx,
+    LeafIdx,
+    Seq,
+    Level,
+    Hash,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum AssetSignatures {
+    Table,
+    Id,
+    AssetId,
+    Signature,
+    Instruction,
+    Slot,
+    CreatedAt,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum AssetLeafHistory {
+    Table,
+    Id,
+    AssetId,
+    Tree,
+    LeafIdx,
+    Seq,
+    Slot,
+    Owner,
+    Delegate,
+    Leaf,
+    Instruction,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum AssetV1AccountAttachments {
+    Table,
+    AssetId,
+    Authority,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum TokenRecords {
+    Table,
+    Pubkey,
+    Mint,
+    TokenAccount,
+    State,
+    Delegate,
+    DelegateRole,
+    LockedTransfer,
+    RuleSetRevision,
+    SlotUpdated,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum TokenGroups {
+    Table,
+    Pubkey,
+    Mint,
+    UpdateAuthority,
+    Size,
+    MaxSize,
+    SlotUpdated,
+}
+
+#[derive(Copy, Clone, Iden)]
+pub enum FailedMessages {
+    Table,
+    Id,
+    Stream,
+    MessageId,
+    MessageType,
+    Data,
+    Error,
+    Tries,
+    Slot,
+    Signature,
+    Pubkey,
+    CreatedAt,
 }
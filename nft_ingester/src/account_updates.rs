@@ -1,16 +1,19 @@
 use {
     crate::{
+        dead_letter::{DeadLetterPolicy, FailedMessage},
         metric,
         metrics::capture_result,
         plerkle::PlerkleAccountInfo,
+        source::MessageSource,
         tasks::{create_download_metadata_notifier, TaskData},
     },
     cadence_macros::{is_global_default_set, statsd_count, statsd_time},
     chrono::Utc,
+    digital_asset_types::dao::sea_orm_active_enums::FailedMessageType,
     log::{debug, error},
-    plerkle_messenger::{ConsumptionType, Messenger, MessengerConfig, RecvData},
+    plerkle_messenger::{ConsumptionType, RecvData},
     plerkle_serialization::root_as_account_info,
-    program_transformers::ProgramTransformer,
+    program_transformers::{AccountInfo, ProgramTransformer},
     sqlx::{Pool, Postgres},
     std::sync::Arc,
     tokio::{
@@ -18,31 +21,46 @@ use {
         task::{JoinHandle, JoinSet},
         time::Instant,
     },
+    tokio_util::sync::CancellationToken,
 };
 
-pub fn account_worker<T: Messenger>(
+#[allow(clippy::too_many_arguments)]
+pub fn account_worker(
     pool: Pool<Postgres>,
-    config: MessengerConfig,
+    source: Arc<dyn MessageSource>,
     bg_task_sender: UnboundedSender<TaskData>,
     ack_channel: UnboundedSender<(&'static str, String)>,
     consumption_type: ConsumptionType,
     stream_key: &'static str,
+    max_tries: Option<usize>,
+    shutdown: CancellationToken,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
-        let source = T::new(config).await;
-        if let Ok(mut msg) = source {
+        let consumer = source.consumer().await;
+        if let Ok(mut msg) = consumer {
+            let dead_letter = DeadLetterPolicy::new(pool.clone(), max_tries);
             let manager = Arc::new(ProgramTransformer::new(
                 pool,
                 create_download_metadata_notifier(bg_task_sender),
             ));
+            // Stop receiving on shutdown, a batch already received is still handled and acked.
             loop {
-                let e = msg.recv(stream_key, consumption_type.clone()).await;
+                let e = tokio::select! {
+                    biased;
+                    _ = shutdown.cancelled() => break,
+                    e = msg.recv(stream_key, consumption_type.clone()) => e,
+                };
                 let mut tasks = JoinSet::new();
                 match e {
                     Ok(data) => {
                         let len = data.len();
                         for item in data {
-                            tasks.spawn(handle_account(Arc::clone(&manager), item, stream_key));
+                            tasks.spawn(handle_account(
+                                Arc::clone(&manager),
+                                dead_letter.clone(),
+                                item,
+                                stream_key,
+                            ));
                         }
                         if len > 0 {
                             debug!("Processed {} accounts", len);
@@ -73,6 +91,7 @@ pub fn account_worker<T: Messenger>(
 
 async fn handle_account(
     manager: Arc<ProgramTransformer>,
+    dead_letter: DeadLetterPolicy,
     item: RecvData,
     stream_key: &'static str,
 ) -> Option<String> {
@@ -84,41 +103,75 @@ async fn handle_account(
             statsd_count!("ingester.account_stream_redelivery", 1);
         }
     }
+    let failed = |error: String, slot: Option<u64>, pubkey: Option<Vec<u8>>| FailedMessage {
+        stream: stream_key,
+        id: &id,
+        message_type: FailedMessageType::Account,
+        data: &data,
+        tries: item.tries,
+        error,
+        slot,
+        signature: None,
+        pubkey,
+    };
     // Get root of account info flatbuffers object.
-    if let Ok(account_update) = root_as_account_info(&data) {
-        let str_program_id =
-            bs58::encode(account_update.owner().unwrap().0.as_slice()).into_string();
-        metric! {
-            statsd_count!("ingester.seen", 1, "owner" => &str_program_id, "stream" => stream_key);
-            let seen_at = Utc::now();
-            statsd_time!(
-                "ingester.bus_ingest_time",
-                (seen_at.timestamp_millis() - account_update.seen_at()) as u64,
-                "owner" => &str_program_id,
-                "stream" => stream_key
-            );
-        }
-        let mut account = None;
-        if let Some(pubkey) = account_update.pubkey() {
-            account = Some(bs58::encode(pubkey.0.as_slice()).into_string());
-        }
-        let begin_processing = Instant::now();
+    match root_as_account_info(&data) {
+        Ok(account_update) => {
+            let str_program_id =
+                bs58::encode(account_update.owner().unwrap().0.as_slice()).into_string();
+            metric! {
+                statsd_count!("ingester.seen", 1, "owner" => &str_program_id, "stream" => stream_key);
+                let seen_at = Utc::now();
+                statsd_time!(
+                    "ingester.bus_ingest_time",
+                    (seen_at.timestamp_millis() - account_update.seen_at()) as u64,
+                    "owner" => &str_program_id,
+                    "stream" => stream_key
+                );
+            }
+            let mut account = None;
+            let pubkey = account_update.pubkey().map(|pubkey| pubkey.0.to_vec());
+            if let Some(pubkey) = &pubkey {
+                account = Some(bs58::encode(pubkey).into_string());
+            }
+            let begin_processing = Instant::now();
 
-        let account_info = PlerkleAccountInfo(account_update).try_into().ok()?;
-        let res = manager.handle_account_update(&account_info).await;
+            let account_info: AccountInfo = match PlerkleAccountInfo(account_update).try_into() {
+                Ok(account_info) => account_info,
+                Err(e) => {
+                    let failed = failed(e.to_string(), Some(account_update.slot()), pubkey);
+                    return dead_letter.quarantine(failed).await.then_some(id.clone());
+                }
+            };
+            let res = manager.handle_account_update(&account_info).await;
+            let error = res.as_ref().err().map(ToString::to_string);
 
-        let should_ack = capture_result(
-            id.clone(),
-            stream_key,
-            ("owner", &str_program_id),
-            item.tries,
-            res,
-            begin_processing,
-            None,
-            account,
-        );
-        if should_ack {
-            ret_id = Some(id);
+            let should_ack = capture_result(
+                id.clone(),
+                stream_key,
+                ("owner", &str_program_id),
+                item.tries,
+                res,
+                begin_processing,
+                None,
+                account,
+            );
+            if should_ack {
+                ret_id = Some(id.clone());
+            } else if let Some(error) = error {
+                let failed = failed(error, Some(account_update.slot()), pubkey);
+                if dead_letter.quarantine(failed).await {
+                    ret_id = Some(id.clone());
+                }
+            }
+        }
+        Err(e) => {
+            if dead_letter
+                .quarantine(failed(e.to_string(), None, None))
+                .await
+            {
+                ret_id = Some(id.clone());
+            }
         }
     }
     ret_id
@@ -1,49 +1,47 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use cadence_macros::{is_global_default_set, statsd_count};
 use log::error;
-use plerkle_messenger::{Messenger, MessengerConfig};
 use tokio::{
     sync::mpsc::{unbounded_channel, UnboundedSender},
     task::JoinHandle,
     time::{interval, Duration},
 };
 
-use crate::metric;
+use crate::{
+    metric,
+    source::{MessageSource, SourceConsumer},
+};
 
-pub fn ack_worker<T: Messenger>(
-    config: MessengerConfig,
+pub fn ack_worker(
+    source: Arc<dyn MessageSource>,
 ) -> (JoinHandle<()>, UnboundedSender<(&'static str, String)>) {
     let (tx, mut rx) = unbounded_channel::<(&'static str, String)>();
     (
         tokio::spawn(async move {
             let mut interval = interval(Duration::from_millis(100));
-            let mut acks: HashMap<&str, Vec<String>> = HashMap::new();
-            let source = T::new(config).await;
-            if let Ok(mut msg) = source {
+            let mut acks: HashMap<&'static str, Vec<String>> = HashMap::new();
+            let consumer = source.consumer().await;
+            if let Ok(mut msg) = consumer {
                 loop {
                     tokio::select! {
                         _ = interval.tick() => {
                             if acks.is_empty() {
                                 continue;
                             }
-                            let len = acks.len();
-                            for (stream, msgs)  in acks.iter_mut() {
-                                if let Err(e) = msg.ack_msg(stream, msgs).await {
-                                    error!("Error acking message: {}", e);
-                                }
-                                metric! {
-                                    statsd_count!("ingester.ack", len as i64, "stream" => stream);
-                                }
-                                msgs.clear();
-                            }
-
-                        }
-                        Some(msg) = rx.recv() => {
-                            let (stream, msg) = msg;
-                            let ackstream = acks.entry(stream).or_default();
-                            ackstream.push(msg);
+                            flush(&mut msg, &mut acks).await;
                         }
+                        ack = rx.recv() => match ack {
+                            Some((stream, id)) => {
+                                let ackstream = acks.entry(stream).or_default();
+                                ackstream.push(id);
+                            }
+                            // Every sender is gone, so nothing is left to ack once the buffer is flushed.
+                            None => {
+                                flush(&mut msg, &mut acks).await;
+                                break;
+                            }
+                        },
                     }
                 }
             }
@@ -51,3 +49,22 @@ pub fn ack_worker<T: Messenger>(
         tx,
     )
 }
+
+async fn flush(
+    consumer: &mut Box<dyn SourceConsumer>,
+    acks: &mut HashMap<&'static str, Vec<String>>,
+) {
+    let len = acks.len();
+    for (stream, msgs) in acks.iter_mut() {
+        if msgs.is_empty() {
+            continue;
+        }
+        if let Err(e) = consumer.ack(stream, msgs).await {
+            error!("Error acking message: {}", e);
+        }
+        metric! {
+            statsd_count!("ingester.ack", len as i64, "stream" => stream);
+        }
+        msgs.clear();
+    }
+}
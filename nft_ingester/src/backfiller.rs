@@ -12,8 +12,10 @@ use plerkle_messenger::{Messenger, TRANSACTION_BACKFILL_STREAM};
 use plerkle_serialization::serializer::seralize_encoded_transaction_with_status;
 
 use sea_orm::{
-    entity::*, query::*, sea_query::Expr, DatabaseConnection, DbBackend, DbErr, FromQueryResult,
-    SqlxPostgresConnector,
+    entity::*,
+    query::*,
+    sea_query::{Expr, OnConflict},
+    DatabaseConnection, DbBackend, DbErr, FromQueryResult, SqlxPostgresConnector,
 };
 use solana_account_decoder::UiAccountEncoding;
 use solana_client::{
@@ -400,7 +402,12 @@ impl<'a, T: Messenger> Backfiller<'a, T> {
             })
             .collect::<Vec<_>>();
 
-        backfill_items::Entity::insert_many(trees).exec(cn).await?;
+        // Trees the API already queued for a forced check hit
+        // `backfill_items_pending_force_chk_idx` and are skipped.
+        let query = backfill_items::Entity::insert_many(trees)
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .build(DbBackend::Postgres);
+        cn.execute(query).await?;
 
         Ok(())
     }
@@ -3,13 +3,17 @@ use figment::{
     value::Value,
     Figment,
 };
-use plerkle_messenger::MessengerConfig;
+use plerkle_messenger::{
+    redis_messenger::DEFAULT_RETRIES, ConsumptionType, MessengerConfig, MessengerType,
+};
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use serde::Deserialize;
+use solana_sdk::commitment_config::CommitmentLevel;
 use std::{
     env,
     fmt::{Display, Formatter},
     path::PathBuf,
+    time::Duration,
 };
 use tracing_subscriber::fmt;
 
@@ -31,6 +35,46 @@ pub struct IngesterConfig {
     pub code_version: Option<&'static str>,
     pub background_task_runner_config: Option<BackgroundTaskRunnerConfig>,
     pub cl_audits: Option<bool>, // save transaction logs for compressed nfts
+    /// Saves the versions of the tree nodes to `cl_items_history` so `getAssetProof` can serve
+    /// `atSeq`. Off by default.
+    pub cl_items_history: Option<bool>,
+    /// Number of trailing sequence numbers of each tree `cl_items_history` keeps the nodes to
+    /// prove. Defaults to `DEFAULT_CL_ITEMS_HISTORY_SEQ_WINDOW`.
+    pub cl_items_history_seq_window: Option<u64>,
+    pub source_config: Option<SourceConfig>,
+    /// Deliveries after which a failing stream message is moved to `failed_messages`.
+    /// The Redis messenger acks and drops messages delivered more than its `retries` times, so
+    /// this must not exceed them. Failing messages are left to the source when unset.
+    pub max_message_tries: Option<usize>,
+    /// Seconds the stream workers get on shutdown to finish the messages they already
+    /// received. Defaults to `DEFAULT_SHUTDOWN_TIMEOUT`.
+    pub shutdown_timeout: Option<u64>,
+}
+
+/// Where the stream workers read their messages from.
+#[derive(Deserialize, PartialEq, Debug, Clone, Default)]
+#[serde(tag = "source_type")]
+pub enum SourceConfig {
+    /// The streams of `messenger_config`, Redis by default.
+    #[default]
+    Messenger,
+    /// A Yellowstone geyser gRPC subscription.
+    Grpc(GrpcSourceConfig),
+}
+
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct GrpcSourceConfig {
+    pub endpoint: String,
+    pub x_token: Option<String>,
+    /// Programs whose accounts and transactions are subscribed to.
+    pub programs: Vec<String>,
+    pub commitment: Option<CommitmentLevel>,
+    /// Stream that account updates are delivered on, it should match an `Account` worker.
+    pub account_stream: Option<String>,
+    /// Stream that transactions are delivered on, it should match a `Transaction` worker.
+    pub transaction_stream: Option<String>,
+    /// Number of messages buffered per stream before the subscription is throttled.
+    pub buffer_size: Option<usize>,
 }
 
 #[derive(Deserialize, PartialEq, Debug, Clone)]
@@ -38,6 +82,41 @@ pub struct WorkerConfig {
     pub stream_name: String,
     pub worker_type: WorkerType,
     pub worker_count: u32,
+    /// Transaction workers only. Processes the transactions of each received batch in this
+    /// many partitions keyed by Merkle tree, serially within a partition and in parallel across
+    /// partitions. The stream then gets a single worker whatever `worker_count` says. Unset
+    /// processes every transaction of a batch concurrently.
+    pub partitions: Option<usize>,
+}
+
+impl WorkerConfig {
+    /// Number of workers to run. Partitions only order the transactions a single consumer
+    /// receives, so a partitioned transaction stream is read by one worker.
+    pub fn consumer_count(&self) -> u32 {
+        if self.is_partitioned() {
+            self.worker_count.min(1)
+        } else {
+            self.worker_count
+        }
+    }
+
+    /// What the consumer at `index` reads. The first consumer claims the messages other
+    /// consumers left pending and the rest read new ones, while the single consumer of a
+    /// partitioned stream does both.
+    pub fn consumption_type(&self, index: u32) -> ConsumptionType {
+        if self.is_partitioned() {
+            ConsumptionType::All
+        } else if index == 0 {
+            ConsumptionType::Redeliver
+        } else {
+            ConsumptionType::New
+        }
+    }
+
+    fn is_partitioned(&self) -> bool {
+        self.worker_type == WorkerType::Transaction
+            && self.partitions.map_or(false, |partitions| partitions > 0)
+    }
 }
 
 #[derive(Deserialize, PartialEq, Debug, Clone)]
@@ -85,20 +164,73 @@ impl IngesterConfig {
                     stream_name: "ACC".to_string(),
                     worker_count: 2,
                     worker_type: WorkerType::Account,
+                    partitions: None,
                 },
                 WorkerConfig {
                     stream_name: "TXN".to_string(),
                     worker_count: 2,
                     worker_type: WorkerType::Transaction,
+                    partitions: None,
                 },
             ]
         }
     }
 
+    /// The `cl_items_history` sequence window, `None` when the history is not saved.
+    pub fn get_cl_items_history_seq_window(&self) -> Option<u64> {
+        self.cl_items_history.unwrap_or(false).then(|| {
+            self.cl_items_history_seq_window
+                .unwrap_or(DEFAULT_CL_ITEMS_HISTORY_SEQ_WINDOW)
+        })
+    }
+
+    pub fn get_shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout.unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT))
+    }
+
+    /// `max_message_tries`, checked against the retries of the Redis messenger, which would
+    /// otherwise drop failing messages before they are quarantined.
+    pub fn get_max_message_tries(&self) -> Result<Option<usize>, IngesterError> {
+        let Some(max_tries) = self.max_message_tries else {
+            return Ok(None);
+        };
+        let source_config = self.source_config.clone().unwrap_or_default();
+        if source_config == SourceConfig::Messenger
+            && self.messenger_config.messenger_type == MessengerType::Redis
+        {
+            let retries = self
+                .messenger_config
+                .connection_config
+                .get("retries")
+                .and_then(|r| r.clone().to_u128().map(|n| n as usize))
+                .unwrap_or(DEFAULT_RETRIES);
+            if max_tries > retries {
+                return Err(IngesterError::ConfigurationError {
+                    msg: format!(
+                        "max_message_tries ({}) exceeds the messenger retries ({}), raise `retries` in messenger_config.connection_config",
+                        max_tries, retries
+                    ),
+                });
+            }
+        }
+        Ok(Some(max_tries))
+    }
+
+    /// The backfiller sends the transactions it fetches to the messenger `TXNFILL` stream, which
+    /// only the messenger source consumes.
+    pub fn check_backfiller_source(&self) -> Result<(), IngesterError> {
+        if self.source_config.clone().unwrap_or_default() != SourceConfig::Messenger {
+            return Err(IngesterError::ConfigurationError {
+                msg: "the backfiller needs the messenger source, run the Ingester role on its own with a gRPC source".to_string(),
+            });
+        }
+        Ok(())
+    }
+
     pub fn get_worker_count(&self) -> u32 {
         let mut count = 0;
         for wc in self.get_worker_config() {
-            count += wc.worker_count;
+            count += wc.consumer_count();
         }
         count
     }
@@ -115,6 +247,8 @@ pub type RpcConfig = figment::value::Dict;
 pub const RPC_URL_KEY: &str = "url";
 pub const RPC_COMMITMENT_KEY: &str = "commitment";
 pub const CODE_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const DEFAULT_CL_ITEMS_HISTORY_SEQ_WINDOW: u64 = 10_000;
+pub const DEFAULT_SHUTDOWN_TIMEOUT: u64 = 20;
 
 #[derive(Deserialize, Default, PartialEq, Eq, Debug, Clone)]
 pub enum IngesterRole {
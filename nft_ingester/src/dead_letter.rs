@@ -0,0 +1,80 @@
+use {
+    crate::metric,
+    cadence_macros::{is_global_default_set, statsd_count},
+    digital_asset_types::dao::{failed_messages, sea_orm_active_enums::FailedMessageType},
+    log::{error, warn},
+    sea_orm::{EntityTrait, Set, SqlxPostgresConnector},
+    sqlx::{Pool, Postgres},
+};
+
+/// A stream message that could not be processed.
+pub struct FailedMessage<'a> {
+    pub stream: &'a str,
+    pub id: &'a str,
+    pub message_type: FailedMessageType,
+    pub data: &'a [u8],
+    /// Deliveries before this one, as reported by the source.
+    pub tries: usize,
+    pub error: String,
+    pub slot: Option<u64>,
+    pub signature: Option<Vec<u8>>,
+    pub pubkey: Option<Vec<u8>>,
+}
+
+/// Moves messages that failed `max_tries` deliveries to the `failed_messages` table, so they
+/// stop clogging the redeliver consumer. Without `max_tries` failed messages are left to the
+/// source, which may redeliver them or drop them after its own retries.
+#[derive(Clone)]
+pub struct DeadLetterPolicy {
+    pool: Pool<Postgres>,
+    max_tries: Option<usize>,
+}
+
+impl DeadLetterPolicy {
+    pub const fn new(pool: Pool<Postgres>, max_tries: Option<usize>) -> Self {
+        Self { pool, max_tries }
+    }
+
+    /// Stores `message` if it just failed its last try. Returns whether it was quarantined, in
+    /// which case it must be acked.
+    pub async fn quarantine(&self, message: FailedMessage<'_>) -> bool {
+        let tries = message.tries + 1;
+        if !self.max_tries.is_some_and(|max_tries| tries >= max_tries) {
+            return false;
+        }
+
+        let model = failed_messages::ActiveModel {
+            stream: Set(message.stream.to_string()),
+            message_id: Set(message.id.to_string()),
+            message_type: Set(message.message_type),
+            data: Set(message.data.to_vec()),
+            error: Set(message.error),
+            tries: Set(tries as i32),
+            slot: Set(message.slot.map(|slot| slot as i64)),
+            signature: Set(message.signature),
+            pubkey: Set(message.pubkey),
+            ..Default::default()
+        };
+
+        let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(self.pool.clone());
+        match failed_messages::Entity::insert(model).exec(&conn).await {
+            Ok(_) => {
+                warn!(
+                    "Quarantined message {} from stream {} after {} tries",
+                    message.id, message.stream, tries
+                );
+                metric! {
+                    statsd_count!("ingester.quarantined", 1, "stream" => message.stream);
+                }
+                true
+            }
+            Err(e) => {
+                error!(
+                    "Error quarantining message {} from stream {}: {}",
+                    message.id, message.stream, e
+                );
+                false
+            }
+        }
+    }
+}
@@ -36,6 +36,8 @@ pub enum IngesterError {
     SerializatonError(String),
     #[error("Messenger error; {0}")]
     MessengerError(String),
+    #[error("Message source error: {0}")]
+    SourceError(String),
     #[error("Blockbuster Parsing error: {0}")]
     ParsingError(String),
     #[error("Database Error: {0}")]
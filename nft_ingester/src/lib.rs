@@ -1,10 +1,13 @@
+pub mod account_updates;
 pub mod ack;
 pub mod backfiller;
 pub mod config;
 pub mod database;
+pub mod dead_letter;
 pub mod error;
 pub mod metrics;
 pub mod plerkle;
+pub mod source;
 pub mod stream;
 pub mod tasks;
 pub mod transaction_notifications;
@@ -3,9 +3,11 @@ mod ack;
 mod backfiller;
 pub mod config;
 mod database;
+mod dead_letter;
 pub mod error;
 pub mod metrics;
 mod plerkle;
+pub mod source;
 mod stream;
 pub mod tasks;
 mod transaction_notifications;
@@ -18,6 +20,7 @@ use crate::{
     database::setup_database,
     error::IngesterError,
     metrics::setup_metrics,
+    source::setup_source,
     stream::StreamSizeTimer,
     tasks::{BgTask, DownloadMetadataTask, TaskManager},
     transaction_notifications::transaction_worker,
@@ -25,10 +28,14 @@ use crate::{
 use cadence_macros::{is_global_default_set, statsd_count};
 use chrono::Duration;
 use clap::{arg, command, value_parser};
-use log::{error, info};
-use plerkle_messenger::{redis_messenger::RedisMessenger, ConsumptionType};
-use std::{path::PathBuf, time};
-use tokio::{signal, task::JoinSet};
+use futures::future::join_all;
+use log::{error, info, warn};
+use plerkle_messenger::redis_messenger::RedisMessenger;
+use std::{path::PathBuf, sync::Arc, time};
+use tokio::{signal, task::JoinSet, time::timeout};
+use tokio_util::sync::CancellationToken;
+
+const SHUTDOWN_FLUSH_TIMEOUT: time::Duration = time::Duration::from_secs(5);
 
 #[tokio::main(flavor = "multi_thread")]
 pub async fn main() -> Result<(), IngesterError> {
@@ -66,6 +73,9 @@ pub async fn main() -> Result<(), IngesterError> {
     let role = config.clone().role.unwrap_or(IngesterRole::All);
 
     info!("Starting Program with Role {}", role);
+    if role == IngesterRole::Backfiller || role == IngesterRole::All {
+        config.check_backfiller_source()?;
+    }
     // Tasks Setup -----------------------------------------------
     // This joinset maages all the tasks that are spawned.
     let mut tasks = JoinSet::new();
@@ -91,17 +101,19 @@ pub async fn main() -> Result<(), IngesterError> {
     let bg_task_listener = background_task_manager
         .start_listener(role == IngesterRole::BackgroundTaskRunner || role == IngesterRole::All);
     let bg_task_sender = background_task_manager.get_sender().unwrap();
-    // Always listen for background tasks unless we are the bg task runner
-    if role != IngesterRole::BackgroundTaskRunner {
-        tasks.spawn(bg_task_listener);
-    }
 
     // Stream Consumers Setup -------------------------------------
+    // Workers stop receiving once cancelled, then finish and ack what they already received.
+    let shutdown = CancellationToken::new();
+    let mut stream_workers = Vec::new();
+    let mut ack_task = None;
     if role == IngesterRole::Ingester || role == IngesterRole::All {
         let workers = config.get_worker_config().clone();
+        let max_message_tries = config.get_max_message_tries()?;
+        let source = setup_source(&config).await?;
 
-        let (_ack_task, ack_sender) =
-            ack_worker::<RedisMessenger>(config.get_messneger_client_config());
+        let (ack_handle, ack_sender) = ack_worker(Arc::clone(&source));
+        ack_task = Some(ack_handle);
 
         // iterate all the workers
         for worker in workers {
@@ -109,41 +121,47 @@ pub async fn main() -> Result<(), IngesterError> {
 
             let mut timer_worker = StreamSizeTimer::new(
                 stream_metrics_timer,
-                config.messenger_config.clone(),
+                Arc::clone(&source),
                 stream_name.as_str(),
             )?;
 
-            if let Some(t) = timer_worker.start::<RedisMessenger>().await {
+            if let Some(t) = timer_worker.start().await {
                 tasks.spawn(t);
             }
 
-            for i in 0..worker.worker_count {
+            let consumer_count = worker.consumer_count();
+            if consumer_count < worker.worker_count {
+                warn!(
+                    "Stream {} is partitioned, running 1 of its {} transaction workers",
+                    stream_name, worker.worker_count
+                );
+            }
+
+            for i in 0..consumer_count {
                 if worker.worker_type == WorkerType::Account {
-                    let _account = account_worker::<RedisMessenger>(
+                    stream_workers.push(account_worker(
                         database_pool.clone(),
-                        config.get_messneger_client_config(),
+                        Arc::clone(&source),
                         bg_task_sender.clone(),
                         ack_sender.clone(),
-                        if i == 0 {
-                            ConsumptionType::Redeliver
-                        } else {
-                            ConsumptionType::New
-                        },
+                        worker.consumption_type(i),
                         stream_name,
-                    );
+                        max_message_tries,
+                        shutdown.clone(),
+                    ));
                 } else if worker.worker_type == WorkerType::Transaction {
-                    let _txn = transaction_worker::<RedisMessenger>(
+                    stream_workers.push(transaction_worker(
                         database_pool.clone(),
-                        config.get_messneger_client_config(),
+                        Arc::clone(&source),
                         bg_task_sender.clone(),
                         ack_sender.clone(),
-                        if i == 0 {
-                            ConsumptionType::Redeliver
-                        } else {
-                            ConsumptionType::New
-                        },
+                        worker.consumption_type(i),
                         stream_name,
-                    );
+                        max_message_tries,
+                        worker.partitions,
+                        config.get_cl_items_history_seq_window(),
+                        shutdown.clone(),
+                    ));
                 }
             }
         }
@@ -165,15 +183,77 @@ pub async fn main() -> Result<(), IngesterError> {
     metric! {
         statsd_count!("ingester.startup", 1, "role" => &roles_str, "version" => config.code_version.unwrap_or("unknown"));
     }
-    match signal::ctrl_c().await {
-        Ok(()) => {}
-        Err(err) => {
-            error!("Unable to listen for shutdown signal: {}", err);
-            // we also shut down in case of error
+    shutdown_signal().await;
+
+    // Graceful Shutdown ------------------------------------------
+    info!(
+        "Shutting down, draining {} stream workers",
+        stream_workers.len()
+    );
+    shutdown.cancel();
+    let shutdown_timeout = config.get_shutdown_timeout();
+    if timeout(shutdown_timeout, join_all(stream_workers.iter_mut()))
+        .await
+        .is_err()
+    {
+        warn!(
+            "Stream workers did not drain within {:?}, unacked messages will be redelivered",
+            shutdown_timeout
+        );
+        for worker in &stream_workers {
+            worker.abort();
         }
     }
 
+    // The ack worker and the background task listener exit once their senders are all dropped,
+    // after flushing the pending acks and saving the queued tasks.
+    drop(stream_workers);
+    drop(bg_task_sender);
+    drop(background_task_manager);
+    let flush = async {
+        if let Some(ack_task) = ack_task {
+            if let Err(e) = ack_task.await {
+                error!("Ack worker failed: {}", e);
+            }
+        }
+        if let Err(e) = bg_task_listener.await {
+            error!("Background task listener failed: {}", e);
+        }
+    };
+    if timeout(SHUTDOWN_FLUSH_TIMEOUT, flush).await.is_err() {
+        warn!("Pending acks and background tasks were not flushed in time");
+    }
+
     tasks.shutdown().await;
+    info!("nft_ingester stopped");
 
     Ok(())
 }
+
+/// Resolves on ctrl-c or, on unix, on SIGTERM as sent by Kubernetes before stopping a pod.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(err) => {
+                error!("Unable to listen for SIGTERM: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        res = signal::ctrl_c() => {
+            if let Err(err) = res {
+                error!("Unable to listen for shutdown signal: {}", err);
+                // we also shut down in case of error
+            }
+        }
+        _ = terminate => {}
+    }
+}
@@ -0,0 +1,119 @@
+use {
+    super::SourceConsumer,
+    crate::error::IngesterError,
+    async_trait::async_trait,
+    plerkle_messenger::{ConsumptionType, RecvData},
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+    },
+    tokio::sync::{mpsc, Mutex as AsyncMutex},
+};
+
+/// Maximum number of messages handed out by a single `recv`.
+const RECV_BATCH_SIZE: usize = 100;
+
+/// In-process streams shared by all the consumers of a source. Each message is delivered to
+/// a single consumer and never redelivered, whatever the consumption type.
+pub struct ChannelStreams {
+    buffer_size: usize,
+    next_id: AtomicU64,
+    streams: Mutex<HashMap<String, ChannelStream>>,
+}
+
+#[derive(Clone)]
+struct ChannelStream {
+    sender: mpsc::Sender<RecvData>,
+    receiver: Arc<AsyncMutex<mpsc::Receiver<RecvData>>>,
+}
+
+impl ChannelStreams {
+    pub fn new(buffer_size: usize) -> Self {
+        Self {
+            buffer_size,
+            next_id: AtomicU64::new(0),
+            streams: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn stream(&self, stream_key: &str) -> ChannelStream {
+        let mut streams = self.streams.lock().expect("channel streams lock poisoned");
+        streams
+            .entry(stream_key.to_string())
+            .or_insert_with(|| {
+                let (sender, receiver) = mpsc::channel(self.buffer_size);
+                ChannelStream {
+                    sender,
+                    receiver: Arc::new(AsyncMutex::new(receiver)),
+                }
+            })
+            .clone()
+    }
+
+    /// Queues a message and returns its id, waiting while the stream is full.
+    pub async fn send(&self, stream_key: &str, data: Vec<u8>) -> String {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed).to_string();
+        let sender = self.stream(stream_key).sender;
+        // The stream holds on to its receiver, so the channel is never closed.
+        let _ = sender.send(RecvData::new(id.clone(), data)).await;
+        id
+    }
+
+    /// Waits for at least one message.
+    pub async fn recv(&self, stream_key: &str) -> Vec<RecvData> {
+        let receiver = self.stream(stream_key).receiver;
+        let mut receiver = receiver.lock().await;
+        let mut messages = Vec::new();
+        if let Some(message) = receiver.recv().await {
+            messages.push(message);
+        }
+        while messages.len() < RECV_BATCH_SIZE {
+            match receiver.try_recv() {
+                Ok(message) => messages.push(message),
+                Err(_) => break,
+            }
+        }
+        messages
+    }
+
+    pub fn size(&self, stream_key: &str) -> u64 {
+        let sender = self.stream(stream_key).sender;
+        (sender.max_capacity() - sender.capacity()) as u64
+    }
+}
+
+pub struct ChannelConsumer {
+    streams: Arc<ChannelStreams>,
+}
+
+impl ChannelConsumer {
+    pub const fn new(streams: Arc<ChannelStreams>) -> Self {
+        Self { streams }
+    }
+}
+
+#[async_trait]
+impl SourceConsumer for ChannelConsumer {
+    async fn recv(
+        &mut self,
+        stream_key: &'static str,
+        _consumption_type: ConsumptionType,
+    ) -> Result<Vec<RecvData>, IngesterError> {
+        Ok(self.streams.recv(stream_key).await)
+    }
+
+    async fn ack(
+        &mut self,
+        _stream_key: &'static str,
+        _ids: &[String],
+    ) -> Result<(), IngesterError> {
+        Ok(())
+    }
+
+    async fn stream_size(&mut self, stream_key: &'static str) -> Result<u64, IngesterError> {
+        Ok(self.streams.size(stream_key))
+    }
+}
@@ -0,0 +1,233 @@
+use {
+    super::{
+        channel::{ChannelConsumer, ChannelStreams},
+        MessageSource, SourceConsumer,
+    },
+    crate::{config::GrpcSourceConfig, error::IngesterError, metric},
+    async_trait::async_trait,
+    cadence_macros::{is_global_default_set, statsd_count},
+    flatbuffers::FlatBufferBuilder,
+    futures::StreamExt,
+    log::{error, warn},
+    plerkle_messenger::{ACCOUNT_STREAM, TRANSACTION_STREAM},
+    plerkle_serialization::{
+        serializer::{seralize_encoded_transaction_with_status, serialize_account},
+        solana_geyser_plugin_interface_shims::ReplicaAccountInfoV2,
+    },
+    solana_sdk::{
+        commitment_config::{CommitmentConfig, CommitmentLevel},
+        pubkey::Pubkey,
+    },
+    solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding},
+    std::{collections::HashMap, sync::Arc},
+    tokio::{
+        task::JoinHandle,
+        time::{sleep, Duration},
+    },
+    yellowstone_grpc_client::GeyserGrpcClient,
+    yellowstone_grpc_proto::{
+        convert_from::create_tx_with_meta,
+        prelude::{
+            subscribe_update::UpdateOneof, CommitmentLevel as GrpcCommitmentLevel,
+            SubscribeRequest, SubscribeRequestFilterAccounts, SubscribeRequestFilterTransactions,
+            SubscribeUpdateAccount, SubscribeUpdateTransaction,
+        },
+    },
+};
+
+const DEFAULT_BUFFER_SIZE: usize = 1000;
+const DEFAULT_COMMITMENT: CommitmentLevel = CommitmentLevel::Confirmed;
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const FILTER_NAME: &str = "das";
+
+/// Source fed by a Yellowstone geyser gRPC subscription. Updates are serialized to plerkle
+/// flatbuffers, like the ones the plerkle plugin writes to Redis, and buffered in memory.
+/// There are no acks: updates missed while the subscription is down are not replayed.
+pub struct GrpcSource {
+    streams: Arc<ChannelStreams>,
+    subscription: JoinHandle<()>,
+}
+
+impl GrpcSource {
+    pub fn new(config: GrpcSourceConfig) -> Result<Self, IngesterError> {
+        if config.programs.is_empty() {
+            return Err(IngesterError::ConfigurationError {
+                msg: "gRPC source needs at least one program to subscribe to".to_string(),
+            });
+        }
+
+        let streams = Arc::new(ChannelStreams::new(
+            config.buffer_size.unwrap_or(DEFAULT_BUFFER_SIZE),
+        ));
+        let subscription = tokio::spawn(run_subscription(config, Arc::clone(&streams)));
+
+        Ok(Self {
+            streams,
+            subscription,
+        })
+    }
+}
+
+impl Drop for GrpcSource {
+    fn drop(&mut self) {
+        self.subscription.abort();
+    }
+}
+
+#[async_trait]
+impl MessageSource for GrpcSource {
+    async fn consumer(&self) -> Result<Box<dyn SourceConsumer>, IngesterError> {
+        Ok(Box::new(ChannelConsumer::new(Arc::clone(&self.streams))))
+    }
+}
+
+async fn run_subscription(config: GrpcSourceConfig, streams: Arc<ChannelStreams>) {
+    let account_stream = config
+        .account_stream
+        .clone()
+        .unwrap_or_else(|| ACCOUNT_STREAM.to_string());
+    let transaction_stream = config
+        .transaction_stream
+        .clone()
+        .unwrap_or_else(|| TRANSACTION_STREAM.to_string());
+
+    loop {
+        match subscribe(&config, &streams, &account_stream, &transaction_stream).await {
+            Ok(()) => warn!("gRPC subscription to {} ended", config.endpoint),
+            Err(e) => error!("gRPC subscription to {} failed: {}", config.endpoint, e),
+        }
+        metric! {
+            statsd_count!("ingester.grpc.reconnect", 1);
+        }
+        sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn subscribe(
+    config: &GrpcSourceConfig,
+    streams: &ChannelStreams,
+    account_stream: &str,
+    transaction_stream: &str,
+) -> Result<(), IngesterError> {
+    let mut client = GeyserGrpcClient::build_from_shared(config.endpoint.clone())
+        .and_then(|builder| builder.x_token(config.x_token.clone()))
+        .map_err(|e| IngesterError::SourceError(e.to_string()))?
+        .connect()
+        .await
+        .map_err(|e| IngesterError::SourceError(e.to_string()))?;
+    let (_requests, mut updates) = client
+        .subscribe_with_request(Some(subscribe_request(config)))
+        .await
+        .map_err(|e| IngesterError::SourceError(e.to_string()))?;
+
+    while let Some(update) = updates.next().await {
+        let update = update.map_err(|e| IngesterError::SourceError(e.to_string()))?;
+        let (stream_key, data) = match update.update_oneof {
+            Some(UpdateOneof::Account(account)) => {
+                (account_stream, serialize_account_update(account))
+            }
+            Some(UpdateOneof::Transaction(transaction)) => (
+                transaction_stream,
+                serialize_transaction_update(transaction),
+            ),
+            _ => continue,
+        };
+        match data {
+            Ok(data) => {
+                streams.send(stream_key, data).await;
+            }
+            Err(e) => {
+                error!("Error serializing gRPC update: {}", e);
+                metric! {
+                    statsd_count!("ingester.grpc.serialize_error", 1, "stream" => stream_key);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn subscribe_request(config: &GrpcSourceConfig) -> SubscribeRequest {
+    let commitment = CommitmentConfig {
+        commitment: config.commitment.unwrap_or(DEFAULT_COMMITMENT),
+    };
+    let commitment = if commitment.is_finalized() {
+        GrpcCommitmentLevel::Finalized
+    } else if commitment.is_confirmed() {
+        GrpcCommitmentLevel::Confirmed
+    } else {
+        GrpcCommitmentLevel::Processed
+    };
+
+    SubscribeRequest {
+        accounts: HashMap::from([(
+            FILTER_NAME.to_string(),
+            SubscribeRequestFilterAccounts {
+                owner: config.programs.clone(),
+                ..Default::default()
+            },
+        )]),
+        transactions: HashMap::from([(
+            FILTER_NAME.to_string(),
+            SubscribeRequestFilterTransactions {
+                vote: Some(false),
+                failed: Some(false),
+                account_include: config.programs.clone(),
+                ..Default::default()
+            },
+        )]),
+        commitment: Some(commitment as i32),
+        ..Default::default()
+    }
+}
+
+fn serialize_account_update(update: SubscribeUpdateAccount) -> Result<Vec<u8>, IngesterError> {
+    let account = update.account.ok_or_else(|| {
+        IngesterError::DeserializationError("Account update without account".to_string())
+    })?;
+    // The serializer panics on keys that are not 32 bytes long.
+    let pubkey = Pubkey::try_from(account.pubkey.as_slice())
+        .map_err(|e| IngesterError::DeserializationError(e.to_string()))?;
+    let owner = Pubkey::try_from(account.owner.as_slice())
+        .map_err(|e| IngesterError::DeserializationError(e.to_string()))?;
+
+    let builder = serialize_account(
+        FlatBufferBuilder::new(),
+        &ReplicaAccountInfoV2 {
+            pubkey: pubkey.as_ref(),
+            lamports: account.lamports,
+            owner: owner.as_ref(),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+            data: &account.data,
+            write_version: account.write_version,
+            txn_signature: None,
+        },
+        update.slot,
+        update.is_startup,
+    );
+    Ok(builder.finished_data().to_vec())
+}
+
+fn serialize_transaction_update(
+    update: SubscribeUpdateTransaction,
+) -> Result<Vec<u8>, IngesterError> {
+    let transaction = update.transaction.ok_or_else(|| {
+        IngesterError::DeserializationError("Transaction update without transaction".to_string())
+    })?;
+    let transaction = create_tx_with_meta(transaction)
+        .map_err(IngesterError::DeserializationError)?
+        .encode(UiTransactionEncoding::Base64, Some(0), false)
+        .map_err(|e| IngesterError::SerializatonError(e.to_string()))?;
+
+    let builder = seralize_encoded_transaction_with_status(
+        FlatBufferBuilder::new(),
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: update.slot,
+            transaction,
+            block_time: None,
+        },
+    )?;
+    Ok(builder.finished_data().to_vec())
+}
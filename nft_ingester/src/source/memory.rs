@@ -0,0 +1,87 @@
+use {
+    super::{
+        channel::{ChannelConsumer, ChannelStreams},
+        MessageSource, SourceConsumer,
+    },
+    crate::error::IngesterError,
+    async_trait::async_trait,
+    plerkle_messenger::{ConsumptionType, RecvData},
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    },
+};
+
+type Acks = Arc<Mutex<HashMap<String, Vec<String>>>>;
+
+/// Source fed from the same process, mostly to drive the workers from tests. Acks are
+/// recorded so callers can tell which messages were processed.
+#[derive(Clone)]
+pub struct MemorySource {
+    streams: Arc<ChannelStreams>,
+    acks: Acks,
+}
+
+impl MemorySource {
+    pub fn new(buffer_size: usize) -> Self {
+        Self {
+            streams: Arc::new(ChannelStreams::new(buffer_size)),
+            acks: Acks::default(),
+        }
+    }
+
+    /// Queues plerkle-serialized `data` on `stream_key` and returns the message id.
+    pub async fn send(&self, stream_key: &str, data: Vec<u8>) -> String {
+        self.streams.send(stream_key, data).await
+    }
+
+    /// Ids of the messages acked so far on `stream_key`.
+    pub fn acked(&self, stream_key: &str) -> Vec<String> {
+        self.acks
+            .lock()
+            .expect("memory source acks lock poisoned")
+            .get(stream_key)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl MessageSource for MemorySource {
+    async fn consumer(&self) -> Result<Box<dyn SourceConsumer>, IngesterError> {
+        Ok(Box::new(MemoryConsumer {
+            inner: ChannelConsumer::new(Arc::clone(&self.streams)),
+            acks: Arc::clone(&self.acks),
+        }))
+    }
+}
+
+struct MemoryConsumer {
+    inner: ChannelConsumer,
+    acks: Acks,
+}
+
+#[async_trait]
+impl SourceConsumer for MemoryConsumer {
+    async fn recv(
+        &mut self,
+        stream_key: &'static str,
+        consumption_type: ConsumptionType,
+    ) -> Result<Vec<RecvData>, IngesterError> {
+        self.inner.recv(stream_key, consumption_type).await
+    }
+
+    async fn ack(&mut self, stream_key: &'static str, ids: &[String]) -> Result<(), IngesterError> {
+        self.acks
+            .lock()
+            .expect("memory source acks lock poisoned")
+            .entry(stream_key.to_string())
+            .or_default()
+            .extend_from_slice(ids);
+        Ok(())
+    }
+
+    async fn stream_size(&mut self, stream_key: &'static str) -> Result<u64, IngesterError> {
+        self.inner.stream_size(stream_key).await
+    }
+}
@@ -0,0 +1,90 @@
+mod channel;
+pub mod grpc;
+pub mod memory;
+
+pub use {grpc::GrpcSource, memory::MemorySource};
+
+use {
+    crate::{
+        config::{rand_string, IngesterConfig, SourceConfig},
+        error::IngesterError,
+    },
+    async_trait::async_trait,
+    figment::value::Value,
+    plerkle_messenger::{select_messenger, ConsumptionType, Messenger, MessengerConfig, RecvData},
+    std::sync::Arc,
+};
+
+/// Where the account and transaction workers read their messages from. Whatever the
+/// source, messages carry plerkle-serialized flatbuffers.
+#[async_trait]
+pub trait MessageSource: Send + Sync {
+    /// Opens a consumer for a single worker.
+    async fn consumer(&self) -> Result<Box<dyn SourceConsumer>, IngesterError>;
+}
+
+#[async_trait]
+pub trait SourceConsumer: Send {
+    async fn recv(
+        &mut self,
+        stream_key: &'static str,
+        consumption_type: ConsumptionType,
+    ) -> Result<Vec<RecvData>, IngesterError>;
+
+    async fn ack(&mut self, stream_key: &'static str, ids: &[String]) -> Result<(), IngesterError>;
+
+    async fn stream_size(&mut self, stream_key: &'static str) -> Result<u64, IngesterError>;
+}
+
+pub async fn setup_source(
+    config: &IngesterConfig,
+) -> Result<Arc<dyn MessageSource>, IngesterError> {
+    match config.source_config.clone().unwrap_or_default() {
+        SourceConfig::Messenger => Ok(Arc::new(MessengerSource::new(
+            config.messenger_config.clone(),
+        ))),
+        SourceConfig::Grpc(grpc_config) => Ok(Arc::new(GrpcSource::new(grpc_config)?)),
+    }
+}
+
+/// Streams of a `plerkle_messenger` backend, each consumer gets its own consumer id.
+pub struct MessengerSource {
+    config: MessengerConfig,
+}
+
+impl MessengerSource {
+    pub const fn new(config: MessengerConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl MessageSource for MessengerSource {
+    async fn consumer(&self) -> Result<Box<dyn SourceConsumer>, IngesterError> {
+        let mut config = self.config.clone();
+        config
+            .connection_config
+            .insert("consumer_id".to_string(), Value::from(rand_string()));
+        let messenger = select_messenger(config).await?;
+        Ok(Box::new(messenger))
+    }
+}
+
+#[async_trait]
+impl SourceConsumer for Box<dyn Messenger> {
+    async fn recv(
+        &mut self,
+        stream_key: &'static str,
+        consumption_type: ConsumptionType,
+    ) -> Result<Vec<RecvData>, IngesterError> {
+        Ok(Messenger::recv(self.as_mut(), stream_key, consumption_type).await?)
+    }
+
+    async fn ack(&mut self, stream_key: &'static str, ids: &[String]) -> Result<(), IngesterError> {
+        Ok(self.ack_msg(stream_key, ids).await?)
+    }
+
+    async fn stream_size(&mut self, stream_key: &'static str) -> Result<u64, IngesterError> {
+        Ok(Messenger::stream_size(self.as_mut(), stream_key).await?)
+    }
+}
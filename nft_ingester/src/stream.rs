@@ -1,8 +1,8 @@
-use crate::{error::IngesterError, metric};
+use crate::{error::IngesterError, metric, source::MessageSource};
 use cadence_macros::{is_global_default_set, statsd_count, statsd_gauge};
 
 use log::error;
-use plerkle_messenger::{Messenger, MessengerConfig};
+use std::sync::Arc;
 use tokio::{
     task::JoinHandle,
     time::{self, Duration},
@@ -10,31 +10,31 @@ use tokio::{
 
 pub struct StreamSizeTimer {
     interval: tokio::time::Duration,
-    messenger_config: MessengerConfig,
+    source: Arc<dyn MessageSource>,
     stream: &'static str,
 }
 
 impl StreamSizeTimer {
     pub const fn new(
         interval: Duration,
-        messenger_config: MessengerConfig,
+        source: Arc<dyn MessageSource>,
         stream: &'static str,
     ) -> Result<Self, IngesterError> {
         Ok(Self {
             interval,
             stream,
-            messenger_config,
+            source,
         })
     }
 
-    pub async fn start<T: Messenger>(&mut self) -> Option<JoinHandle<()>> {
+    pub async fn start(&mut self) -> Option<JoinHandle<()>> {
         metric! {
             let i = self.interval;
-            let messenger_config = self.messenger_config.clone();
+            let source = Arc::clone(&self.source);
             let stream = self.stream;
 
            return Some(tokio::spawn(async move {
-            let messenger = T::new(messenger_config).await;
+            let messenger = source.consumer().await;
             if let Ok(mut messenger) = messenger {
             let mut interval = time::interval(i);
                 loop {
@@ -4,6 +4,7 @@ use cadence_macros::{is_global_default_set, statsd_count, statsd_histogram};
 use chrono::{Duration, NaiveDateTime, Utc};
 use crypto::{digest::Digest, sha2::Sha256};
 use digital_asset_types::dao::{sea_orm_active_enums::TaskStatus, tasks};
+use futures::future::join_all;
 use log::{debug, error, info, warn};
 use sea_orm::{
     entity::*, query::*, sea_query::Expr, ActiveValue::Set, ColumnTrait, DatabaseConnection,
@@ -322,7 +323,9 @@ impl TaskManager {
         let instance_name = self.instance_name.clone();
 
         tokio::task::spawn(async move {
+            let mut handlers = Vec::new();
             while let Some(task) = receiver.recv().await {
+                handlers.retain(|handler: &JoinHandle<_>| !handler.is_finished());
                 if let Some(task_created_time) = task.created_at {
                     let bus_time = Utc::now().timestamp_millis()
                         - task_created_time.and_utc().timestamp_millis();
@@ -346,14 +349,20 @@ impl TaskManager {
                     metric! {
                         statsd_count!("ingester.bgtask.new", 1, "type" => task.name);
                     }
-                    TaskManager::new_task_handler(
+                    handlers.push(TaskManager::new_task_handler(
                         pool.clone(),
                         instance_name.clone(),
                         name,
                         task,
                         Arc::clone(&task_map),
                         process_on_receive,
-                    );
+                    ));
+                }
+            }
+            // The channel closes once every sender is dropped, wait for the queued tasks to be saved.
+            for res in join_all(handlers).await.into_iter().flatten() {
+                if let Err(e) = res {
+                    error!("Error saving background task: {}", e);
                 }
             }
         })
@@ -1,48 +1,72 @@
 use {
     crate::{
+        dead_letter::{DeadLetterPolicy, FailedMessage},
         metric,
         metrics::capture_result,
         plerkle::{into_program_transformer_err, PlerkleTransactionInfo},
+        source::MessageSource,
         tasks::{create_download_metadata_notifier, TaskData},
     },
     cadence_macros::{is_global_default_set, statsd_count, statsd_time},
     chrono::Utc,
+    digital_asset_types::dao::sea_orm_active_enums::FailedMessageType,
     log::{debug, error},
-    plerkle_messenger::{ConsumptionType, Messenger, MessengerConfig, RecvData},
+    plerkle_messenger::{ConsumptionType, RecvData},
     plerkle_serialization::root_as_transaction_info,
-    program_transformers::ProgramTransformer,
+    program_transformers::{ProgramTransformer, TransactionInfo},
+    solana_sdk::pubkey::Pubkey,
     sqlx::{Pool, Postgres},
-    std::sync::Arc,
+    std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+        sync::Arc,
+    },
     tokio::{
         sync::mpsc::UnboundedSender,
         task::{JoinHandle, JoinSet},
         time::Instant,
     },
+    tokio_util::sync::CancellationToken,
 };
 
-pub fn transaction_worker<T: Messenger>(
+#[allow(clippy::too_many_arguments)]
+pub fn transaction_worker(
     pool: Pool<Postgres>,
-    config: MessengerConfig,
+    source: Arc<dyn MessageSource>,
     bg_task_sender: UnboundedSender<TaskData>,
     ack_channel: UnboundedSender<(&'static str, String)>,
     consumption_type: ConsumptionType,
     stream_key: &'static str,
+    max_tries: Option<usize>,
+    partitions: Option<usize>,
+    cl_items_history_seq_window: Option<u64>,
+    shutdown: CancellationToken,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
-        let source = T::new(config).await;
-        if let Ok(mut msg) = source {
-            let manager = Arc::new(ProgramTransformer::new(
-                pool,
-                create_download_metadata_notifier(bg_task_sender),
-            ));
+        let consumer = source.consumer().await;
+        if let Ok(mut msg) = consumer {
+            let dead_letter = DeadLetterPolicy::new(pool.clone(), max_tries);
+            let manager = Arc::new(
+                ProgramTransformer::new(pool, create_download_metadata_notifier(bg_task_sender))
+                    .with_cl_items_history(cl_items_history_seq_window),
+            );
             loop {
-                let e = msg.recv(stream_key, consumption_type.clone()).await;
+                let e = tokio::select! {
+                    biased;
+                    _ = shutdown.cancelled() => break,
+                    e = msg.recv(stream_key, consumption_type.clone()) => e,
+                };
                 let mut tasks = JoinSet::new();
                 match e {
                     Ok(data) => {
                         let len = data.len();
-                        for item in data {
-                            tasks.spawn(handle_transaction(Arc::clone(&manager), item, stream_key));
+                        for items in partition_messages(data, partitions) {
+                            tasks.spawn(handle_partition(
+                                Arc::clone(&manager),
+                                dead_letter.clone(),
+                                items,
+                                stream_key,
+                            ));
                         }
                         if len > 0 {
                             debug!("Processed {} txns", len);
@@ -56,7 +80,7 @@ pub fn transaction_worker<T: Messenger>(
                     }
                 }
                 while let Some(res) = tasks.join_next().await {
-                    if let Ok(Some(id)) = res {
+                    for id in res.unwrap_or_default() {
                         let send = ack_channel.send((stream_key, id));
                         if let Err(err) = send {
                             metric! {
@@ -71,8 +95,84 @@ pub fn transaction_worker<T: Messenger>(
     })
 }
 
+/// Splits received messages into at most `partitions` groups, keeping their order. Messages
+/// touching the same Merkle tree land in the same group, the others are spread by message id.
+/// Without `partitions` every message gets a group of its own.
+pub fn partition_messages(items: Vec<RecvData>, partitions: Option<usize>) -> Vec<Vec<RecvData>> {
+    let Some(partitions) = partitions.filter(|partitions| *partitions > 0) else {
+        return items.into_iter().map(|item| vec![item]).collect();
+    };
+
+    let mut groups: Vec<Vec<RecvData>> = (0..partitions).map(|_| Vec::new()).collect();
+    for item in items {
+        let mut hasher = DefaultHasher::new();
+        match merkle_tree(&item.data) {
+            Some(tree) => tree.hash(&mut hasher),
+            None => item.id.hash(&mut hasher),
+        }
+        groups[(hasher.finish() % partitions as u64) as usize].push(item);
+    }
+    groups.retain(|group| !group.is_empty());
+    groups
+}
+
+/// The first Merkle tree an account compression instruction of the transaction writes to. Only
+/// the account keys and instruction accounts are read, the transaction is not deserialized.
+fn merkle_tree(data: &[u8]) -> Option<Pubkey> {
+    let tx = root_as_transaction_info(data).ok()?;
+    let keys = tx.account_keys()?;
+    let key = |index: u8| {
+        let index = index as usize;
+        (index < keys.len()).then(|| Pubkey::new_from_array(keys.get(index).0))
+    };
+
+    let compiled_inner_instructions = tx
+        .compiled_inner_instructions()
+        .into_iter()
+        .flatten()
+        .flat_map(|inner| inner.instructions().into_iter().flatten())
+        .filter_map(|inner| inner.compiled_instruction());
+    let inner_instructions = tx
+        .inner_instructions()
+        .into_iter()
+        .flatten()
+        .flat_map(|inner| inner.instructions().into_iter().flatten());
+
+    tx.outer_instructions()
+        .into_iter()
+        .flatten()
+        .chain(compiled_inner_instructions)
+        .chain(inner_instructions)
+        .find_map(|instruction| {
+            let program = key(instruction.program_id_index())?;
+            // Every account compression instruction takes the tree as its first account.
+            if program == spl_account_compression::id() || program == mpl_account_compression::id()
+            {
+                key(instruction.accounts()?.iter().next()?)
+            } else {
+                None
+            }
+        })
+}
+
+/// Processes the messages of a partition one after the other, returning the ids to ack.
+async fn handle_partition(
+    manager: Arc<ProgramTransformer>,
+    dead_letter: DeadLetterPolicy,
+    items: Vec<RecvData>,
+    stream_key: &'static str,
+) -> Vec<String> {
+    let mut ids = Vec::with_capacity(items.len());
+    for item in items {
+        let id = handle_transaction(Arc::clone(&manager), dead_letter.clone(), item, stream_key);
+        ids.extend(id.await);
+    }
+    ids
+}
+
 async fn handle_transaction(
     manager: Arc<ProgramTransformer>,
+    dead_letter: DeadLetterPolicy,
     item: RecvData,
     stream_key: &'static str,
 ) -> Option<String> {
@@ -84,39 +184,70 @@ async fn handle_transaction(
     }
     let id = item.id.to_string();
     let tx_data = item.data;
-    if let Ok(tx) = root_as_transaction_info(&tx_data) {
-        let signature = tx.signature().unwrap_or("NO SIG");
-        debug!("Received transaction: {}", signature);
-        metric! {
-            statsd_count!("ingester.seen", 1, "stream" => stream_key);
-        }
-        let seen_at = Utc::now();
-        metric! {
-            statsd_time!(
-                "ingester.bus_ingest_time",
-                (seen_at.timestamp_millis() - tx.seen_at()) as u64,
-                "stream" => stream_key
+    let failed = |error: String, slot: Option<u64>, signature: Option<&str>| FailedMessage {
+        stream: stream_key,
+        id: &id,
+        message_type: FailedMessageType::Transaction,
+        data: &tx_data,
+        tries: item.tries,
+        error,
+        slot,
+        signature: signature.and_then(|signature| bs58::decode(signature).into_vec().ok()),
+        pubkey: None,
+    };
+    match root_as_transaction_info(&tx_data) {
+        Ok(tx) => {
+            let signature = tx.signature().unwrap_or("NO SIG");
+            debug!("Received transaction: {}", signature);
+            metric! {
+                statsd_count!("ingester.seen", 1, "stream" => stream_key);
+            }
+            let seen_at = Utc::now();
+            metric! {
+                statsd_time!(
+                    "ingester.bus_ingest_time",
+                    (seen_at.timestamp_millis() - tx.seen_at()) as u64,
+                    "stream" => stream_key
+                );
+            }
+
+            let begin = Instant::now();
+            let transaction_info: TransactionInfo = match PlerkleTransactionInfo(tx).try_into() {
+                Ok(transaction_info) => transaction_info,
+                Err(e) => {
+                    let error = into_program_transformer_err(e).to_string();
+                    let failed = failed(error, Some(tx.slot()), tx.signature());
+                    return dead_letter.quarantine(failed).await.then_some(id.clone());
+                }
+            };
+            let res = manager.handle_transaction(&transaction_info).await;
+            let error = res.as_ref().err().map(ToString::to_string);
+            let should_ack = capture_result(
+                id.clone(),
+                stream_key,
+                ("txn", "txn"),
+                item.tries,
+                res,
+                begin,
+                tx.signature(),
+                None,
             );
+            if should_ack {
+                ret_id = Some(id.clone());
+            } else if let Some(error) = error {
+                let failed = failed(error, Some(tx.slot()), tx.signature());
+                if dead_letter.quarantine(failed).await {
+                    ret_id = Some(id.clone());
+                }
+            }
         }
-
-        let begin = Instant::now();
-        let transaction_info = PlerkleTransactionInfo(tx)
-            .try_into()
-            .map_err(into_program_transformer_err)
-            .ok()?;
-        let res = manager.handle_transaction(&transaction_info).await;
-        let should_ack = capture_result(
-            id.clone(),
-            stream_key,
-            ("txn", "txn"),
-            item.tries,
-            res,
-            begin,
-            tx.signature(),
-            None,
-        );
-        if should_ack {
-            ret_id = Some(id);
+        Err(e) => {
+            if dead_letter
+                .quarantine(failed(e.to_string(), None, None))
+                .await
+            {
+                ret_id = Some(id.clone());
+            }
         }
     }
     ret_id
@@ -2,3 +2,5 @@
 pub mod account;
 pub mod bubblegum;
 pub mod purge;
+pub mod quarantine;
+pub mod replay;
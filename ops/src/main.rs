@@ -1,6 +1,8 @@
 mod account;
 mod bubblegum;
 mod purge;
+mod quarantine;
+mod replay;
 
 use account::{subcommand as account_subcommand, AccountCommand};
 use anyhow::Result;
@@ -22,6 +24,10 @@ enum Command {
     Account(AccountCommand),
     #[clap(name = "purge")]
     Purge(purge::PurgeCommand),
+    #[clap(name = "quarantine")]
+    Quarantine(quarantine::QuarantineCommand),
+    #[clap(name = "replay")]
+    Replay(replay::ReplayCommand),
 }
 
 #[tokio::main]
@@ -34,6 +40,8 @@ async fn main() -> Result<()> {
         Command::Bubblegum(subcommand) => bubblegum_subcommand(subcommand).await?,
         Command::Account(subcommand) => account_subcommand(subcommand).await?,
         Command::Purge(subcommand) => purge::subcommand(subcommand).await?,
+        Command::Quarantine(subcommand) => quarantine::subcommand(subcommand).await?,
+        Command::Replay(subcommand) => replay::subcommand(subcommand).await?,
     }
 
     Ok(())
@@ -0,0 +1,64 @@
+use super::{
+    details, find_message, list_messages, replay_messages, summary, ListArgs, ReplayArgs, ShowArgs,
+};
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use das_core::{connect_db, DatabasePool, PoolArgs};
+use futures::future::{ready, FutureExt};
+use program_transformers::ProgramTransformer;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Commands {
+    /// List quarantined messages, oldest first
+    #[clap(name = "list")]
+    List(ListArgs),
+    /// Show a quarantined message, including its plerkle payload
+    #[clap(name = "show")]
+    Show(ShowArgs),
+    /// Replay quarantined messages, removing the ones indexed successfully
+    #[clap(name = "replay")]
+    Replay(ReplayArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct QuarantineCommand {
+    /// Database configuration
+    #[clap(flatten)]
+    pub database: PoolArgs,
+    /// The action to take
+    #[clap(subcommand)]
+    pub action: Commands,
+}
+
+pub async fn subcommand(subcommand: QuarantineCommand) -> Result<()> {
+    let pool = connect_db(subcommand.database).await?;
+    let conn = pool.connection();
+
+    match subcommand.action {
+        Commands::List(args) => {
+            for message in list_messages(&conn, &args).await? {
+                println!("{}", summary(&message));
+            }
+        }
+        Commands::Show(args) => match find_message(&conn, args.id).await? {
+            Some(message) => println!("{}", details(&message)),
+            None => bail!("no quarantined message with id {}", args.id),
+        },
+        Commands::Replay(args) => {
+            let transformer =
+                ProgramTransformer::new(pool, Box::new(|_info| ready(Ok(())).boxed()));
+            let report = replay_messages(&transformer, &conn, &args, |outcome| {
+                println!("{}", outcome)
+            })
+            .await?;
+
+            println!("{}", report);
+
+            if report.failed > 0 {
+                bail!("{} message(s) failed to replay", report.failed);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use crate::replay::{ReplayItem, ReplayOutcome, ReplayReport, ReplayStatus};
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::Parser;
+use digital_asset_types::dao::{failed_messages, sea_orm_active_enums::FailedMessageType};
+use program_transformers::ProgramTransformer;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use solana_sdk::bs58;
+use std::collections::HashSet;
+
+const REPLAY_BATCH_SIZE: u64 = 100;
+
+#[derive(Debug, Parser, Clone)]
+pub struct ListArgs {
+    /// Only list messages quarantined from this stream
+    #[arg(long)]
+    pub stream: Option<String>,
+    /// The maximum number of messages to list
+    #[arg(long, default_value = "100")]
+    pub limit: u64,
+}
+
+#[derive(Debug, Parser, Clone)]
+pub struct ShowArgs {
+    /// The id of the quarantined message
+    pub id: i64,
+}
+
+#[derive(Debug, Parser, Clone)]
+pub struct ReplayArgs {
+    /// The ids of the quarantined messages to replay
+    #[arg(required_unless_present = "all")]
+    pub ids: Vec<i64>,
+    /// Replay every quarantined message
+    #[arg(long, conflicts_with = "ids")]
+    pub all: bool,
+    /// Only replay messages quarantined from this stream
+    #[arg(long)]
+    pub stream: Option<String>,
+}
+
+/// One line description of a quarantined message.
+pub fn summary(message: &failed_messages::Model) -> String {
+    let message_type = match message.message_type {
+        FailedMessageType::Transaction => "transaction",
+        FailedMessageType::Account => "account",
+    };
+    let key = message
+        .signature
+        .as_ref()
+        .or(message.pubkey.as_ref())
+        .map(|key| bs58::encode(key).into_string())
+        .unwrap_or_else(|| "-".to_string());
+    let slot = message
+        .slot
+        .map(|slot| slot.to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    format!(
+        "{} {} {} {} slot={} tries={} {}: {}",
+        message.id,
+        message.created_at,
+        message.stream,
+        message_type,
+        slot,
+        message.tries,
+        key,
+        message.error
+    )
+}
+
+/// Full description of a quarantined message, including its base64 encoded plerkle payload.
+pub fn details(message: &failed_messages::Model) -> String {
+    let encode = |key: &Option<Vec<u8>>| {
+        key.as_ref()
+            .map(|key| bs58::encode(key).into_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    format!(
+        "id: {}\nstream: {}\nmessage id: {}\ntype: {:?}\nquarantined at: {}\ntries: {}\nslot: {}\nsignature: {}\npubkey: {}\nerror: {}\ndata: {}",
+        message.id,
+        message.stream,
+        message.message_id,
+        message.message_type,
+        message.created_at,
+        message.tries,
+        message
+            .slot
+            .map(|slot| slot.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        encode(&message.signature),
+        encode(&message.pubkey),
+        message.error,
+        STANDARD.encode(&message.data)
+    )
+}
+
+pub async fn list_messages(
+    conn: &DatabaseConnection,
+    args: &ListArgs,
+) -> Result<Vec<failed_messages::Model>> {
+    let mut query = failed_messages::Entity::find()
+        .order_by_asc(failed_messages::Column::Id)
+        .limit(args.limit);
+    if let Some(stream) = &args.stream {
+        query = query.filter(failed_messages::Column::Stream.eq(stream.clone()));
+    }
+
+    Ok(query.all(conn).await?)
+}
+
+pub async fn find_message(
+    conn: &DatabaseConnection,
+    id: i64,
+) -> Result<Option<failed_messages::Model>> {
+    Ok(failed_messages::Entity::find_by_id(id).one(conn).await?)
+}
+
+/// Replays quarantined messages through the program transformer in the order they were
+/// quarantined. Messages indexed successfully are removed, the error of the ones that fail
+/// again is updated.
+pub async fn replay_messages<F: FnMut(&ReplayOutcome)>(
+    transformer: &ProgramTransformer,
+    conn: &DatabaseConnection,
+    args: &ReplayArgs,
+    mut on_outcome: F,
+) -> Result<ReplayReport> {
+    let mut report = ReplayReport::default();
+    let mut replayed = HashSet::new();
+    let mut last_id = i64::MIN;
+
+    loop {
+        let mut query = failed_messages::Entity::find()
+            .filter(failed_messages::Column::Id.gt(last_id))
+            .order_by_asc(failed_messages::Column::Id)
+            .limit(REPLAY_BATCH_SIZE);
+        if !args.all {
+            query = query.filter(failed_messages::Column::Id.is_in(args.ids.clone()));
+        }
+        if let Some(stream) = &args.stream {
+            query = query.filter(failed_messages::Column::Stream.eq(stream.clone()));
+        }
+
+        let messages = query.all(conn).await?;
+        let Some(last) = messages.last() else {
+            break;
+        };
+        last_id = last.id;
+
+        for message in messages {
+            replayed.insert(message.id);
+            let outcome = replay_message(transformer, conn, message).await?;
+            match outcome.status {
+                ReplayStatus::Indexed => report.indexed += 1,
+                ReplayStatus::Skipped => report.skipped += 1,
+                ReplayStatus::Failed(_) => report.failed += 1,
+            }
+            on_outcome(&outcome);
+        }
+    }
+
+    for id in args.ids.iter().filter(|id| !replayed.contains(id)) {
+        report.failed += 1;
+        on_outcome(&ReplayOutcome {
+            source: format!("failed_messages:{}", id),
+            item: None,
+            status: ReplayStatus::Failed("no such quarantined message".to_string()),
+        });
+    }
+
+    Ok(report)
+}
+
+async fn replay_message(
+    transformer: &ProgramTransformer,
+    conn: &DatabaseConnection,
+    message: failed_messages::Model,
+) -> Result<ReplayOutcome> {
+    let source = format!("failed_messages:{}", message.id);
+    let item = match message.message_type {
+        FailedMessageType::Transaction => ReplayItem::from_plerkle_transaction(&message.data),
+        FailedMessageType::Account => ReplayItem::from_plerkle_account(&message.data),
+    };
+
+    let (item, result) = match item {
+        Ok(item) => {
+            let result = item.handle(transformer).await.map_err(|e| e.to_string());
+            (Some(item.to_string()), result)
+        }
+        Err(e) => (None, Err(format!("{:#}", e))),
+    };
+
+    let status = match result {
+        Ok(()) => {
+            failed_messages::Entity::delete_by_id(message.id)
+                .exec(conn)
+                .await?;
+            ReplayStatus::Indexed
+        }
+        Err(error) => {
+            failed_messages::Entity::update_many()
+                .col_expr(failed_messages::Column::Error, Expr::value(error.clone()))
+                .filter(failed_messages::Column::Id.eq(message.id))
+                .exec(conn)
+                .await?;
+            ReplayStatus::Failed(error)
+        }
+    };
+
+    Ok(ReplayOutcome {
+        source,
+        item,
+        status,
+    })
+}
@@ -0,0 +1,5 @@
+mod cmd;
+mod messages;
+
+pub use cmd::*;
+pub use messages::*;
@@ -0,0 +1,42 @@
+use super::replay;
+use anyhow::{bail, Result};
+use clap::Args;
+use das_core::{connect_db, PoolArgs};
+use futures::future::{ready, FutureExt};
+use program_transformers::ProgramTransformer;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Args)]
+pub struct ReplayCommand {
+    /// Database configuration
+    #[clap(flatten)]
+    pub database: PoolArgs,
+    /// A JSONL file of records, or a directory whose files are replayed in file name order
+    pub path: PathBuf,
+    /// Stop at the first record that fails to decode or index
+    #[arg(long, default_value_t = false)]
+    pub stop_on_error: bool,
+}
+
+/// Indexes recorded transactions and account snapshots straight into the database, without
+/// Redis or RPC. Metadata JSON downloads are not queued.
+pub async fn subcommand(subcommand: ReplayCommand) -> Result<()> {
+    let pool = connect_db(subcommand.database).await?;
+    let transformer = ProgramTransformer::new(pool, Box::new(|_info| ready(Ok(())).boxed()));
+
+    let report = replay(
+        &transformer,
+        &subcommand.path,
+        subcommand.stop_on_error,
+        |outcome| println!("{}", outcome),
+    )
+    .await?;
+
+    println!("{}", report);
+
+    if report.failed > 0 {
+        bail!("{} record(s) failed to replay", report.failed);
+    }
+
+    Ok(())
+}
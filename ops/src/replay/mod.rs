@@ -0,0 +1,5 @@
+mod cmd;
+mod replayer;
+
+pub use cmd::*;
+pub use replayer::*;
@@ -0,0 +1,333 @@
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use flatbuffers::FlatBufferBuilder;
+use nft_ingester::plerkle::{PlerkleAccountInfo, PlerkleTransactionInfo};
+use plerkle_serialization::{
+    root_as_account_info, root_as_transaction_info,
+    serializer::seralize_encoded_transaction_with_status,
+};
+use program_transformers::{
+    error::ProgramTransformerResult, AccountInfo, ProgramTransformer, TransactionInfo,
+};
+use serde::Deserialize;
+use solana_account_decoder::UiAccount;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+/// A line of a `.jsonl` file, or the content of a `.json` file. Objects without a `type`
+/// are read as transactions returned by `getTransaction`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayRecord {
+    /// A transaction as returned by `getTransaction`.
+    Transaction {
+        transaction: EncodedConfirmedTransactionWithStatusMeta,
+    },
+    /// An account as returned by `getAccountInfo`, with the slot it was read at.
+    Account {
+        pubkey: String,
+        slot: u64,
+        account: UiAccount,
+    },
+    /// A base64 encoded plerkle transaction, as found on the `TXN` stream.
+    PlerkleTransaction { data: String },
+    /// A base64 encoded plerkle account, as found on the `ACC` stream.
+    PlerkleAccount { data: String },
+}
+
+impl FromStr for ReplayRecord {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(s)?;
+
+        if value.get("type").is_some() {
+            Ok(serde_json::from_value(value)?)
+        } else {
+            Ok(Self::Transaction {
+                transaction: serde_json::from_value(value)?,
+            })
+        }
+    }
+}
+
+pub(crate) enum ReplayItem {
+    Transaction(TransactionInfo),
+    Account(AccountInfo),
+}
+
+impl ReplayItem {
+    fn from_record(record: ReplayRecord) -> Result<Option<Self>> {
+        match record {
+            ReplayRecord::Transaction { transaction } => {
+                // Same as the backfillers, failed transactions never reach the parsers.
+                let meta = transaction.transaction.meta.as_ref();
+                if meta.map(|meta| meta.status.is_err()).unwrap_or(true) {
+                    return Ok(None);
+                }
+
+                let fbb = seralize_encoded_transaction_with_status(
+                    FlatBufferBuilder::new(),
+                    transaction,
+                )?;
+                Self::from_plerkle_transaction(fbb.finished_data()).map(Some)
+            }
+            ReplayRecord::Account {
+                pubkey,
+                slot,
+                account,
+            } => {
+                let account: Account = account
+                    .decode()
+                    .ok_or_else(|| anyhow!("unsupported account data encoding"))?;
+
+                Ok(Some(Self::Account(AccountInfo {
+                    slot,
+                    pubkey: Pubkey::from_str(&pubkey)?,
+                    owner: account.owner,
+                    data: account.data,
+                })))
+            }
+            ReplayRecord::PlerkleTransaction { data } => {
+                Self::from_plerkle_transaction(&STANDARD.decode(data)?).map(Some)
+            }
+            ReplayRecord::PlerkleAccount { data } => {
+                Self::from_plerkle_account(&STANDARD.decode(data)?).map(Some)
+            }
+        }
+    }
+
+    pub(crate) fn from_plerkle_transaction(bytes: &[u8]) -> Result<Self> {
+        let transaction = root_as_transaction_info(bytes)?;
+        Ok(Self::Transaction(
+            PlerkleTransactionInfo(transaction).try_into()?,
+        ))
+    }
+
+    pub(crate) fn from_plerkle_account(bytes: &[u8]) -> Result<Self> {
+        let account = root_as_account_info(bytes)?;
+        Ok(Self::Account(PlerkleAccountInfo(account).try_into()?))
+    }
+
+    pub(crate) async fn handle(
+        &self,
+        transformer: &ProgramTransformer,
+    ) -> ProgramTransformerResult<()> {
+        match self {
+            Self::Transaction(transaction) => transformer.handle_transaction(transaction).await,
+            Self::Account(account) => transformer.handle_account_update(account).await,
+        }
+    }
+}
+
+impl fmt::Display for ReplayItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transaction(transaction) => write!(f, "transaction {}", transaction.signature),
+            Self::Account(account) => write!(f, "account {}", account.pubkey),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayStatus {
+    Indexed,
+    /// The record holds a failed transaction, or one without status meta.
+    Skipped,
+    Failed(String),
+}
+
+/// The result of replaying one record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayOutcome {
+    /// The file, and line for `.jsonl` files, the record was read from.
+    pub source: String,
+    /// The transaction signature or account pubkey, when the record could be decoded.
+    pub item: Option<String>,
+    pub status: ReplayStatus,
+}
+
+impl fmt::Display for ReplayOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let item = self.item.as_deref().unwrap_or("-");
+        match &self.status {
+            ReplayStatus::Indexed => write!(f, "indexed {} {}", self.source, item),
+            ReplayStatus::Skipped => write!(f, "skipped {} {}", self.source, item),
+            ReplayStatus::Failed(error) => {
+                write!(f, "failed  {} {}: {}", self.source, item, error)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    pub indexed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Replayed {} records: {} indexed, {} skipped, {} failed",
+            self.indexed + self.skipped + self.failed,
+            self.indexed,
+            self.skipped,
+            self.failed
+        )
+    }
+}
+
+struct Replayer<'a, F> {
+    transformer: &'a ProgramTransformer,
+    stop_on_error: bool,
+    on_outcome: F,
+    report: ReplayReport,
+}
+
+impl<F: FnMut(&ReplayOutcome)> Replayer<'_, F> {
+    const fn stopped(&self) -> bool {
+        self.stop_on_error && self.report.failed > 0
+    }
+
+    async fn replay_file(&mut self, path: &Path) -> Result<()> {
+        let source = path.display().to_string();
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("jsonl") => {
+                let lines = BufReader::new(File::open(path)?).lines();
+                for (index, line) in lines.enumerate() {
+                    if self.stopped() {
+                        break;
+                    }
+                    let line = line?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let item = line.parse().and_then(ReplayItem::from_record);
+                    self.replay_item(format!("{}:{}", source, index + 1), item)
+                        .await;
+                }
+            }
+            Some("json") => {
+                let item = fs::read_to_string(path)
+                    .map_err(Into::into)
+                    .and_then(|content| content.parse())
+                    .and_then(ReplayItem::from_record);
+                self.replay_item(source, item).await;
+            }
+            Some("txn") => {
+                let item = fs::read(path)
+                    .map_err(Into::into)
+                    .and_then(|bytes| ReplayItem::from_plerkle_transaction(&bytes))
+                    .map(Some);
+                self.replay_item(source, item).await;
+            }
+            Some("acc") => {
+                let item = fs::read(path)
+                    .map_err(Into::into)
+                    .and_then(|bytes| ReplayItem::from_plerkle_account(&bytes))
+                    .map(Some);
+                self.replay_item(source, item).await;
+            }
+            _ => {
+                let item = Err(anyhow!(
+                    "unsupported file, expected a .jsonl, .json, .txn or .acc file"
+                ));
+                self.replay_item(source, item).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn replay_item(&mut self, source: String, item: Result<Option<ReplayItem>>) {
+        let (item, status) = match item {
+            Ok(Some(item)) => {
+                let status = match item.handle(self.transformer).await {
+                    Ok(()) => ReplayStatus::Indexed,
+                    Err(e) => ReplayStatus::Failed(e.to_string()),
+                };
+                (Some(item.to_string()), status)
+            }
+            Ok(None) => (None, ReplayStatus::Skipped),
+            Err(e) => (None, ReplayStatus::Failed(format!("{:#}", e))),
+        };
+
+        match status {
+            ReplayStatus::Indexed => self.report.indexed += 1,
+            ReplayStatus::Skipped => self.report.skipped += 1,
+            ReplayStatus::Failed(_) => self.report.failed += 1,
+        }
+
+        (self.on_outcome)(&ReplayOutcome {
+            source,
+            item,
+            status,
+        });
+    }
+}
+
+/// Replays the records under `path` through the program transformer, in order, calling
+/// `on_outcome` after each one.
+///
+/// `path` is either a file or a directory whose files are replayed in file name order,
+/// skipping hidden files. Files are read according to their extension:
+///
+/// * `.jsonl` - one `ReplayRecord` per line.
+/// * `.json` - a single `ReplayRecord`.
+/// * `.txn` / `.acc` - a plerkle serialized transaction or account.
+///
+/// Records that fail to decode or index are reported and replay moves on, unless
+/// `stop_on_error` is set. Only I/O errors on the inputs themselves abort the replay.
+pub async fn replay<F: FnMut(&ReplayOutcome)>(
+    transformer: &ProgramTransformer,
+    path: &Path,
+    stop_on_error: bool,
+    on_outcome: F,
+) -> Result<ReplayReport> {
+    let files = if path.is_dir() {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(path).with_context(|| format!("reading {}", path.display()))? {
+            let entry = entry?;
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if entry.file_type()?.is_file() && !hidden {
+                files.push(entry.path());
+            }
+        }
+        files.sort();
+        files
+    } else if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        bail!("{} is neither a file nor a directory", path.display());
+    };
+
+    let mut replayer = Replayer {
+        transformer,
+        stop_on_error,
+        on_outcome,
+        report: ReplayReport::default(),
+    };
+
+    for file in files {
+        if replayer.stopped() {
+            break;
+        }
+        replayer
+            .replay_file(&file)
+            .await
+            .with_context(|| format!("reading {}", file.display()))?;
+    }
+
+    Ok(replayer.report)
+}
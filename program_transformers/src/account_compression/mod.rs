@@ -0,0 +1,63 @@
+use crate::{error::ProgramTransformerResult, AccountInfo};
+use blockbuster::programs::account_compression::ConcurrentMerkleTreeAccount;
+use digital_asset_types::dao::merkle_tree;
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveValue, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryTrait,
+};
+
+pub async fn handle_account_compression_account(
+    account_info: &AccountInfo,
+    parsing_result: &ConcurrentMerkleTreeAccount,
+    db: &DatabaseConnection,
+) -> ProgramTransformerResult<()> {
+    let model = merkle_tree::ActiveModel {
+        id: ActiveValue::Set(account_info.pubkey.to_bytes().to_vec()),
+        max_depth: ActiveValue::Set(Some(parsing_result.max_depth as i32)),
+        max_buffer_size: ActiveValue::Set(Some(parsing_result.max_buffer_size as i32)),
+        canopy_depth: ActiveValue::Set(Some(parsing_result.canopy_depth as i32)),
+        creation_slot: ActiveValue::Set(Some(parsing_result.creation_slot as i64)),
+        // Bubblegum only ever appends leaves when minting.
+        num_minted: ActiveValue::Set(parsing_result.num_leaves as i64),
+        slot_updated: ActiveValue::Set(account_info.slot as i64),
+        ..Default::default()
+    };
+
+    // The header never changes once the tree is initialized. The leaf count and the slot only
+    // move forward, even when an older account update arrives late.
+    let query = merkle_tree::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([merkle_tree::Column::Id])
+                .values([
+                    (
+                        merkle_tree::Column::MaxDepth,
+                        Expr::cust("excluded.max_depth"),
+                    ),
+                    (
+                        merkle_tree::Column::MaxBufferSize,
+                        Expr::cust("excluded.max_buffer_size"),
+                    ),
+                    (
+                        merkle_tree::Column::CanopyDepth,
+                        Expr::cust("excluded.canopy_depth"),
+                    ),
+                    (
+                        merkle_tree::Column::CreationSlot,
+                        Expr::cust("excluded.creation_slot"),
+                    ),
+                    (
+                        merkle_tree::Column::NumMinted,
+                        Expr::cust("GREATEST(merkle_tree.num_minted, excluded.num_minted)"),
+                    ),
+                    (
+                        merkle_tree::Column::SlotUpdated,
+                        Expr::cust("GREATEST(merkle_tree.slot_updated, excluded.slot_updated)"),
+                    ),
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    db.execute(query).await?;
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use {
+    crate::error::{ProgramTransformerError, ProgramTransformerResult},
+    blockbuster::{
+        programs::{mpl_core_program, token_account, token_extensions, token_metadata},
+        token_metadata::accounts::Metadata,
+    },
+    digital_asset_types::dao::{asset, asset_signatures, token_accounts},
+    sea_orm::{
+        entity::{ActiveValue, ColumnTrait, EntityTrait},
+        query::{QueryFilter, QuerySelect, QueryTrait},
+        sea_query::query::OnConflict,
+        ConnectionTrait, DbBackend, FromQueryResult,
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::collections::{HashMap, HashSet},
+};
+
+#[derive(FromQueryResult)]
+struct TokenAccountMint {
+    pubkey: Vec<u8>,
+    mint: Vec<u8>,
+}
+
+#[derive(FromQueryResult)]
+struct AssetId {
+    id: Vec<u8>,
+}
+
+/// Name of an instruction from one of the programs that manage uncompressed assets, e.g.
+/// `Transfer` or `Burn`, stored with its signature like `cl_audits_v2` does for Bubblegum.
+/// `Unknown` when the instruction data cannot be parsed, `None` for any other program.
+pub fn asset_instruction_name(program: &Pubkey, data: &[u8]) -> Option<String> {
+    let name = if program == &token_metadata::token_metadata_id() {
+        token_metadata::instruction_name(data)
+    } else if program == &mpl_core_program::mpl_core_id() {
+        mpl_core_program::instruction_name(data)
+    } else if program == &token_account::token_program_id() {
+        token_account::instruction_name(data)
+    } else if program == &token_extensions::token_program_id() {
+        token_extensions::instruction_name(data)
+    } else {
+        return None;
+    };
+    Some(name.unwrap_or("Unknown").to_string())
+}
+
+/// Records `signature` against every known uncompressed asset referenced by `instructions`,
+/// given as the name and account keys of each instruction. Assets are matched by their
+/// id (mint or core asset), one of their token accounts or their metadata account. Returns the
+/// number of assets the signature was recorded for.
+pub async fn save_asset_signatures<T>(
+    signature: &[u8],
+    slot: u64,
+    instructions: &[(String, Vec<Pubkey>)],
+    conn: &T,
+) -> ProgramTransformerResult<usize>
+where
+    T: ConnectionTrait,
+{
+    let keys: HashSet<Vec<u8>> = instructions
+        .iter()
+        .flat_map(|(_, keys)| keys.iter().map(|key| key.to_bytes().to_vec()))
+        .collect();
+    if keys.is_empty() {
+        return Ok(0);
+    }
+
+    let token_account_mints: HashMap<Vec<u8>, Vec<u8>> = token_accounts::Entity::find()
+        .select_only()
+        .column(token_accounts::Column::Pubkey)
+        .column(token_accounts::Column::Mint)
+        .filter(token_accounts::Column::Pubkey.is_in(keys.clone()))
+        .into_model::<TokenAccountMint>()
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|account| (account.pubkey, account.mint))
+        .collect();
+
+    let candidates: HashSet<Vec<u8>> = keys
+        .iter()
+        .cloned()
+        .chain(token_account_mints.values().cloned())
+        .collect();
+    let assets: HashSet<Vec<u8>> = asset::Entity::find()
+        .select_only()
+        .column(asset::Column::Id)
+        .filter(asset::Column::Id.is_in(candidates))
+        .filter(asset::Column::Compressed.eq(false))
+        .into_model::<AssetId>()
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|asset| asset.id)
+        .collect();
+    if assets.is_empty() {
+        return Ok(0);
+    }
+
+    // Metadata accounts can only be derived from the mint, so they are matched for the assets
+    // already referenced somewhere in the transaction.
+    let metadata_accounts: HashMap<Vec<u8>, Vec<u8>> = assets
+        .iter()
+        .filter_map(|id| Pubkey::try_from(id.as_slice()).ok())
+        .map(|mint| {
+            (
+                Metadata::find_pda(&mint).0.to_bytes().to_vec(),
+                mint.to_bytes().to_vec(),
+            )
+        })
+        .collect();
+
+    let mut items: HashMap<Vec<u8>, &str> = HashMap::new();
+    for (name, ix_keys) in instructions {
+        for key in ix_keys {
+            let key = key.to_bytes().to_vec();
+            let asset_id = if assets.contains(&key) {
+                Some(key)
+            } else {
+                token_account_mints
+                    .get(&key)
+                    .filter(|mint| assets.contains(*mint))
+                    .or_else(|| metadata_accounts.get(&key))
+                    .cloned()
+            };
+            if let Some(asset_id) = asset_id {
+                items.entry(asset_id).or_insert(name);
+            }
+        }
+    }
+
+    let saved = items.len();
+    let models = items
+        .into_iter()
+        .map(|(asset_id, name)| asset_signatures::ActiveModel {
+            asset_id: ActiveValue::Set(asset_id),
+            signature: ActiveValue::Set(signature.to_vec()),
+            instruction: ActiveValue::Set(Some(name.to_string())),
+            slot: ActiveValue::Set(slot as i64),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    let query = asset_signatures::Entity::insert_many(models)
+        .on_conflict(
+            OnConflict::columns([
+                asset_signatures::Column::AssetId,
+                asset_signatures::Column::Signature,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    conn.execute(query)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+
+    Ok(saved)
+}
@@ -23,12 +23,21 @@ pub async fn burn<'c, T>(
     bundle: &InstructionBundle<'c>,
     txn: &'c T,
     instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<()>
 where
     T: ConnectionTrait + TransactionTrait,
 {
     if let Some(cl) = &parsing_result.tree_update {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction).await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_items_history_seq_window,
+        )
+        .await?;
         let leaf_index = cl.index;
         let (asset_id, _) = Pubkey::find_program_address(
             &[
@@ -1,8 +1,11 @@
 use {
     crate::{
-        bubblegum::db::{
-            save_changelog_event, upsert_asset_with_leaf_info,
-            upsert_asset_with_owner_and_delegate_info, upsert_asset_with_seq,
+        bubblegum::{
+            db::{
+                insert_asset_leaf_history, save_changelog_event, upsert_asset_with_leaf_info,
+                upsert_asset_with_owner_and_delegate_info, upsert_asset_with_seq,
+            },
+            NormalizedLeafFields,
         },
         error::{ProgramTransformerError, ProgramTransformerResult},
     },
@@ -18,12 +21,21 @@ pub async fn cancel_redeem<'c, T>(
     bundle: &InstructionBundle<'c>,
     txn: &'c T,
     instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<()>
 where
     T: ConnectionTrait + TransactionTrait,
 {
     if let (Some(le), Some(cl)) = (&parsing_result.leaf_update, &parsing_result.tree_update) {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction).await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_items_history_seq_window,
+        )
+        .await?;
         let (id, owner, delegate, data_hash, creator_hash) = match le.schema {
             LeafSchema::V1 {
                 id,
@@ -40,6 +52,8 @@ where
             }
         };
 
+        let leaf = NormalizedLeafFields::from(&le.schema);
+
         let id_bytes = id.to_bytes();
         let owner_bytes = owner.to_bytes().to_vec();
         let delegate = if owner == delegate || delegate.to_bytes() == [0; 32] {
@@ -71,6 +85,17 @@ where
         )
         .await?;
 
+        insert_asset_leaf_history(
+            &multi_txn,
+            &leaf,
+            tree_id.to_vec(),
+            le.leaf_hash.to_vec(),
+            seq as i64,
+            bundle.slot,
+            instruction,
+        )
+        .await?;
+
         // Partial update of asset table with just leaf owner and delegate.
         upsert_asset_with_owner_and_delegate_info(
             &multi_txn,
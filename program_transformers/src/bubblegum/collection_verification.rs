@@ -2,8 +2,8 @@ use {
     crate::{
         bubblegum::{
             db::{
-                save_changelog_event, upsert_asset_with_leaf_info, upsert_asset_with_seq,
-                upsert_collection_info,
+                insert_asset_leaf_history, save_changelog_event, upsert_asset_with_leaf_info,
+                upsert_asset_with_seq, upsert_collection_info,
             },
             NormalizedLeafFields,
         },
@@ -23,6 +23,7 @@ pub async fn process<'c, T>(
     bundle: &InstructionBundle<'c>,
     txn: &'c T,
     instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<()>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -46,7 +47,15 @@ where
             "Handling collection verification event for {} (verify: {}): {}",
             collection, verify, bundle.txn_id
         );
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction).await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_items_history_seq_window,
+        )
+        .await?;
 
         let leaf = NormalizedLeafFields::from(&le.schema);
 
@@ -75,6 +84,17 @@ where
         )
         .await?;
 
+        insert_asset_leaf_history(
+            &multi_txn,
+            &leaf,
+            tree_id.to_vec(),
+            le.leaf_hash.to_vec(),
+            seq as i64,
+            bundle.slot,
+            instruction,
+        )
+        .await?;
+
         upsert_asset_with_seq(&multi_txn, id_bytes.to_vec(), seq as i64).await?;
 
         // If the collection ID is the MPL Bubblegum program ID, it means the new MPL Core
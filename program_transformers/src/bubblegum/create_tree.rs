@@ -0,0 +1,51 @@
+use {
+    crate::{
+        bubblegum::db::upsert_merkle_tree_config,
+        error::{ProgramTransformerError, ProgramTransformerResult},
+    },
+    blockbuster::{
+        instruction::InstructionBundle,
+        programs::bubblegum::{BubblegumInstruction, InstructionName, Payload},
+    },
+    digital_asset_types::dao::sea_orm_active_enums::TreeVersion,
+    sea_orm::{ConnectionTrait, TransactionTrait},
+};
+
+pub async fn create_tree<'c, T>(
+    parsing_result: &BubblegumInstruction,
+    bundle: &InstructionBundle<'c>,
+    txn: &'c T,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    if let Some(Payload::CreateTree {
+        tree_id,
+        tree_creator,
+        max_depth,
+        max_buffer_size,
+        public,
+    }) = &parsing_result.payload
+    {
+        let version = match parsing_result.instruction {
+            InstructionName::CreateTreeV2 => TreeVersion::V2,
+            _ => TreeVersion::V1,
+        };
+
+        // Bubblegum initializes the tree delegate to the tree creator.
+        return upsert_merkle_tree_config(
+            txn,
+            tree_id.to_bytes().to_vec(),
+            tree_creator.to_bytes().to_vec(),
+            *public,
+            version,
+            *max_depth,
+            *max_buffer_size,
+            bundle.slot as i64,
+        )
+        .await;
+    }
+    Err(ProgramTransformerError::ParsingError(
+        "Ix not parsed correctly".to_string(),
+    ))
+}
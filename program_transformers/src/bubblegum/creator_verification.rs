@@ -1,8 +1,9 @@
 use {
     crate::{
         bubblegum::db::{
-            save_changelog_event, upsert_asset_creators, upsert_asset_with_leaf_info,
-            upsert_asset_with_owner_and_delegate_info, upsert_asset_with_seq,
+            insert_asset_leaf_history, save_changelog_event, upsert_asset_creators,
+            upsert_asset_with_leaf_info, upsert_asset_with_owner_and_delegate_info,
+            upsert_asset_with_seq,
         },
         bubblegum::NormalizedLeafFields,
         error::{ProgramTransformerError, ProgramTransformerResult},
@@ -21,6 +22,7 @@ pub async fn process<'c, T>(
     bundle: &InstructionBundle<'c>,
     txn: &'c T,
     instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<()>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -60,7 +62,15 @@ where
             "Handling creator verification event for creator {} (verify: {}): {}",
             creator, verify, bundle.txn_id
         );
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction).await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_items_history_seq_window,
+        )
+        .await?;
 
         let leaf = NormalizedLeafFields::from(&le.schema);
 
@@ -95,6 +105,17 @@ where
         )
         .await?;
 
+        insert_asset_leaf_history(
+            &multi_txn,
+            &leaf,
+            tree_id.to_vec(),
+            le.leaf_hash.to_vec(),
+            seq as i64,
+            bundle.slot,
+            instruction,
+        )
+        .await?;
+
         // Partial update of asset table with just leaf owner and delegate.
         upsert_asset_with_owner_and_delegate_info(
             &multi_txn,
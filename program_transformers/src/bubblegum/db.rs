@@ -1,11 +1,14 @@
 use {
-    crate::error::{ProgramTransformerError, ProgramTransformerResult},
+    crate::{
+        bubblegum::NormalizedLeafFields,
+        error::{ProgramTransformerError, ProgramTransformerResult},
+    },
     digital_asset_types::dao::{
-        asset, asset_authority, asset_creators, asset_data, asset_grouping, backfill_items,
-        cl_audits_v2, cl_items,
+        asset, asset_authority, asset_creators, asset_data, asset_grouping, asset_leaf_history,
+        backfill_items, cl_audits_v2, cl_items, cl_items_history, merkle_tree,
         sea_orm_active_enums::{
-            ChainMutability, Instruction, Mutability, OwnerType, RoyaltyTargetType,
-            SpecificationAssetClass, SpecificationVersions,
+            AssetDataSchema, ChainMutability, Instruction, Mutability, OwnerType,
+            RoyaltyTargetType, SpecificationAssetClass, SpecificationVersions, TreeVersion,
         },
     },
     mpl_bubblegum::{
@@ -16,7 +19,7 @@ use {
         entity::{ActiveValue, ColumnTrait, EntityTrait},
         prelude::*,
         query::{JsonValue, QueryFilter, QuerySelect, QueryTrait},
-        sea_query::query::OnConflict,
+        sea_query::{query::OnConflict, Expr},
         ConnectionTrait, DbBackend, TransactionTrait,
     },
     spl_account_compression::events::ChangeLogEventV1,
@@ -29,11 +32,20 @@ pub async fn save_changelog_event<'c, T>(
     txn_id: &str,
     txn: &T,
     instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<u64>
 where
     T: ConnectionTrait + TransactionTrait,
 {
-    insert_change_log(change_log_event, slot, txn_id, txn, instruction).await?;
+    insert_change_log(
+        change_log_event,
+        slot,
+        txn_id,
+        txn,
+        instruction,
+        cl_items_history_seq_window,
+    )
+    .await?;
     Ok(change_log_event.seq)
 }
 
@@ -47,6 +59,7 @@ pub async fn insert_change_log<'c, T>(
     txn_id: &str,
     txn: &T,
     instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<()>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -80,6 +93,15 @@ where
             leaf_idx: ActiveValue::Set(leaf_idx),
             ..Default::default()
         };
+        let history_item = cl_items_history::ActiveModel {
+            tree: item.tree.clone(),
+            level: item.level.clone(),
+            node_idx: item.node_idx.clone(),
+            hash: item.hash.clone(),
+            seq: item.seq.clone(),
+            leaf_idx: item.leaf_idx.clone(),
+            ..Default::default()
+        };
 
         i += 1;
         let mut query = cl_items::Entity::insert(item)
@@ -98,6 +120,27 @@ where
         txn.execute(query)
             .await
             .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+
+        if cl_items_history_seq_window.is_some() {
+            let query = cl_items_history::Entity::insert(history_item)
+                .on_conflict(
+                    OnConflict::columns([
+                        cl_items_history::Column::Tree,
+                        cl_items_history::Column::NodeIdx,
+                        cl_items_history::Column::Seq,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .build(DbBackend::Postgres);
+            txn.execute(query)
+                .await
+                .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+        }
+    }
+
+    if let Some(seq_window) = cl_items_history_seq_window {
+        prune_cl_items_history(change_log_event, seq_window, txn).await?;
     }
 
     let tx_id_bytes = bs58::decode(txn_id)
@@ -113,6 +156,7 @@ where
         seq: ActiveValue::Set(change_log_event.seq as i64),
         tx: ActiveValue::Set(tx_id_bytes),
         instruction: ActiveValue::Set(ix),
+        slot: ActiveValue::Set(Some(slot as i64)),
         ..Default::default()
     };
 
@@ -162,12 +206,53 @@ where
             ..Default::default()
         };
 
-        backfill_items::Entity::insert(item).exec(txn).await?;
+        // A forced check the API queued for a stale proof holds
+        // `backfill_items_pending_force_chk_idx`, the tree is checked either way.
+        let query = backfill_items::Entity::insert(item)
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .build(DbBackend::Postgres);
+        txn.execute(query).await?;
     }
 
     Ok(())
 }
 
+/// Drops the versions of the changed nodes that no sequence number of the last `seq_window` can
+/// be proven with. The newest version at or before the start of the window is kept, as it is
+/// still the node's value at the sequence numbers that follow it.
+async fn prune_cl_items_history<T>(
+    change_log_event: &ChangeLogEventV1,
+    seq_window: u64,
+    txn: &T,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    let Some(oldest_seq) = change_log_event.seq.checked_sub(seq_window) else {
+        return Ok(());
+    };
+    let node_idxs = change_log_event
+        .path
+        .iter()
+        .map(|p| p.index as i64)
+        .collect::<Vec<_>>();
+
+    cl_items_history::Entity::delete_many()
+        .filter(cl_items_history::Column::Tree.eq(change_log_event.id.as_ref().to_vec()))
+        .filter(cl_items_history::Column::NodeIdx.is_in(node_idxs))
+        .filter(Expr::cust_with_values(
+            "cl_items_history.seq < (SELECT MAX(kept.seq) FROM cl_items_history kept \
+             WHERE kept.tree = cl_items_history.tree AND kept.node_idx = cl_items_history.node_idx \
+             AND kept.seq <= $1)",
+            [oldest_seq as i64],
+        ))
+        .exec(txn)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn upsert_asset_with_leaf_info<T>(
     txn: &T,
@@ -306,6 +391,57 @@ where
     Ok(())
 }
 
+/// Appends the leaf written by a Bubblegum instruction to `asset_leaf_history`, which keeps
+/// every owner and delegate the asset has had rather than only the latest one.
+pub(crate) async fn insert_asset_leaf_history<T>(
+    txn: &T,
+    leaf: &NormalizedLeafFields,
+    tree_id: Vec<u8>,
+    leaf_hash: Vec<u8>,
+    seq: i64,
+    slot: u64,
+    instruction: &str,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    let delegate = if leaf.owner == leaf.delegate || leaf.delegate.to_bytes() == [0; 32] {
+        None
+    } else {
+        Some(leaf.delegate.to_bytes().to_vec())
+    };
+
+    let model = asset_leaf_history::ActiveModel {
+        asset_id: ActiveValue::Set(leaf.id.to_bytes().to_vec()),
+        tree: ActiveValue::Set(tree_id),
+        leaf_idx: ActiveValue::Set(leaf.nonce as i64),
+        seq: ActiveValue::Set(seq),
+        slot: ActiveValue::Set(slot as i64),
+        owner: ActiveValue::Set(leaf.owner.to_bytes().to_vec()),
+        delegate: ActiveValue::Set(delegate),
+        leaf: ActiveValue::Set(leaf_hash),
+        instruction: ActiveValue::Set(Instruction::from(instruction)),
+        ..Default::default()
+    };
+
+    let query = asset_leaf_history::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([
+                asset_leaf_history::Column::AssetId,
+                asset_leaf_history::Column::Seq,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+
+    txn.execute(query)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+
+    Ok(())
+}
+
 pub async fn upsert_asset_with_compression_info<T>(
     txn: &T,
     id: Vec<u8>,
@@ -466,6 +602,7 @@ where
         raw_name: ActiveValue::Set(Some(raw_name)),
         raw_symbol: ActiveValue::Set(Some(raw_symbol)),
         base_info_seq: ActiveValue::Set(Some(seq)),
+        ..Default::default()
     };
 
     let mut query = asset_data::Entity::insert(model)
@@ -502,6 +639,58 @@ where
     Ok(())
 }
 
+/// Stores the asset data of a Bubblegum V2 leaf. The `asset_data` row is created with placeholder
+/// metadata if the mint has not been indexed yet, the mint then overwrites it since its
+/// `base_info_seq` is unset.
+pub async fn upsert_asset_bubblegum_asset_data<T>(
+    txn: &T,
+    id: Vec<u8>,
+    asset_data: Option<Vec<u8>>,
+    asset_data_schema: Option<AssetDataSchema>,
+    slot_updated: i64,
+    seq: i64,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    let model = asset_data::ActiveModel {
+        id: ActiveValue::Set(id),
+        chain_data_mutability: ActiveValue::Set(ChainMutability::Unknown),
+        chain_data: ActiveValue::Set(JsonValue::Object(Default::default())),
+        metadata_url: ActiveValue::Set(String::new()),
+        metadata_mutability: ActiveValue::Set(Mutability::Unknown),
+        metadata: ActiveValue::Set(JsonValue::String("processing".to_string())),
+        slot_updated: ActiveValue::Set(slot_updated),
+        bubblegum_asset_data: ActiveValue::Set(asset_data),
+        bubblegum_asset_data_schema: ActiveValue::Set(asset_data_schema),
+        bubblegum_asset_data_seq: ActiveValue::Set(Some(seq)),
+        ..Default::default()
+    };
+
+    let mut query = asset_data::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([asset_data::Column::Id])
+                .update_columns([
+                    asset_data::Column::BubblegumAssetData,
+                    asset_data::Column::BubblegumAssetDataSchema,
+                    asset_data::Column::BubblegumAssetDataSeq,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+
+    // Do not overwrite changes from a later Bubblegum instruction.
+    query.sql = format!(
+        "{} WHERE excluded.bubblegum_asset_data_seq >= asset_data.bubblegum_asset_data_seq OR asset_data.bubblegum_asset_data_seq IS NULL",
+        query.sql
+    );
+    txn.execute(query)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn upsert_asset_base_info<T>(
     txn: &T,
@@ -667,3 +856,135 @@ where
 
     Ok(())
 }
+
+#[allow(clippy::too_many_arguments)]
+pub async fn upsert_merkle_tree_config<T>(
+    txn: &T,
+    tree_id: Vec<u8>,
+    tree_creator: Vec<u8>,
+    is_public: bool,
+    version: TreeVersion,
+    max_depth: u32,
+    max_buffer_size: u32,
+    slot_updated: i64,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    let model = merkle_tree::ActiveModel {
+        id: ActiveValue::Set(tree_id),
+        max_depth: ActiveValue::Set(Some(max_depth as i32)),
+        max_buffer_size: ActiveValue::Set(Some(max_buffer_size as i32)),
+        tree_creator: ActiveValue::Set(Some(tree_creator.clone())),
+        tree_delegate: ActiveValue::Set(Some(tree_creator)),
+        tree_delegate_slot_updated: ActiveValue::Set(Some(slot_updated)),
+        is_public: ActiveValue::Set(Some(is_public)),
+        version: ActiveValue::Set(Some(version)),
+        slot_updated: ActiveValue::Set(slot_updated),
+        ..Default::default()
+    };
+
+    // The tree account update may have been indexed first, keep its header and count. A
+    // `SetTreeDelegate` from the same or a later slot may also have been indexed first, keep its
+    // delegate.
+    let query = merkle_tree::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([merkle_tree::Column::Id])
+                .values([
+                    (
+                        merkle_tree::Column::MaxDepth,
+                        Expr::cust("excluded.max_depth"),
+                    ),
+                    (
+                        merkle_tree::Column::MaxBufferSize,
+                        Expr::cust("excluded.max_buffer_size"),
+                    ),
+                    (
+                        merkle_tree::Column::TreeCreator,
+                        Expr::cust("excluded.tree_creator"),
+                    ),
+                    (
+                        merkle_tree::Column::TreeDelegate,
+                        Expr::cust(
+                            "CASE WHEN merkle_tree.tree_delegate_slot_updated IS NULL \
+                             OR merkle_tree.tree_delegate_slot_updated < excluded.tree_delegate_slot_updated \
+                             THEN excluded.tree_delegate ELSE merkle_tree.tree_delegate END",
+                        ),
+                    ),
+                    (
+                        merkle_tree::Column::TreeDelegateSlotUpdated,
+                        Expr::cust(
+                            "GREATEST(merkle_tree.tree_delegate_slot_updated, \
+                             excluded.tree_delegate_slot_updated)",
+                        ),
+                    ),
+                    (
+                        merkle_tree::Column::IsPublic,
+                        Expr::cust("excluded.is_public"),
+                    ),
+                    (merkle_tree::Column::Version, Expr::cust("excluded.version")),
+                    (
+                        merkle_tree::Column::SlotUpdated,
+                        Expr::cust("GREATEST(merkle_tree.slot_updated, excluded.slot_updated)"),
+                    ),
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+
+    txn.execute(query)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+
+    Ok(())
+}
+
+pub async fn upsert_merkle_tree_delegate<T>(
+    txn: &T,
+    tree_id: Vec<u8>,
+    tree_delegate: Vec<u8>,
+    slot_updated: i64,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    let model = merkle_tree::ActiveModel {
+        id: ActiveValue::Set(tree_id),
+        tree_delegate: ActiveValue::Set(Some(tree_delegate)),
+        tree_delegate_slot_updated: ActiveValue::Set(Some(slot_updated)),
+        slot_updated: ActiveValue::Set(slot_updated),
+        ..Default::default()
+    };
+
+    // Later delegate changes win, including over a `CreateTree` in the same slot.
+    let mut query = merkle_tree::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([merkle_tree::Column::Id])
+                .values([
+                    (
+                        merkle_tree::Column::TreeDelegate,
+                        Expr::cust("excluded.tree_delegate"),
+                    ),
+                    (
+                        merkle_tree::Column::TreeDelegateSlotUpdated,
+                        Expr::cust("excluded.tree_delegate_slot_updated"),
+                    ),
+                    (
+                        merkle_tree::Column::SlotUpdated,
+                        Expr::cust("GREATEST(merkle_tree.slot_updated, excluded.slot_updated)"),
+                    ),
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    query.sql = format!(
+        "{} WHERE merkle_tree.tree_delegate_slot_updated IS NULL OR merkle_tree.tree_delegate_slot_updated <= excluded.tree_delegate_slot_updated",
+        query.sql
+    );
+
+    txn.execute(query)
+        .await
+        .map_err(|db_err| ProgramTransformerError::StorageWriteError(db_err.to_string()))?;
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use {
     crate::{
         bubblegum::db::{
-            save_changelog_event, upsert_asset_with_leaf_info,
+            insert_asset_leaf_history, save_changelog_event, upsert_asset_with_leaf_info,
             upsert_asset_with_owner_and_delegate_info, upsert_asset_with_seq,
         },
         bubblegum::NormalizedLeafFields,
@@ -16,12 +16,21 @@ pub async fn delegation_freezing_nontransferability<'c, T>(
     bundle: &InstructionBundle<'c>,
     txn: &'c T,
     instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<()>
 where
     T: ConnectionTrait + TransactionTrait,
 {
     if let (Some(le), Some(cl)) = (&parsing_result.leaf_update, &parsing_result.tree_update) {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction).await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_items_history_seq_window,
+        )
+        .await?;
 
         let leaf = NormalizedLeafFields::from(&le.schema);
 
@@ -55,6 +64,17 @@ where
         )
         .await?;
 
+        insert_asset_leaf_history(
+            &multi_txn,
+            &leaf,
+            tree_id.to_vec(),
+            le.leaf_hash.to_vec(),
+            seq as i64,
+            bundle.slot,
+            instruction,
+        )
+        .await?;
+
         // Partial update of asset table with just leaf owner and delegate.
         upsert_asset_with_owner_and_delegate_info(
             &multi_txn,
@@ -1,12 +1,13 @@
 use {
     crate::{
         bubblegum::{
-            bgum_use_method_to_token_metadata_use_method,
+            asset_data_schema_to_db, bgum_use_method_to_token_metadata_use_method,
             db::{
-                save_changelog_event, upsert_asset_authority, upsert_asset_base_info,
-                upsert_asset_creators, upsert_asset_data, upsert_asset_with_compression_info,
-                upsert_asset_with_leaf_info, upsert_asset_with_owner_and_delegate_info,
-                upsert_asset_with_seq, upsert_collection_info,
+                insert_asset_leaf_history, save_changelog_event, upsert_asset_authority,
+                upsert_asset_base_info, upsert_asset_bubblegum_asset_data, upsert_asset_creators,
+                upsert_asset_data, upsert_asset_with_compression_info, upsert_asset_with_leaf_info,
+                upsert_asset_with_owner_and_delegate_info, upsert_asset_with_seq,
+                upsert_collection_info,
             },
             NormalizedLeafFields,
         },
@@ -34,6 +35,7 @@ pub async fn mint<'c, T>(
     bundle: &InstructionBundle<'c>,
     txn: &'c T,
     instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<Option<DownloadMetadataInfo>>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -45,13 +47,23 @@ where
             args,
             authority,
             tree_id,
+            asset_data,
+            asset_data_schema,
         }),
     ) = (
         &parsing_result.leaf_update,
         &parsing_result.tree_update,
         &parsing_result.payload,
     ) {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction).await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_items_history_seq_window,
+        )
+        .await?;
         let metadata = args;
 
         let leaf = NormalizedLeafFields::from(&le.schema);
@@ -102,6 +114,19 @@ where
         )
         .await?;
 
+        // Only `MintV2` carries asset data.
+        if asset_data.is_some() || asset_data_schema.is_some() {
+            upsert_asset_bubblegum_asset_data(
+                &multi_txn,
+                id_bytes.to_vec(),
+                asset_data.clone(),
+                asset_data_schema.as_ref().map(asset_data_schema_to_db),
+                slot_i,
+                seq as i64,
+            )
+            .await?;
+        }
+
         // Upsert `asset` table base info.
         let delegate = if leaf.owner == leaf.delegate || leaf.delegate.to_bytes() == [0; 32] {
             None
@@ -144,6 +169,17 @@ where
         )
         .await?;
 
+        insert_asset_leaf_history(
+            &multi_txn,
+            &leaf,
+            tree_id.to_bytes().to_vec(),
+            le.leaf_hash.to_vec(),
+            seq as i64,
+            bundle.slot,
+            instruction,
+        )
+        .await?;
+
         // Partial update of asset table with just leaf owner and delegate.
         upsert_asset_with_owner_and_delegate_info(
             &multi_txn,
@@ -6,10 +6,12 @@ use {
     blockbuster::{
         instruction::InstructionBundle,
         programs::bubblegum::{
-            BubblegumInstruction, InstructionName, LeafSchema, UseMethod as BubblegumUseMethod,
+            AssetDataSchema, BubblegumInstruction, InstructionName, LeafSchema, Payload,
+            UseMethod as BubblegumUseMethod,
         },
         token_metadata::types::UseMethod as TokenMetadataUseMethod,
     },
+    digital_asset_types::dao::sea_orm_active_enums,
     sea_orm::{ConnectionTrait, TransactionTrait},
     solana_sdk::pubkey::Pubkey,
     tracing::{debug, info},
@@ -18,12 +20,15 @@ use {
 mod burn;
 mod cancel_redeem;
 mod collection_verification;
+mod create_tree;
 mod creator_verification;
 mod db;
 mod delegate;
 mod mint;
 mod redeem;
+mod set_tree_delegate;
 mod transfer;
+mod update_asset_data;
 mod update_metadata;
 
 pub async fn handle_bubblegum_instruction<'c, T>(
@@ -31,6 +36,7 @@ pub async fn handle_bubblegum_instruction<'c, T>(
     bundle: &'c InstructionBundle<'c>,
     txn: &T,
     download_metadata_notifier: &DownloadMetadataNotifier,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<()>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -40,7 +46,10 @@ where
     // @TODO this would be much better served by implemneting Debug trait on the InstructionName
     // or wrapping it into something that can display it more neatly.
     let ix_str = match ix_type {
-        InstructionName::Unknown => "Unknown",
+        InstructionName::Unknown => match parsing_result.payload {
+            Some(Payload::SetTreeDelegate { .. }) => "SetTreeDelegate",
+            _ => "Unknown",
+        },
         InstructionName::MintV1 => "MintV1",
         InstructionName::MintToCollectionV1 => "MintToCollectionV1",
         InstructionName::Redeem => "Redeem",
@@ -79,10 +88,24 @@ where
 
     match ix_type {
         InstructionName::Transfer | InstructionName::TransferV2 => {
-            transfer::transfer(parsing_result, bundle, txn, ix_str).await?;
+            transfer::transfer(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?;
         }
         InstructionName::Burn | InstructionName::BurnV2 => {
-            burn::burn(parsing_result, bundle, txn, ix_str).await?;
+            burn::burn(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?;
         }
         InstructionName::Delegate
         | InstructionName::DelegateV2
@@ -91,21 +114,49 @@ where
         | InstructionName::SetNonTransferableV2
         | InstructionName::ThawV2
         | InstructionName::ThawAndRevokeV2 => {
-            delegate::delegation_freezing_nontransferability(parsing_result, bundle, txn, ix_str)
-                .await?;
+            delegate::delegation_freezing_nontransferability(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?;
         }
         InstructionName::MintV1 | InstructionName::MintToCollectionV1 | InstructionName::MintV2 => {
-            if let Some(info) = mint::mint(parsing_result, bundle, txn, ix_str).await? {
+            if let Some(info) = mint::mint(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?
+            {
                 download_metadata_notifier(info)
                     .await
                     .map_err(ProgramTransformerError::DownloadMetadataNotify)?;
             }
         }
         InstructionName::Redeem => {
-            redeem::redeem(parsing_result, bundle, txn, ix_str).await?;
+            redeem::redeem(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?;
         }
         InstructionName::CancelRedeem => {
-            cancel_redeem::cancel_redeem(parsing_result, bundle, txn, ix_str).await?;
+            cancel_redeem::cancel_redeem(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?;
         }
         InstructionName::DecompressV1 => {
             debug!("No action necessary for decompression")
@@ -114,25 +165,65 @@ where
         | InstructionName::UnverifyCreator
         | InstructionName::VerifyCreatorV2
         | InstructionName::UnverifyCreatorV2 => {
-            creator_verification::process(parsing_result, bundle, txn, ix_str).await?;
+            creator_verification::process(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?;
         }
         InstructionName::VerifyCollection
         | InstructionName::UnverifyCollection
         | InstructionName::SetAndVerifyCollection
         | InstructionName::SetCollectionV2 => {
-            collection_verification::process(parsing_result, bundle, txn, ix_str).await?;
+            collection_verification::process(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?;
+        }
+        InstructionName::CreateTree | InstructionName::CreateTreeV2 => {
+            create_tree::create_tree(parsing_result, bundle, txn).await?;
         }
         InstructionName::SetDecompressibleState => (), // Nothing to index.
         InstructionName::UpdateMetadata | InstructionName::UpdateMetadataV2 => {
-            if let Some(info) =
-                update_metadata::update_metadata(parsing_result, bundle, txn, ix_str).await?
+            if let Some(info) = update_metadata::update_metadata(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?
             {
                 download_metadata_notifier(info)
                     .await
                     .map_err(ProgramTransformerError::DownloadMetadataNotify)?;
             }
         }
-        InstructionName::UpdateAssetDataV2 => debug!("Bubblegum: Not Implemented Instruction"),
+        InstructionName::UpdateAssetDataV2 => {
+            update_asset_data::update_asset_data(
+                parsing_result,
+                bundle,
+                txn,
+                ix_str,
+                cl_items_history_seq_window,
+            )
+            .await?;
+        }
+        InstructionName::Unknown
+            if matches!(
+                parsing_result.payload,
+                Some(Payload::SetTreeDelegate { .. })
+            ) =>
+        {
+            set_tree_delegate::set_tree_delegate(parsing_result, bundle, txn).await?;
+        }
         _ => debug!("Bubblegum: Not Implemented Instruction"),
     }
     Ok(())
@@ -156,6 +247,16 @@ const fn bgum_use_method_to_token_metadata_use_method(
     }
 }
 
+const fn asset_data_schema_to_db(
+    schema: &AssetDataSchema,
+) -> sea_orm_active_enums::AssetDataSchema {
+    match schema {
+        AssetDataSchema::Binary => sea_orm_active_enums::AssetDataSchema::Binary,
+        AssetDataSchema::Json => sea_orm_active_enums::AssetDataSchema::Json,
+        AssetDataSchema::MsgPack => sea_orm_active_enums::AssetDataSchema::MsgPack,
+    }
+}
+
 /// A normalized representation of both V1 and V2 leaf schemas,
 /// providing a unified view of all fields.
 ///
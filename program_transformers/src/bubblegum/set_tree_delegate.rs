@@ -0,0 +1,37 @@
+use {
+    crate::{
+        bubblegum::db::upsert_merkle_tree_delegate,
+        error::{ProgramTransformerError, ProgramTransformerResult},
+    },
+    blockbuster::{
+        instruction::InstructionBundle,
+        programs::bubblegum::{BubblegumInstruction, Payload},
+    },
+    sea_orm::{ConnectionTrait, TransactionTrait},
+};
+
+pub async fn set_tree_delegate<'c, T>(
+    parsing_result: &BubblegumInstruction,
+    bundle: &InstructionBundle<'c>,
+    txn: &'c T,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    if let Some(Payload::SetTreeDelegate {
+        tree_id,
+        new_tree_delegate,
+    }) = &parsing_result.payload
+    {
+        return upsert_merkle_tree_delegate(
+            txn,
+            tree_id.to_bytes().to_vec(),
+            new_tree_delegate.to_bytes().to_vec(),
+            bundle.slot as i64,
+        )
+        .await;
+    }
+    Err(ProgramTransformerError::ParsingError(
+        "Ix not parsed correctly".to_string(),
+    ))
+}
@@ -0,0 +1,108 @@
+use {
+    crate::{
+        bubblegum::{
+            asset_data_schema_to_db,
+            db::{
+                insert_asset_leaf_history, save_changelog_event, upsert_asset_bubblegum_asset_data,
+                upsert_asset_with_leaf_info, upsert_asset_with_seq,
+            },
+            NormalizedLeafFields,
+        },
+        error::{ProgramTransformerError, ProgramTransformerResult},
+    },
+    blockbuster::{
+        instruction::InstructionBundle,
+        programs::bubblegum::{BubblegumInstruction, Payload},
+    },
+    sea_orm::{query::*, ConnectionTrait},
+};
+
+pub async fn update_asset_data<'c, T>(
+    parsing_result: &BubblegumInstruction,
+    bundle: &InstructionBundle<'c>,
+    txn: &'c T,
+    instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
+) -> ProgramTransformerResult<()>
+where
+    T: ConnectionTrait + TransactionTrait,
+{
+    if let (
+        Some(le),
+        Some(cl),
+        Some(Payload::UpdateAssetData {
+            asset_data,
+            asset_data_schema,
+            tree_id,
+        }),
+    ) = (
+        &parsing_result.leaf_update,
+        &parsing_result.tree_update,
+        &parsing_result.payload,
+    ) {
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_items_history_seq_window,
+        )
+        .await?;
+
+        let leaf = NormalizedLeafFields::from(&le.schema);
+
+        let id_bytes = leaf.id.to_bytes();
+
+        // Begin a transaction.  If the transaction goes out of scope (i.e. one of the executions has
+        // an error and this function returns it using the `?` operator), then the transaction is
+        // automatically rolled back.
+        let multi_txn = txn.begin().await?;
+
+        // Partial update of asset table with just leaf, which carries the new asset data hash.
+        upsert_asset_with_leaf_info(
+            &multi_txn,
+            id_bytes.to_vec(),
+            leaf.nonce as i64,
+            tree_id.to_bytes().to_vec(),
+            le.leaf_hash.to_vec(),
+            leaf.data_hash,
+            leaf.creator_hash,
+            leaf.collection_hash,
+            leaf.asset_data_hash,
+            leaf.flags,
+            seq as i64,
+        )
+        .await?;
+
+        insert_asset_leaf_history(
+            &multi_txn,
+            &leaf,
+            tree_id.to_bytes().to_vec(),
+            le.leaf_hash.to_vec(),
+            seq as i64,
+            bundle.slot,
+            instruction,
+        )
+        .await?;
+
+        upsert_asset_bubblegum_asset_data(
+            &multi_txn,
+            id_bytes.to_vec(),
+            asset_data.clone(),
+            asset_data_schema.as_ref().map(asset_data_schema_to_db),
+            bundle.slot as i64,
+            seq as i64,
+        )
+        .await?;
+
+        upsert_asset_with_seq(&multi_txn, id_bytes.to_vec(), seq as i64).await?;
+
+        multi_txn.commit().await?;
+
+        return Ok(());
+    }
+    Err(ProgramTransformerError::ParsingError(
+        "Ix not parsed correctly".to_string(),
+    ))
+}
@@ -3,8 +3,9 @@ use {
         bubblegum::{
             bgum_use_method_to_token_metadata_use_method,
             db::{
-                save_changelog_event, upsert_asset_base_info, upsert_asset_creators,
-                upsert_asset_data, upsert_asset_with_leaf_info, upsert_asset_with_seq,
+                insert_asset_leaf_history, save_changelog_event, upsert_asset_base_info,
+                upsert_asset_creators, upsert_asset_data, upsert_asset_with_leaf_info,
+                upsert_asset_with_seq,
             },
             NormalizedLeafFields,
         },
@@ -32,6 +33,7 @@ pub async fn update_metadata<'c, T>(
     bundle: &InstructionBundle<'c>,
     txn: &'c T,
     instruction: &str,
+    cl_items_history_seq_window: Option<u64>,
 ) -> ProgramTransformerResult<Option<DownloadMetadataInfo>>
 where
     T: ConnectionTrait + TransactionTrait,
@@ -49,7 +51,15 @@ where
         &parsing_result.tree_update,
         &parsing_result.payload,
     ) {
-        let seq = save_changelog_event(cl, bundle.slot, bundle.txn_id, txn, instruction).await?;
+        let seq = save_changelog_event(
+            cl,
+            bundle.slot,
+            bundle.txn_id,
+            txn,
+            instruction,
+            cl_items_history_seq_window,
+        )
+        .await?;
 
         let leaf = NormalizedLeafFields::from(&le.schema);
 
@@ -174,6 +184,17 @@ where
         )
         .await?;
 
+        insert_asset_leaf_history(
+            &multi_txn,
+            &leaf,
+            tree_id.to_bytes().to_vec(),
+            le.leaf_hash.to_vec(),
+            seq as i64,
+            bundle.slot,
+            instruction,
+        )
+        .await?;
+
         upsert_asset_with_seq(&multi_txn, id_bytes.to_vec(), seq as i64).await?;
 
         // Upsert creators to `asset_creators` table.
@@ -1,16 +1,19 @@
 use {
     crate::{
+        account_compression::handle_account_compression_account,
+        asset_signatures::{asset_instruction_name, save_asset_signatures},
         bubblegum::handle_bubblegum_instruction,
         error::{ProgramTransformerError, ProgramTransformerResult},
         mpl_core_program::handle_mpl_core_account,
         token::handle_token_program_account,
         token_inscription::handle_token_inscription_program_update,
-        token_metadata::handle_token_metadata_account,
+        token_metadata::{handle_token_metadata_account, handle_token_metadata_instruction},
     },
     blockbuster::{
         instruction::{order_instructions, InstructionBundle, IxPair},
         program_handler::ProgramParser,
         programs::{
+            account_compression::{MplAccountCompressionParser, SplAccountCompressionParser},
             bubblegum::BubblegumParser,
             mpl_core_program::MplCoreParser,
             token_account::{TokenProgramEntity, TokenProgramParser},
@@ -39,6 +42,8 @@ use {
     tracing::{debug, error},
 };
 
+mod account_compression;
+mod asset_signatures;
 mod asset_upserts;
 mod bubblegum;
 pub mod error;
@@ -97,23 +102,34 @@ pub struct ProgramTransformer {
     download_metadata_notifier: DownloadMetadataNotifier,
     parsers: HashMap<Pubkey, Box<dyn ProgramParser>>,
     key_set: HashSet<Pubkey>,
+    cl_items_history_seq_window: Option<u64>,
 }
 
 impl ProgramTransformer {
     pub fn new(pool: PgPool, download_metadata_notifier: DownloadMetadataNotifier) -> Self {
-        let mut parsers: HashMap<Pubkey, Box<dyn ProgramParser>> = HashMap::with_capacity(6);
+        let mut parsers: HashMap<Pubkey, Box<dyn ProgramParser>> = HashMap::with_capacity(8);
         let bgum = BubblegumParser {};
         let token_metadata = TokenMetadataParser {};
         let token = TokenProgramParser {};
         let mpl_core = MplCoreParser {};
         let token_extensions = Token2022ProgramParser {};
         let token_inscription = TokenInscriptionParser {};
+        let spl_account_compression = SplAccountCompressionParser {};
+        let mpl_account_compression = MplAccountCompressionParser {};
         parsers.insert(bgum.key(), Box::new(bgum));
         parsers.insert(token_metadata.key(), Box::new(token_metadata));
         parsers.insert(token.key(), Box::new(token));
         parsers.insert(mpl_core.key(), Box::new(mpl_core));
         parsers.insert(token_extensions.key(), Box::new(token_extensions));
         parsers.insert(token_inscription.key(), Box::new(token_inscription));
+        parsers.insert(
+            spl_account_compression.key(),
+            Box::new(spl_account_compression),
+        );
+        parsers.insert(
+            mpl_account_compression.key(),
+            Box::new(mpl_account_compression),
+        );
         let hs = parsers.iter().fold(HashSet::new(), |mut acc, (k, _)| {
             acc.insert(*k);
             acc
@@ -124,9 +140,18 @@ impl ProgramTransformer {
             download_metadata_notifier,
             parsers,
             key_set: hs,
+            cl_items_history_seq_window: None,
         }
     }
 
+    /// Saves the versions of the tree nodes to `cl_items_history` when set, keeping those needed
+    /// to prove any of the last `seq_window` sequence numbers of a tree. `getAssetProof` can only
+    /// serve `atSeq` for sequence numbers within that history.
+    pub const fn with_cl_items_history(mut self, seq_window: Option<u64>) -> Self {
+        self.cl_items_history_seq_window = seq_window;
+        self
+    }
+
     pub fn break_transaction<'a>(
         &self,
         tx_info: &'a TransactionInfo,
@@ -151,6 +176,7 @@ impl ProgramTransformer {
         let instructions = self.break_transaction(tx_info);
         let mut not_impl = 0;
         let ixlen = instructions.len();
+        let mut asset_instructions = Vec::new();
         debug!("Instructions: {}", ixlen);
         for (outer_ix, inner_ix) in instructions {
             let (program, instruction) = outer_ix;
@@ -171,6 +197,9 @@ impl ProgramTransformer {
                         }
                         acc
                     });
+            if let Some(name) = asset_instruction_name(&program, &instruction.data) {
+                asset_instructions.push((name, ix_accounts.clone()));
+            }
             let ix = InstructionBundle {
                 txn_id: &tx_info.signature.to_string(),
                 program,
@@ -192,6 +221,7 @@ impl ProgramTransformer {
                             &ix,
                             &self.storage,
                             &self.download_metadata_notifier,
+                            self.cl_items_history_seq_window,
                         )
                         .await
                         .map_err(|err| {
@@ -202,6 +232,17 @@ impl ProgramTransformer {
                             err
                         })?;
                     }
+                    ProgramParseResult::TokenMetadataInstruction(parsing_result) => {
+                        handle_token_metadata_instruction(parsing_result, &ix, &self.storage)
+                            .await
+                            .map_err(|err| {
+                                error!(
+                                    "Failed to handle token metadata instruction for txn {:?}: {:?}",
+                                    tx_info.signature, err
+                                );
+                                err
+                            })?;
+                    }
                     ProgramParseResult::TokenProgramEntity(parsing_result) => {
                         if let TokenProgramEntity::CloseIx(acc_to_close) = parsing_result {
                             handle_token_program_close_ix(acc_to_close, &self.storage).await;
@@ -220,6 +261,24 @@ impl ProgramTransformer {
             }
         }
 
+        // The signatures are an index on top of the asset state, failing to save them must not
+        // fail the transaction.
+        if !asset_instructions.is_empty() {
+            if let Err(err) = save_asset_signatures(
+                tx_info.signature.as_ref(),
+                tx_info.slot,
+                &asset_instructions,
+                &self.storage,
+            )
+            .await
+            {
+                error!(
+                    "Failed to save asset signatures for txn {:?}: {:?}",
+                    tx_info.signature, err
+                );
+            }
+        }
+
         if not_impl == ixlen {
             debug!(
                 "Not implemented for transaction signature: {:?}",
@@ -275,6 +334,10 @@ impl ProgramTransformer {
                     )
                     .await
                 }
+                ProgramParseResult::ConcurrentMerkleTree(parsing_result) => {
+                    handle_account_compression_account(account_info, parsing_result, &self.storage)
+                        .await
+                }
                 _ => Err(ProgramTransformerError::NotImplemented),
             }?;
         }
@@ -5,10 +5,13 @@ use {
             AssetMintAccountColumns, AssetTokenAccountColumns,
         },
         error::{ProgramTransformerError, ProgramTransformerResult},
+        token_metadata::link_token_record,
         AccountInfo,
     },
     blockbuster::programs::token_account::TokenProgramEntity,
     digital_asset_types::dao::{
+        asset,
+        sea_orm_active_enums::SpecificationAssetClass,
         token_accounts,
         tokens::{self, IsNonFungible},
     },
@@ -94,6 +97,17 @@ pub async fn handle_token_program_account<'a, 'b>(
                 .await?;
 
                 txn.commit().await?;
+
+                // Programmable NFT token accounts stay frozen, their lock state lives in the
+                // token record derived from the holding token account.
+                if frozen && ta.amount == 1 {
+                    let asset = asset::Entity::find_by_id(mint.clone()).one(db).await?;
+                    if asset.and_then(|a| a.specification_asset_class)
+                        == Some(SpecificationAssetClass::ProgrammableNft)
+                    {
+                        link_token_record(ta.mint, account_info.pubkey, db).await?;
+                    }
+                }
             }
 
             Ok(())
@@ -118,6 +132,7 @@ pub async fn handle_token_program_account<'a, 'b>(
                 mint_authority: ActiveValue::Set(mint_auth),
                 freeze_authority: ActiveValue::Set(freeze_auth),
                 extensions: ActiveValue::Set(None),
+                ..Default::default()
             };
 
             let mut query = tokens::Entity::insert(model)
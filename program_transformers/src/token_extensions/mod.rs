@@ -8,21 +8,23 @@ use {
         filter_non_null_fields, AccountInfo, DownloadMetadataInfo, DownloadMetadataNotifier,
     },
     blockbuster::programs::token_extensions::{
-        extension::ShadowMetadata, MintAccount, TokenAccount, TokenExtensionsProgramEntity,
+        extension::{ShadowMetadata, ShadowTokenGroup, ShadowTokenGroupMember},
+        MintAccount, TokenAccount, TokenExtensionsProgramEntity,
     },
     digital_asset_types::dao::{
-        asset, asset_data,
+        asset, asset_authority, asset_data, asset_grouping,
+        extensions::asset_grouping::TOKEN_GROUP_KEY,
         sea_orm_active_enums::ChainMutability,
-        token_accounts,
+        token_accounts, token_groups,
         tokens::{self, IsNonFungible as IsNonFungibleModel},
     },
     sea_orm::{
-        entity::ActiveValue, query::QueryTrait, sea_query::query::OnConflict, ConnectionTrait,
-        DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, EntityTrait, Set,
-        TransactionTrait,
+        entity::ActiveValue, query::QueryTrait, sea_query::query::OnConflict, ColumnTrait,
+        ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, EntityTrait,
+        QueryFilter, Set, TransactionTrait,
     },
     serde_json::Value,
-    solana_sdk::program_option::COption,
+    solana_sdk::{program_option::COption, pubkey::Pubkey},
     spl_token_2022::state::AccountState,
     tracing::warn,
 };
@@ -146,6 +148,7 @@ pub async fn handle_token_extensions_program_account<'a, 'b, 'c>(
                 COption::Some(d) => Some(d.to_bytes().to_vec()),
                 COption::None => None,
             };
+            let transfer_fee_config = extensions.transfer_fee_config.as_ref();
             let model = tokens::ActiveModel {
                 mint: ActiveValue::Set(account_key.clone()),
                 token_program: ActiveValue::Set(account_owner),
@@ -157,6 +160,31 @@ pub async fn handle_token_extensions_program_account<'a, 'b, 'c>(
                 mint_authority: ActiveValue::Set(mint_auth),
                 freeze_authority: ActiveValue::Set(freeze_auth),
                 extensions: ActiveValue::Set(mint_extensions.clone()),
+                transfer_hook_program: ActiveValue::Set(
+                    extensions
+                        .transfer_hook
+                        .and_then(|hook| Option::<Pubkey>::from(hook.program_id))
+                        .map(|program| program.to_bytes().to_vec()),
+                ),
+                permanent_delegate: ActiveValue::Set(
+                    extensions
+                        .permanent_delegate
+                        .and_then(|delegate| Option::<Pubkey>::from(delegate.delegate))
+                        .map(|delegate| delegate.to_bytes().to_vec()),
+                ),
+                non_transferable: ActiveValue::Set(extensions.non_transferable.unwrap_or(false)),
+                // Which of the two fees applies depends on the current epoch, so both are kept and
+                // the fee is resolved at query time.
+                older_transfer_fee_basis_points: ActiveValue::Set(transfer_fee_config.map(
+                    |config| u16::from(config.older_transfer_fee.transfer_fee_basis_points) as i32,
+                )),
+                newer_transfer_fee_basis_points: ActiveValue::Set(transfer_fee_config.map(
+                    |config| u16::from(config.newer_transfer_fee.transfer_fee_basis_points) as i32,
+                )),
+                newer_transfer_fee_epoch: ActiveValue::Set(
+                    transfer_fee_config
+                        .map(|config| u64::from(config.newer_transfer_fee.epoch) as i64),
+                ),
             };
 
             let mut query = tokens::Entity::insert(model)
@@ -172,6 +200,12 @@ pub async fn handle_token_extensions_program_account<'a, 'b, 'c>(
                             tokens::Column::Decimals,
                             tokens::Column::FreezeAuthority,
                             tokens::Column::Extensions,
+                            tokens::Column::TransferHookProgram,
+                            tokens::Column::PermanentDelegate,
+                            tokens::Column::NonTransferable,
+                            tokens::Column::OlderTransferFeeBasisPoints,
+                            tokens::Column::NewerTransferFeeBasisPoints,
+                            tokens::Column::NewerTransferFeeEpoch,
                         ])
                         .to_owned(),
                 )
@@ -206,12 +240,114 @@ pub async fn handle_token_extensions_program_account<'a, 'b, 'c>(
                 }
             }
 
+            if let Some(group) = &extensions.token_group {
+                upsert_token_group(group, account_info.pubkey, slot, db).await?;
+            }
+
+            if let Some(member) = &extensions.token_group_member {
+                upsert_token_group_member(member, account_info.pubkey, slot, db).await?;
+            }
+
             Ok(())
         }
         _ => Err(ProgramTransformerError::NotImplemented),
     }
 }
 
+async fn upsert_token_group(
+    group: &ShadowTokenGroup,
+    mint: Pubkey,
+    slot: i64,
+    db: &DatabaseConnection,
+) -> ProgramTransformerResult<()> {
+    // The group extension lives in the group mint itself, so the group address is the mint.
+    if group.mint != mint.to_string() {
+        warn!(
+            "Token group in mint {} belongs to mint {}",
+            mint, group.mint
+        );
+        return Ok(());
+    }
+
+    let model = token_groups::ActiveModel {
+        pubkey: ActiveValue::Set(mint.to_bytes().to_vec()),
+        mint: ActiveValue::Set(mint.to_bytes().to_vec()),
+        update_authority: ActiveValue::Set(
+            Option::<Pubkey>::from(group.update_authority).map(|a| a.to_bytes().to_vec()),
+        ),
+        size: ActiveValue::Set(u32::from(group.size) as i64),
+        max_size: ActiveValue::Set(u32::from(group.max_size) as i64),
+        slot_updated: ActiveValue::Set(slot),
+    };
+    let mut query = token_groups::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([token_groups::Column::Pubkey])
+                .update_columns([
+                    token_groups::Column::UpdateAuthority,
+                    token_groups::Column::Size,
+                    token_groups::Column::MaxSize,
+                    token_groups::Column::SlotUpdated,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    query.sql = format!(
+        "{} WHERE excluded.slot_updated >= token_groups.slot_updated",
+        query.sql
+    );
+    db.execute(query).await?;
+
+    Ok(())
+}
+
+async fn upsert_token_group_member(
+    member: &ShadowTokenGroupMember,
+    mint: Pubkey,
+    slot: i64,
+    db: &DatabaseConnection,
+) -> ProgramTransformerResult<()> {
+    if member.mint != mint.to_string() {
+        warn!(
+            "Token group member in mint {} belongs to mint {}",
+            mint, member.mint
+        );
+        return Ok(());
+    }
+
+    // Members can only be initialized with the signature of the group update authority.
+    let model = asset_grouping::ActiveModel {
+        asset_id: ActiveValue::Set(mint.to_bytes().to_vec()),
+        group_key: ActiveValue::Set(TOKEN_GROUP_KEY.to_string()),
+        group_value: ActiveValue::Set(Some(member.group.clone())),
+        verified: ActiveValue::Set(true),
+        group_info_seq: ActiveValue::Set(Some(0)),
+        slot_updated: ActiveValue::Set(Some(slot)),
+        ..Default::default()
+    };
+    let mut query = asset_grouping::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([
+                asset_grouping::Column::AssetId,
+                asset_grouping::Column::GroupKey,
+            ])
+            .update_columns([
+                asset_grouping::Column::GroupValue,
+                asset_grouping::Column::Verified,
+                asset_grouping::Column::SlotUpdated,
+                asset_grouping::Column::GroupInfoSeq,
+            ])
+            .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    query.sql = format!(
+        "{} WHERE excluded.slot_updated >= asset_grouping.slot_updated",
+        query.sql
+    );
+    db.execute(query).await?;
+
+    Ok(())
+}
+
 async fn upsert_asset_data(
     metadata: &ShadowMetadata,
     key_bytes: Vec<u8>,
@@ -220,11 +356,18 @@ async fn upsert_asset_data(
 ) -> ProgramTransformerResult<Option<DownloadMetadataInfo>> {
     let metadata_json = serde_json::to_value(metadata.clone())
         .map_err(|e| ProgramTransformerError::SerializatonError(e.to_string()))?;
+    let update_authority = Option::<Pubkey>::from(metadata.update_authority);
+    // Token-2022 metadata without an update authority can no longer be changed.
+    let chain_data_mutability = if update_authority.is_some() {
+        ChainMutability::Mutable
+    } else {
+        ChainMutability::Immutable
+    };
     let asset_data_model = asset_data::ActiveModel {
         metadata_url: ActiveValue::Set(metadata.uri.clone()),
         metadata: ActiveValue::Set(Value::String("processing".to_string())),
         id: ActiveValue::Set(key_bytes.clone()),
-        chain_data_mutability: ActiveValue::Set(ChainMutability::Mutable),
+        chain_data_mutability: ActiveValue::Set(chain_data_mutability),
         chain_data: ActiveValue::Set(metadata_json),
         slot_updated: ActiveValue::Set(slot),
         base_info_seq: ActiveValue::Set(Some(0)),
@@ -263,6 +406,8 @@ async fn upsert_asset_data(
     )
     .await?;
 
+    upsert_asset_authority(key_bytes.clone(), update_authority, slot, &txn).await?;
+
     txn.commit().await?;
 
     if metadata.uri.is_empty() {
@@ -279,6 +424,55 @@ async fn upsert_asset_data(
     }))
 }
 
+async fn upsert_asset_authority(
+    mint: Vec<u8>,
+    update_authority: Option<Pubkey>,
+    slot: i64,
+    db: &DatabaseTransaction,
+) -> Result<(), DbErr> {
+    // Immutable metadata keeps the last known authority rather than removing the row, which may
+    // also belong to Token Metadata.
+    let Some(update_authority) = update_authority else {
+        return Ok(());
+    };
+
+    // Token Metadata owns the authority of mints that also have a Metaplex metadata account.
+    let has_metaplex_metadata = asset::Entity::find_by_id(mint.clone())
+        .filter(asset::Column::SpecificationVersion.is_not_null())
+        .one(db)
+        .await?
+        .is_some();
+    if has_metaplex_metadata {
+        return Ok(());
+    }
+
+    let model = asset_authority::ActiveModel {
+        asset_id: ActiveValue::Set(mint),
+        authority: ActiveValue::Set(update_authority.to_bytes().to_vec()),
+        seq: ActiveValue::Set(0),
+        slot_updated: ActiveValue::Set(slot),
+        ..Default::default()
+    };
+    let mut query = asset_authority::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([asset_authority::Column::AssetId])
+                .update_columns([
+                    asset_authority::Column::Authority,
+                    asset_authority::Column::Seq,
+                    asset_authority::Column::SlotUpdated,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+    query.sql = format!(
+        "{} WHERE excluded.slot_updated >= asset_authority.slot_updated",
+        query.sql
+    );
+    db.execute(query).await?;
+
+    Ok(())
+}
+
 struct AssetMetadataAccountCols {
     mint: Vec<u8>,
     slot_updated_metadata_account: i64,
@@ -36,6 +36,7 @@ pub async fn handle_token_inscription_program_update<'a, 'b>(
         slot_updated: ActiveValue::Set(account_info.slot as i64),
         initialized: ActiveValue::Set(true),
         attachment_type: ActiveValue::Set(V1AccountAttachments::TokenInscription),
+        ..Default::default()
     };
 
     let mut query = asset_v1_account_attachments::Entity::insert(model)
@@ -3,21 +3,33 @@ use {
         error::{ProgramTransformerError, ProgramTransformerResult},
         token_metadata::{
             master_edition::{save_v1_master_edition, save_v2_master_edition},
+            records::{
+                close_authority_record, close_token_record, link_authority_record,
+                save_collection_authority_record, save_edition_marker,
+                save_metadata_delegate_record, save_token_record, save_use_authority_record,
+            },
             v1_asset::{burn_v1_asset, save_v1_asset},
         },
         AccountInfo, DownloadMetadataNotifier,
     },
     blockbuster::{
-        programs::token_metadata::{TokenMetadataAccountData, TokenMetadataAccountState},
+        instruction::InstructionBundle,
+        programs::token_metadata::{
+            TokenMetadataAccountData, TokenMetadataAccountState, TokenMetadataInstruction,
+        },
         token_metadata::types::TokenStandard,
     },
+    digital_asset_types::dao::sea_orm_active_enums::V1AccountAttachments,
     master_edition::save_edition,
     sea_orm::{DatabaseConnection, TransactionTrait},
 };
 
 mod master_edition;
+mod records;
 mod v1_asset;
 
+pub(crate) use records::link_token_record;
+
 pub async fn handle_token_metadata_account<'a, 'b>(
     account_info: &AccountInfo,
     parsing_result: &'a TokenMetadataAccountState,
@@ -26,7 +38,12 @@ pub async fn handle_token_metadata_account<'a, 'b>(
 ) -> ProgramTransformerResult<()> {
     match &parsing_result.data {
         TokenMetadataAccountData::EmptyAccount => {
-            burn_v1_asset(db, account_info.pubkey, account_info.slot).await?;
+            let closed_record = close_authority_record(account_info.pubkey, account_info.slot, db)
+                .await?
+                || close_token_record(account_info.pubkey, account_info.slot, db).await?;
+            if !closed_record {
+                burn_v1_asset(db, account_info.pubkey, account_info.slot).await?;
+            }
             Ok(())
         }
         TokenMetadataAccountData::MasterEditionV1(m) => {
@@ -55,11 +72,60 @@ pub async fn handle_token_metadata_account<'a, 'b>(
             txn.commit().await?;
             Ok(())
         }
+        TokenMetadataAccountData::EditionMarker(m) => {
+            save_edition_marker(account_info.pubkey, account_info.slot, m, db).await
+        }
+        TokenMetadataAccountData::UseAuthorityRecord(r) => {
+            save_use_authority_record(account_info.pubkey, account_info.slot, r, db).await
+        }
+        TokenMetadataAccountData::CollectionAuthorityRecord(r) => {
+            save_collection_authority_record(account_info.pubkey, account_info.slot, r, db).await
+        }
+        TokenMetadataAccountData::TokenRecord(r) => {
+            save_token_record(account_info.pubkey, account_info.slot, r, db).await
+        }
+        TokenMetadataAccountData::MetadataDelegateRecord(r) => {
+            save_metadata_delegate_record(account_info.pubkey, account_info.slot, r, db).await
+        }
+    }
+}
 
-        // TokenMetadataAccountData::EditionMarker(_) => {}
-        // TokenMetadataAccountData::UseAuthorityRecord(_) => {}
-        // TokenMetadataAccountData::CollectionAuthorityRecord(_) => {}
-        _ => Err(ProgramTransformerError::NotImplemented),
+pub async fn handle_token_metadata_instruction<'a, 'b>(
+    parsing_result: &'a TokenMetadataInstruction,
+    bundle: &'b InstructionBundle<'b>,
+    db: &DatabaseConnection,
+) -> ProgramTransformerResult<()> {
+    match parsing_result {
+        TokenMetadataInstruction::ApproveUseAuthority {
+            use_authority_record,
+            mint,
+            user,
+        } => {
+            link_authority_record(
+                V1AccountAttachments::UseAuthorityRecord,
+                *use_authority_record,
+                *mint,
+                *user,
+                bundle.slot,
+                db,
+            )
+            .await
+        }
+        TokenMetadataInstruction::ApproveCollectionAuthority {
+            collection_authority_record,
+            mint,
+            new_collection_authority,
+        } => {
+            link_authority_record(
+                V1AccountAttachments::CollectionAuthorityRecord,
+                *collection_authority_record,
+                *mint,
+                *new_collection_authority,
+                bundle.slot,
+                db,
+            )
+            .await
+        }
     }
 }
 
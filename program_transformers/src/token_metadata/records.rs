@@ -0,0 +1,434 @@
+use {
+    crate::error::{ProgramTransformerError, ProgramTransformerResult},
+    blockbuster::token_metadata::{
+        accounts::{
+            CollectionAuthorityRecord, EditionMarker, MetadataDelegateRecord, TokenRecord,
+            UseAuthorityRecord,
+        },
+        types::{TokenDelegateRole, TokenState},
+    },
+    digital_asset_types::dao::{
+        asset, asset_v1_account_attachments,
+        sea_orm_active_enums::{self, V1AccountAttachments},
+        token_accounts, token_records,
+    },
+    sea_orm::{
+        entity::{ActiveValue, ColumnTrait, EntityTrait},
+        query::{JsonValue as Json, QueryFilter, QueryTrait},
+        sea_query::{query::OnConflict, Expr},
+        ConnectionTrait, DbBackend,
+    },
+    serde::Serialize,
+    solana_sdk::pubkey::Pubkey,
+    tracing::warn,
+};
+
+pub async fn save_edition_marker<T: ConnectionTrait>(
+    id: Pubkey,
+    slot: u64,
+    marker: &EditionMarker,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    save_record(V1AccountAttachments::EditionMarker, id, slot, marker, conn).await
+}
+
+pub async fn save_use_authority_record<T: ConnectionTrait>(
+    id: Pubkey,
+    slot: u64,
+    record: &UseAuthorityRecord,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    save_record(
+        V1AccountAttachments::UseAuthorityRecord,
+        id,
+        slot,
+        record,
+        conn,
+    )
+    .await
+}
+
+pub async fn save_collection_authority_record<T: ConnectionTrait>(
+    id: Pubkey,
+    slot: u64,
+    record: &CollectionAuthorityRecord,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    save_record(
+        V1AccountAttachments::CollectionAuthorityRecord,
+        id,
+        slot,
+        record,
+        conn,
+    )
+    .await
+}
+
+async fn save_record<T: ConnectionTrait, D: Serialize>(
+    attachment_type: V1AccountAttachments,
+    id: Pubkey,
+    slot: u64,
+    data: &D,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    let ser = serde_json::to_value(data)
+        .map_err(|e| ProgramTransformerError::SerializatonError(e.to_string()))?;
+
+    let model = asset_v1_account_attachments::ActiveModel {
+        id: ActiveValue::Set(id.to_bytes().to_vec()),
+        attachment_type: ActiveValue::Set(attachment_type),
+        data: ActiveValue::Set(Some(ser)),
+        slot_updated: ActiveValue::Set(slot as i64),
+        ..Default::default()
+    };
+
+    let mut query = asset_v1_account_attachments::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([asset_v1_account_attachments::Column::Id])
+                .update_columns([
+                    asset_v1_account_attachments::Column::AttachmentType,
+                    asset_v1_account_attachments::Column::Data,
+                    asset_v1_account_attachments::Column::SlotUpdated,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+
+    query.sql = format!(
+        "{} WHERE excluded.slot_updated >= asset_v1_account_attachments.slot_updated",
+        query.sql
+    );
+
+    conn.execute(query).await?;
+    Ok(())
+}
+
+pub async fn save_metadata_delegate_record<T: ConnectionTrait>(
+    id: Pubkey,
+    slot: u64,
+    record: &MetadataDelegateRecord,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    let ser = serde_json::to_value(record)
+        .map_err(|e| ProgramTransformerError::SerializatonError(e.to_string()))?;
+
+    let model = asset_v1_account_attachments::ActiveModel {
+        id: ActiveValue::Set(id.to_bytes().to_vec()),
+        asset_id: ActiveValue::Set(Some(record.mint.to_bytes().to_vec())),
+        authority: ActiveValue::Set(Some(record.delegate.to_bytes().to_vec())),
+        attachment_type: ActiveValue::Set(V1AccountAttachments::MetadataDelegateRecord),
+        data: ActiveValue::Set(Some(ser)),
+        slot_updated: ActiveValue::Set(slot as i64),
+        ..Default::default()
+    };
+
+    let mut query = asset_v1_account_attachments::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([asset_v1_account_attachments::Column::Id])
+                .update_columns([
+                    asset_v1_account_attachments::Column::AssetId,
+                    asset_v1_account_attachments::Column::Authority,
+                    asset_v1_account_attachments::Column::AttachmentType,
+                    asset_v1_account_attachments::Column::Data,
+                    asset_v1_account_attachments::Column::SlotUpdated,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+
+    query.sql = format!(
+        "{} WHERE excluded.slot_updated >= asset_v1_account_attachments.slot_updated",
+        query.sql
+    );
+
+    conn.execute(query).await?;
+    Ok(())
+}
+
+/// Links a use or collection authority record to the mint and authority it was approved for.
+/// Only the approving instruction knows them, so the record may not have been indexed yet and
+/// is inserted without data in that case.
+pub async fn link_authority_record<T: ConnectionTrait>(
+    attachment_type: V1AccountAttachments,
+    id: Pubkey,
+    mint: Pubkey,
+    authority: Pubkey,
+    slot: u64,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    let expected = match attachment_type {
+        V1AccountAttachments::UseAuthorityRecord => UseAuthorityRecord::find_pda(&mint, &authority),
+        V1AccountAttachments::CollectionAuthorityRecord => {
+            CollectionAuthorityRecord::find_pda(&mint, &authority)
+        }
+        _ => return Err(ProgramTransformerError::NotImplemented),
+    }
+    .0;
+    if expected != id {
+        warn!(
+            "Authority record {} does not match mint {} and authority {}",
+            id, mint, authority
+        );
+        return Ok(());
+    }
+
+    let model = asset_v1_account_attachments::ActiveModel {
+        id: ActiveValue::Set(id.to_bytes().to_vec()),
+        asset_id: ActiveValue::Set(Some(mint.to_bytes().to_vec())),
+        authority: ActiveValue::Set(Some(authority.to_bytes().to_vec())),
+        attachment_type: ActiveValue::Set(attachment_type),
+        slot_updated: ActiveValue::Set(slot as i64),
+        ..Default::default()
+    };
+
+    // The mint and authority are part of the record address, so they never change.
+    let query = asset_v1_account_attachments::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([asset_v1_account_attachments::Column::Id])
+                .update_columns([
+                    asset_v1_account_attachments::Column::AssetId,
+                    asset_v1_account_attachments::Column::Authority,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+
+    conn.execute(query).await?;
+    Ok(())
+}
+
+/// Clears the data of a closed use, collection or metadata delegate record. Returns `false` when
+/// `id` is not a known authority record.
+pub async fn close_authority_record<T: ConnectionTrait>(
+    id: Pubkey,
+    slot: u64,
+    conn: &T,
+) -> ProgramTransformerResult<bool> {
+    let record = asset_v1_account_attachments::Entity::find_by_id(id.to_bytes().to_vec())
+        .one(conn)
+        .await?;
+    if !matches!(
+        record.map(|record| record.attachment_type),
+        Some(
+            V1AccountAttachments::UseAuthorityRecord
+                | V1AccountAttachments::CollectionAuthorityRecord
+                | V1AccountAttachments::MetadataDelegateRecord
+        )
+    ) {
+        return Ok(false);
+    }
+
+    asset_v1_account_attachments::Entity::update_many()
+        .filter(asset_v1_account_attachments::Column::Id.eq(id.to_bytes().to_vec()))
+        .filter(asset_v1_account_attachments::Column::SlotUpdated.lte(slot as i64))
+        .col_expr(
+            asset_v1_account_attachments::Column::Data,
+            Expr::value(Option::<Json>::None),
+        )
+        .col_expr(
+            asset_v1_account_attachments::Column::SlotUpdated,
+            Expr::value(slot as i64),
+        )
+        .exec(conn)
+        .await?;
+    Ok(true)
+}
+
+pub async fn save_token_record<T: ConnectionTrait>(
+    id: Pubkey,
+    slot: u64,
+    record: &TokenRecord,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    let model = token_records::ActiveModel {
+        pubkey: ActiveValue::Set(id.to_bytes().to_vec()),
+        state: ActiveValue::Set(Some(token_state_to_db(&record.state))),
+        delegate: ActiveValue::Set(record.delegate.map(|d| d.to_bytes().to_vec())),
+        delegate_role: ActiveValue::Set(record.delegate_role.as_ref().map(delegate_role_to_db)),
+        locked_transfer: ActiveValue::Set(record.locked_transfer.map(|l| l.to_bytes().to_vec())),
+        rule_set_revision: ActiveValue::Set(record.rule_set_revision.map(|r| r as i64)),
+        slot_updated: ActiveValue::Set(slot as i64),
+        ..Default::default()
+    };
+
+    let mut query = token_records::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([token_records::Column::Pubkey])
+                .update_columns([
+                    token_records::Column::State,
+                    token_records::Column::Delegate,
+                    token_records::Column::DelegateRole,
+                    token_records::Column::LockedTransfer,
+                    token_records::Column::RuleSetRevision,
+                    token_records::Column::SlotUpdated,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+
+    query.sql = format!(
+        "{} WHERE excluded.slot_updated >= token_records.slot_updated",
+        query.sql
+    );
+
+    conn.execute(query).await?;
+    sync_asset_token_record(id, conn).await
+}
+
+/// Links the token record of a programmable NFT to its mint and token account. The record only
+/// stores its state, so this is called from both the token account and the metadata account,
+/// whichever gets indexed last. A placeholder record starts at slot 0 so it never shadows the
+/// state saved from the record account itself.
+pub async fn link_token_record<T: ConnectionTrait>(
+    mint: Pubkey,
+    token_account: Pubkey,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    let id = TokenRecord::find_pda(&mint, &token_account).0;
+
+    let model = token_records::ActiveModel {
+        pubkey: ActiveValue::Set(id.to_bytes().to_vec()),
+        mint: ActiveValue::Set(Some(mint.to_bytes().to_vec())),
+        token_account: ActiveValue::Set(Some(token_account.to_bytes().to_vec())),
+        slot_updated: ActiveValue::Set(0),
+        ..Default::default()
+    };
+
+    // The mint and token account are part of the record address, so they never change.
+    let query = token_records::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([token_records::Column::Pubkey])
+                .update_columns([
+                    token_records::Column::Mint,
+                    token_records::Column::TokenAccount,
+                ])
+                .to_owned(),
+        )
+        .build(DbBackend::Postgres);
+
+    conn.execute(query).await?;
+    sync_asset_token_record(id, conn).await
+}
+
+/// Links the token record of the account currently holding a programmable NFT.
+pub async fn link_holder_token_record<T: ConnectionTrait>(
+    mint: Pubkey,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    let holder = token_accounts::Entity::find()
+        .filter(token_accounts::Column::Mint.eq(mint.to_bytes().to_vec()))
+        .filter(token_accounts::Column::Amount.gt(0))
+        .one(conn)
+        .await?;
+    if let Some(holder) = holder {
+        let token_account = Pubkey::try_from(holder.pubkey)
+            .map_err(|_| ProgramTransformerError::ParsingError("Invalid pubkey".to_string()))?;
+        link_token_record(mint, token_account, conn).await?;
+    }
+    Ok(())
+}
+
+/// Copies the lock state and delegate role of a token record to its asset, once the record is
+/// linked and its token account still holds the asset.
+async fn sync_asset_token_record<T: ConnectionTrait>(
+    id: Pubkey,
+    conn: &T,
+) -> ProgramTransformerResult<()> {
+    let record = token_records::Entity::find_by_id(id.to_bytes().to_vec())
+        .one(conn)
+        .await?;
+    let (mint, token_account, state, delegate_role) = match record {
+        Some(token_records::Model {
+            mint: Some(mint),
+            token_account: Some(token_account),
+            state: Some(state),
+            delegate_role,
+            ..
+        }) => (mint, token_account, state, delegate_role),
+        _ => return Ok(()),
+    };
+
+    let holder = token_accounts::Entity::find_by_id(token_account)
+        .filter(token_accounts::Column::Mint.eq(mint.clone()))
+        .filter(token_accounts::Column::Amount.gt(0))
+        .one(conn)
+        .await?;
+    if holder.is_none() {
+        return Ok(());
+    }
+
+    asset::Entity::update_many()
+        .filter(asset::Column::Id.eq(mint))
+        .set(asset::ActiveModel {
+            token_state: ActiveValue::Set(Some(state)),
+            token_delegate_role: ActiveValue::Set(delegate_role),
+            ..Default::default()
+        })
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+/// Clears the state of a closed token record and of the asset it was synced to. Returns `false`
+/// when `id` is not a known token record.
+pub async fn close_token_record<T: ConnectionTrait>(
+    id: Pubkey,
+    slot: u64,
+    conn: &T,
+) -> ProgramTransformerResult<bool> {
+    let result = token_records::Entity::update_many()
+        .filter(token_records::Column::Pubkey.eq(id.to_bytes().to_vec()))
+        .filter(token_records::Column::SlotUpdated.lte(slot as i64))
+        .set(token_records::ActiveModel {
+            state: ActiveValue::Set(None),
+            slot_updated: ActiveValue::Set(slot as i64),
+            ..Default::default()
+        })
+        .exec(conn)
+        .await?;
+    if result.rows_affected == 0 {
+        return Ok(false);
+    }
+
+    // The asset falls back to its current holder's record, if that one is already indexed.
+    let record = token_records::Entity::find_by_id(id.to_bytes().to_vec())
+        .one(conn)
+        .await?;
+    if let Some(mint) = record.and_then(|record| record.mint) {
+        asset::Entity::update_many()
+            .filter(asset::Column::Id.eq(mint.clone()))
+            .set(asset::ActiveModel {
+                token_state: ActiveValue::Set(None),
+                token_delegate_role: ActiveValue::Set(None),
+                ..Default::default()
+            })
+            .exec(conn)
+            .await?;
+        let mint = Pubkey::try_from(mint)
+            .map_err(|_| ProgramTransformerError::ParsingError("Invalid pubkey".to_string()))?;
+        link_holder_token_record(mint, conn).await?;
+    }
+    Ok(true)
+}
+
+const fn token_state_to_db(state: &TokenState) -> sea_orm_active_enums::TokenState {
+    match state {
+        TokenState::Unlocked => sea_orm_active_enums::TokenState::Unlocked,
+        TokenState::Locked => sea_orm_active_enums::TokenState::Locked,
+        TokenState::Listed => sea_orm_active_enums::TokenState::Listed,
+    }
+}
+
+const fn delegate_role_to_db(role: &TokenDelegateRole) -> sea_orm_active_enums::TokenDelegateRole {
+    match role {
+        TokenDelegateRole::Sale => sea_orm_active_enums::TokenDelegateRole::Sale,
+        TokenDelegateRole::Transfer => sea_orm_active_enums::TokenDelegateRole::Transfer,
+        TokenDelegateRole::Utility => sea_orm_active_enums::TokenDelegateRole::Utility,
+        TokenDelegateRole::Staking => sea_orm_active_enums::TokenDelegateRole::Staking,
+        TokenDelegateRole::Standard => sea_orm_active_enums::TokenDelegateRole::Standard,
+        TokenDelegateRole::LockedTransfer => {
+            sea_orm_active_enums::TokenDelegateRole::LockedTransfer
+        }
+        TokenDelegateRole::Migration => sea_orm_active_enums::TokenDelegateRole::Migration,
+    }
+}
@@ -1,5 +1,5 @@
 use {
-    super::IsNonFungibe,
+    super::{records::link_holder_token_record, IsNonFungibe},
     crate::{
         asset_upserts::{
             upsert_assets_metadata_account_columns, upsert_assets_mint_account_columns,
@@ -242,6 +242,7 @@ pub async fn save_v1_asset<T: ConnectionTrait + TransactionTrait>(
         raw_name: ActiveValue::Set(Some(name.to_vec())),
         raw_symbol: ActiveValue::Set(Some(symbol.to_vec())),
         base_info_seq: ActiveValue::Set(Some(0)),
+        ..Default::default()
     };
     let txn = conn.begin().await?;
     let mut query = asset_data::Entity::insert(asset_data_model)
@@ -269,6 +270,7 @@ pub async fn save_v1_asset<T: ConnectionTrait + TransactionTrait>(
         .await
         .map_err(|db_err| ProgramTransformerError::AssetIndexError(db_err.to_string()))?;
 
+    let is_programmable = class == SpecificationAssetClass::ProgrammableNft;
     upsert_assets_metadata_account_columns(
         AssetMetadataAccountColumns {
             mint: mint_pubkey_vec.clone(),
@@ -415,6 +417,10 @@ pub async fn save_v1_asset<T: ConnectionTrait + TransactionTrait>(
         upsert_asset_v1_account_attachments(conn, &mint_pubkey, slot).await?;
     }
 
+    if is_programmable {
+        link_holder_token_record(mint_pubkey, conn).await?;
+    }
+
     if uri.is_empty() {
         warn!(
             "URI is empty for mint {}. Skipping background task.",